@@ -1,12 +0,0 @@
-CREATE TABLE IF NOT EXISTS programs (
-    id TEXT PRIMARY KEY NOT NULL,
-    title TEXT NOT NULL,
-    description TEXT NOT NULL DEFAULT '',
-    target_days INTEGER NOT NULL DEFAULT 40,
-    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'paused', 'completed', 'archived')),
-    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
-    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
-);
-
-CREATE INDEX idx_programs_status ON programs(status);
-CREATE INDEX idx_programs_created_at ON programs(created_at);
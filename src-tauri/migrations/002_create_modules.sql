@@ -1,14 +0,0 @@
-CREATE TABLE IF NOT EXISTS modules (
-    id TEXT PRIMARY KEY NOT NULL,
-    program_id TEXT NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
-    title TEXT NOT NULL,
-    description TEXT NOT NULL DEFAULT '',
-    order_index INTEGER NOT NULL DEFAULT 0,
-    color TEXT NOT NULL DEFAULT '#6366F1',
-    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
-    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
-    UNIQUE(program_id, order_index)
-);
-
-CREATE INDEX idx_modules_program ON modules(program_id);
-CREATE INDEX idx_modules_order ON modules(program_id, order_index);
@@ -0,0 +1,146 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::artifact_store::{ArtifactStore, BlobStatus};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::State;
+use uuid::Uuid;
+
+// Artifact Store Commands (3 commands)
+
+#[tauri::command]
+pub async fn import_artifact_file(
+    pool: State<'_, SqlitePool>,
+    store: State<'_, ArtifactStore>,
+    input: ImportArtifactFileInput,
+) -> Result<ImportedArtifact, String> {
+    if let Some(artifact_type) = &input.artifact_type {
+        if artifact_type != "file" && artifact_type != "screenshot" {
+            return Err(AppError::Validation(format!(
+                "Only file and screenshot artifacts can be imported, not {}",
+                artifact_type
+            ))
+            .into());
+        }
+    }
+
+    let exists: Option<i32> = sqlx::query_scalar("SELECT 1 FROM day_attempts WHERE id = ?")
+        .bind(&input.day_attempt_id)
+        .fetch_optional(pool.inner())
+        .await
+        .map_err(|e| e.to_string())?;
+    if exists.is_none() {
+        return Err(AppError::NotFound {
+            entity: "DayAttempt".to_string(),
+            id: input.day_attempt_id.clone(),
+        }
+        .into());
+    }
+
+    let source = Path::new(&input.source_path);
+    let blob = store.import(source).await?;
+
+    let artifact_type = input.artifact_type.clone().unwrap_or_else(|| {
+        if blob.mime_type.starts_with("image/") { "screenshot" } else { "file" }.to_string()
+    });
+    let title = input
+        .title
+        .clone()
+        .filter(|title| !title.trim().is_empty())
+        .or_else(|| source.file_name().map(|name| name.to_string_lossy().to_string()))
+        .unwrap_or_default();
+
+    let artifact = sqlx::query_as::<_, Artifact>(
+        "INSERT INTO artifacts (
+            id, day_attempt_id, artifact_type, title, description, content, file_path,
+            file_size, mime_type, sha256, storage_path, thumbnail_path, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING *"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&input.day_attempt_id)
+    .bind(&artifact_type)
+    .bind(&title)
+    .bind(&input.description)
+    .bind(&input.content)
+    .bind(blob.absolute_path.to_string_lossy().to_string())
+    .bind(blob.size_bytes)
+    .bind(&blob.mime_type)
+    .bind(&blob.sha256)
+    .bind(&blob.storage_path)
+    .bind(blob.thumbnail_path.as_ref().map(|path| path.to_string_lossy().to_string()))
+    .bind(chrono::Utc::now().to_rfc3339())
+    .fetch_one(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(ImportedArtifact {
+        artifact,
+        deduplicated: blob.deduplicated,
+    })
+}
+
+#[tauri::command]
+pub async fn verify_artifact_blobs(
+    pool: State<'_, SqlitePool>,
+    store: State<'_, ArtifactStore>,
+) -> Result<ArtifactIntegrityReport, String> {
+    let stored: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT id, storage_path, sha256 FROM artifacts
+         WHERE sha256 IS NOT NULL AND storage_path IS NOT NULL
+         ORDER BY created_at"
+    )
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut report = ArtifactIntegrityReport {
+        checked: stored.len() as i64,
+        healthy: 0,
+        issues: Vec::new(),
+    };
+    for (artifact_id, storage_path, sha256) in stored {
+        let (problem, actual_sha256) = match store.verify(&storage_path, &sha256).await? {
+            BlobStatus::Healthy => {
+                report.healthy += 1;
+                continue;
+            }
+            BlobStatus::Missing => ("missing", None),
+            BlobStatus::Corrupted { actual_sha256 } => ("corrupted", Some(actual_sha256)),
+        };
+        report.issues.push(ArtifactBlobIssue {
+            artifact_id,
+            storage_path,
+            expected_sha256: sha256,
+            problem: problem.to_string(),
+            actual_sha256,
+        });
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn collect_artifact_garbage(
+    pool: State<'_, SqlitePool>,
+    store: State<'_, ArtifactStore>,
+) -> Result<ArtifactGcReport, String> {
+    let referenced: HashSet<String> =
+        sqlx::query_scalar("SELECT DISTINCT sha256 FROM artifacts WHERE sha256 IS NOT NULL")
+            .fetch_all(pool.inner())
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+
+    let report = store.collect_garbage(referenced).await?;
+    tracing::info!(
+        "Artifact GC removed {} blob(s) and {} thumbnail(s), {} bytes",
+        report.removed_blobs,
+        report.removed_thumbnails,
+        report.reclaimed_bytes
+    );
+
+    Ok(report)
+}
@@ -1,9 +1,15 @@
 use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::{
+    attempt_compare, attempt_state, checklist_gate, dependency_check, quiz_grader,
+    score_suggestions, scoring, similar_bugs, snapshots, streaks,
+};
 use sqlx::SqlitePool;
+use std::path::Path;
 use tauri::State;
 use uuid::Uuid;
 
-// Day Attempt Commands (8 commands)
+// Day Attempt Commands (10 commands)
 
 #[tauri::command]
 pub async fn start_attempt(
@@ -12,13 +18,49 @@ pub async fn start_attempt(
 ) -> Result<DayAttempt, String> {
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let latest_status: Option<String> = sqlx::query_scalar(
+        "SELECT status FROM day_attempts WHERE day_plan_id = ?
+         ORDER BY attempt_number DESC LIMIT 1"
+    )
+    .bind(&input.day_plan_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    attempt_state::ensure_can_start(latest_status.as_deref())?;
+
+    let override_reason = input
+        .prerequisite_override_reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+
+    let unmet: Vec<String> = dependency_check::dependency_statuses(&mut tx, &input.day_plan_id)
+        .await?
+        .into_iter()
+        .filter(|dep| !dep.is_met)
+        .map(|dep| {
+            format!(
+                "Day {} {} (needs {}, best {})",
+                dep.depends_on_day_number,
+                dep.depends_on_title,
+                dep.minimum_score,
+                dep.best_score.map_or_else(|| "none".to_string(), |s| s.to_string())
+            )
+        })
+        .collect();
+    if !unmet.is_empty() && override_reason.is_none() {
+        return Err(AppError::DependencyNotMet(unmet.join("; ")).into());
+    }
     
     // Get next attempt number
     let next_attempt_number: i32 = sqlx::query_scalar(
         "SELECT COALESCE(MAX(attempt_number), 0) + 1 FROM day_attempts WHERE day_plan_id = ?"
     )
     .bind(&input.day_plan_id)
-    .fetch_one(pool.inner())
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
     
@@ -29,20 +71,26 @@ pub async fn start_attempt(
             score_performance, score_quiz, total_score,
             daily_summary, what_went_well, what_to_improve, key_learnings,
             memory_rebuild_passed, memory_rebuild_notes, actual_minutes,
-            started_at, last_autosave, created_at, updated_at
-        ) VALUES (?, ?, ?, 'in_progress', 1, 0, 0, 0, 0, 0, 0, '', '', '', '', 0, '', 0, ?, ?, ?, ?)
+            prerequisite_override_reason,
+            started_at, last_autosave, content_saved_at, created_at, updated_at
+        ) VALUES (?, ?, ?, 'in_progress', 1, 0, 0, 0, 0, 0, 0, '', '', '', '', 0, '', 0, ?, ?, ?, ?, ?, ?)
         RETURNING *"
     )
     .bind(&id)
     .bind(&input.day_plan_id)
     .bind(next_attempt_number)
+    // Only worth keeping when something was actually overridden
+    .bind(override_reason.filter(|_| !unmet.is_empty()))
     .bind(&now)
     .bind(&now)
+    .bind(snapshots::saved_timestamp())
     .bind(&now)
     .bind(&now)
-    .fetch_one(pool.inner())
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
     
     Ok(attempt)
 }
@@ -104,6 +152,19 @@ pub async fn list_attempts(
     Ok(summaries)
 }
 
+#[tauri::command]
+pub async fn compare_attempts(
+    pool: State<'_, SqlitePool>,
+    base_attempt_id: String,
+    target_attempt_id: String,
+) -> Result<AttemptComparison, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let comparison =
+        attempt_compare::compare(&mut conn, &base_attempt_id, &target_attempt_id).await?;
+
+    Ok(comparison)
+}
+
 #[tauri::command]
 pub async fn update_attempt(
     pool: State<'_, SqlitePool>,
@@ -111,6 +172,9 @@ pub async fn update_attempt(
     input: UpdateDayAttemptInput,
 ) -> Result<DayAttempt, String> {
     let now = chrono::Utc::now().to_rfc3339();
+
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    attempt_state::ensure_editable(&attempt_state::current_status(&mut conn, &id).await?)?;
     
     let attempt = sqlx::query_as::<_, DayAttempt>(
         "UPDATE day_attempts SET
@@ -120,7 +184,9 @@ pub async fn update_attempt(
             key_learnings = COALESCE(?, key_learnings),
             memory_rebuild_passed = COALESCE(?, memory_rebuild_passed),
             memory_rebuild_notes = COALESCE(?, memory_rebuild_notes),
+            code_snapshot = COALESCE(?, code_snapshot),
             last_autosave = ?,
+            content_saved_at = ?,
             updated_at = ?
          WHERE id = ?
          RETURNING *"
@@ -131,32 +197,84 @@ pub async fn update_attempt(
     .bind(input.key_learnings.as_ref())
     .bind(input.memory_rebuild_passed)
     .bind(input.memory_rebuild_notes.as_ref())
+    .bind(input.code_snapshot.as_ref())
     .bind(&now)
+    .bind(snapshots::saved_timestamp())
     .bind(&now)
     .bind(&id)
-    .fetch_one(pool.inner())
+    .fetch_one(&mut *conn)
     .await
     .map_err(|e| e.to_string())?;
     
     Ok(attempt)
 }
 
+#[tauri::command]
+pub async fn set_attempt_project_path(
+    pool: State<'_, SqlitePool>,
+    id: String,
+    project_path: Option<String>,
+) -> Result<DayAttempt, String> {
+    // A blank path unlinks the project
+    let project_path = project_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if let Some(path) = &project_path {
+        if !Path::new(path).is_absolute() || !Path::new(path).is_dir() {
+            return Err(AppError::Validation(format!(
+                "{} is not an existing directory",
+                path
+            ))
+            .into());
+        }
+    }
+
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    attempt_state::ensure_editable(&attempt_state::current_status(&mut conn, &id).await?)?;
+
+    let attempt = sqlx::query_as::<_, DayAttempt>(
+        "UPDATE day_attempts SET project_path = ?, updated_at = ? WHERE id = ? RETURNING *"
+    )
+    .bind(&project_path)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&id)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(attempt)
+}
+
 #[tauri::command]
 pub async fn autosave_attempt(
     pool: State<'_, SqlitePool>,
     id: String,
-) -> Result<(), String> {
+    input: AutosaveAttemptInput,
+) -> Result<AttemptSnapshotSummary, String> {
     let now = chrono::Utc::now().to_rfc3339();
-    
-    sqlx::query("UPDATE day_attempts SET last_autosave = ?, updated_at = ? WHERE id = ?")
-        .bind(&now)
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let attempt = sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+        .bind(&id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    attempt_state::ensure_editable(&attempt.status)?;
+
+    let snapshot = snapshots::capture(&mut tx, &attempt, input).await?;
+
+    // content_saved_at stays on the last explicit save, which crash recovery compares against
+    sqlx::query("UPDATE day_attempts SET last_autosave = ? WHERE id = ?")
         .bind(&now)
         .bind(&id)
-        .execute(pool.inner())
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
     
-    Ok(())
+    Ok(snapshot)
 }
 
 #[tauri::command]
@@ -166,26 +284,64 @@ pub async fn submit_attempt(
     input: SubmitScoresInput,
 ) -> Result<DayAttempt, String> {
     let now = chrono::Utc::now().to_rfc3339();
-    
-    let total_score = input.score_implementation 
-        + input.score_code_quality 
-        + input.score_accessibility 
-        + input.score_performance 
-        + input.score_quiz;
-    
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let current = attempt_state::current_status(&mut tx, &id).await?;
+    attempt_state::ensure_editable(&current)?;
+
+    checklist_gate::enforce(&mut tx, &id).await?;
+
+    let program_id: String = sqlx::query_scalar(
+        "SELECT dp.program_id FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE da.id = ?"
+    )
+    .bind(&id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let RubricWithDimensions { rubric, dimensions } =
+        scoring::rubric_for_program(&mut tx, &program_id).await?;
+    let mut scores = scoring::resolve_scores(&dimensions, &input)?;
+
+    // An auto-graded quiz outranks whatever quiz score was typed in
+    if let Some(percent) = quiz_grader::latest_quiz_percent(&mut tx, &id).await? {
+        let graded = quiz_grader::quiz_dimension_score(percent, &dimensions);
+        for (dimension, score) in &mut scores {
+            if dimension.key == quiz_grader::QUIZ_DIMENSION_KEY {
+                *score = graded;
+            }
+        }
+    }
+
+    let suggestions = score_suggestions::list(&mut tx, &id).await?;
+    if input.accept_suggestions {
+        score_suggestions::accept(&suggestions, &mut scores);
+    }
+
+    let weighted: Vec<(i32, i32, f64)> = scores
+        .iter()
+        .map(|(dimension, score)| (*score, dimension.max_points, dimension.weight))
+        .collect();
+    let total_score = scoring::weighted_total(&weighted);
+
     // Determine status based on score and memory rebuild
-    let status = if !input.memory_rebuild_passed {
-        "blocked"
-    } else if total_score < 70 {
-        "blocked"
-    } else if total_score >= 95 {
-        "mastery"
-    } else {
-        "passed"
+    let status = scoring::attempt_status(total_score, input.memory_rebuild_passed, &rubric);
+    attempt_state::ensure_transition(&current, status)?;
+
+    // The legacy columns keep mirroring the default dimensions for older readers
+    let legacy = |key: &str| {
+        scores
+            .iter()
+            .find(|(dimension, _)| dimension.key == key)
+            .map_or(0, |(_, score)| *score)
     };
-    
+
     let attempt = sqlx::query_as::<_, DayAttempt>(
         "UPDATE day_attempts SET
+            rubric_id = ?,
             score_implementation = ?,
             score_code_quality = ?,
             score_accessibility = ?,
@@ -205,11 +361,12 @@ pub async fn submit_attempt(
          WHERE id = ?
          RETURNING *"
     )
-    .bind(input.score_implementation)
-    .bind(input.score_code_quality)
-    .bind(input.score_accessibility)
-    .bind(input.score_performance)
-    .bind(input.score_quiz)
+    .bind(&rubric.id)
+    .bind(legacy("implementation"))
+    .bind(legacy("code_quality"))
+    .bind(legacy("accessibility"))
+    .bind(legacy("performance"))
+    .bind(legacy("quiz"))
     .bind(total_score)
     .bind(&input.daily_summary)
     .bind(&input.what_went_well)
@@ -221,10 +378,47 @@ pub async fn submit_attempt(
     .bind(&now)
     .bind(&now)
     .bind(&id)
-    .fetch_one(pool.inner())
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
-    
+
+    sqlx::query("DELETE FROM attempt_dimension_scores WHERE day_attempt_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for (dimension, score) in &scores {
+        sqlx::query(
+            "INSERT INTO attempt_dimension_scores
+                (id, day_attempt_id, dimension_key, label, score, max_points, weight, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&id)
+        .bind(&dimension.key)
+        .bind(&dimension.label)
+        .bind(score)
+        .bind(dimension.max_points)
+        .bind(dimension.weight)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    score_suggestions::resolve(&mut tx, &suggestions, &scores, &now).await?;
+
+    streaks::record_activity(
+        &mut tx,
+        &attempt.day_plan_id,
+        streaks::Activity::Attempt { minutes: attempt.actual_minutes },
+        chrono::Utc::now(),
+    )
+    .await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(attempt)
 }
 
@@ -261,8 +455,8 @@ pub async fn create_exercise_entry(
     .map_err(|e| e.to_string())?;
     
     let entry = sqlx::query_as::<_, ExerciseEntry>(
-        "INSERT INTO exercise_entries (id, day_attempt_id, language, code, notes, order_index, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "INSERT INTO exercise_entries (id, day_attempt_id, language, code, notes, expected_output, order_index, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
          RETURNING *"
     )
     .bind(&id)
@@ -270,6 +464,7 @@ pub async fn create_exercise_entry(
     .bind(&input.language)
     .bind(&input.code)
     .bind(&input.notes)
+    .bind(input.expected_output.as_ref())
     .bind(next_order)
     .bind(&now)
     .bind(&now)
@@ -293,6 +488,7 @@ pub async fn update_exercise_entry(
             language = COALESCE(?, language),
             code = COALESCE(?, code),
             notes = COALESCE(?, notes),
+            expected_output = COALESCE(?, expected_output),
             updated_at = ?
          WHERE id = ?
          RETURNING *"
@@ -300,6 +496,7 @@ pub async fn update_exercise_entry(
     .bind(input.language.as_ref())
     .bind(input.code.as_ref())
     .bind(input.notes.as_ref())
+    .bind(input.expected_output.as_ref())
     .bind(&now)
     .bind(&id)
     .fetch_one(pool.inner())
@@ -351,15 +548,16 @@ pub async fn create_artifact(
     
     let artifact = sqlx::query_as::<_, Artifact>(
         "INSERT INTO artifacts (
-            id, day_attempt_id, artifact_type, title, content,
+            id, day_attempt_id, artifact_type, title, description, content,
             file_path, file_size, mime_type, url, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *"
     )
     .bind(&id)
     .bind(&input.day_attempt_id)
     .bind(&input.artifact_type)
     .bind(&input.title)
+    .bind(&input.description)
     .bind(&input.content)
     .bind(input.file_path.as_ref())
     .bind(input.file_size)
@@ -382,11 +580,13 @@ pub async fn update_artifact(
     let artifact = sqlx::query_as::<_, Artifact>(
         "UPDATE artifacts SET
             title = COALESCE(?, title),
+            description = COALESCE(?, description),
             content = COALESCE(?, content)
          WHERE id = ?
          RETURNING *"
     )
     .bind(input.title.as_ref())
+    .bind(input.description.as_ref())
     .bind(input.content.as_ref())
     .bind(&id)
     .fetch_one(pool.inner())
@@ -426,7 +626,7 @@ pub async fn delete_artifact(
     Ok(())
 }
 
-// Bug Log Commands (4 commands)
+// Bug Log Commands (5 commands)
 
 #[tauri::command]
 pub async fn create_bug_log(
@@ -528,3 +728,27 @@ pub async fn delete_bug_log(
     
     Ok(())
 }
+
+#[tauri::command]
+pub async fn find_similar_bugs(
+    pool: State<'_, SqlitePool>,
+    input: FindSimilarBugsInput,
+) -> Result<Vec<SimilarBug>, String> {
+    let limit = input
+        .limit
+        .map(|limit| (limit.max(1) as usize).min(similar_bugs::MAX_LIMIT))
+        .unwrap_or(similar_bugs::DEFAULT_LIMIT);
+
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let similar = similar_bugs::find(
+        &mut conn,
+        input.day_attempt_id.as_deref(),
+        &input.symptom,
+        &input.root_cause,
+        input.exclude_bug_id.as_deref(),
+        limit,
+    )
+    .await?;
+
+    Ok(similar)
+}
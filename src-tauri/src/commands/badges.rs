@@ -0,0 +1,36 @@
+use crate::db::models::*;
+use crate::services::badges;
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Badge Rules Commands (3 commands)
+
+#[tauri::command]
+pub async fn list_badge_definitions(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<BadgeDefinition>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let definitions = badges::definitions(&mut conn).await?;
+
+    Ok(definitions)
+}
+
+#[tauri::command]
+pub async fn get_badge_progress(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<Vec<BadgeProgress>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let progress = badges::badge_progress(&mut conn, &program_id, chrono::Utc::now()).await?;
+
+    Ok(progress)
+}
+
+#[tauri::command]
+pub async fn backfill_badges(pool: State<'_, SqlitePool>) -> Result<Vec<Badge>, String> {
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+    let awarded = badges::backfill_badges(&mut tx, chrono::Utc::now()).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(awarded)
+}
@@ -0,0 +1,17 @@
+use crate::db::models::*;
+use crate::services::bug_analytics;
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Bug Analytics Commands (1 command)
+
+#[tauri::command]
+pub async fn get_bug_analytics(
+    pool: State<'_, SqlitePool>,
+    program_id: Option<String>,
+) -> Result<BugAnalytics, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let bugs = bug_analytics::occurrences(&mut conn, program_id.as_deref()).await?;
+
+    Ok(bug_analytics::analyze(program_id, &bugs, chrono::Utc::now()))
+}
@@ -0,0 +1,117 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::checklist_gate;
+use sqlx::SqlitePool;
+use tauri::State;
+use uuid::Uuid;
+
+// Attempt Checklist Commands (3 commands)
+
+#[tauri::command]
+pub async fn get_attempt_checklist(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<AttemptChecklistItem>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let items = checklist_gate::attempt_checklist(&mut conn, &day_attempt_id).await?;
+
+    Ok(items)
+}
+
+#[tauri::command]
+pub async fn toggle_checklist_item(
+    pool: State<'_, SqlitePool>,
+    input: ToggleChecklistItemInput,
+) -> Result<AttemptChecklistItem, String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let status: String = sqlx::query_scalar(
+        "SELECT da.status FROM day_attempts da
+         JOIN checklist_items ci ON ci.day_plan_id = da.day_plan_id
+         WHERE da.id = ? AND ci.id = ?"
+    )
+    .bind(&input.day_attempt_id)
+    .bind(&input.checklist_item_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| {
+        AppError::Validation("Checklist item does not belong to this attempt's day".to_string())
+    })?;
+
+    if status != "in_progress" {
+        return Err(AppError::InvalidStateTransition(
+            "The checklist can only change while the attempt is in progress".to_string(),
+        )
+        .into());
+    }
+
+    if let Some(artifact_id) = &input.artifact_id {
+        let belongs: Option<i32> = sqlx::query_scalar(
+            "SELECT 1 FROM artifacts WHERE id = ? AND day_attempt_id = ?"
+        )
+        .bind(artifact_id)
+        .bind(&input.day_attempt_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if belongs.is_none() {
+            return Err(AppError::Validation(
+                "Evidence must be an artifact from the same attempt".to_string(),
+            )
+            .into());
+        }
+    }
+
+    // Re-checking an item keeps the time it was first completed
+    sqlx::query(
+        "INSERT INTO attempt_checklist (
+            id, day_attempt_id, checklist_item_id, is_completed, completed_at, artifact_id,
+            created_at, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(day_attempt_id, checklist_item_id) DO UPDATE SET
+            is_completed = excluded.is_completed,
+            completed_at = CASE WHEN excluded.is_completed
+                THEN COALESCE(attempt_checklist.completed_at, excluded.completed_at)
+                ELSE NULL END,
+            artifact_id = excluded.artifact_id,
+            updated_at = excluded.updated_at"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&input.day_attempt_id)
+    .bind(&input.checklist_item_id)
+    .bind(input.is_completed)
+    .bind(input.is_completed.then_some(&now))
+    .bind(&input.artifact_id)
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let item = checklist_gate::attempt_checklist(&mut tx, &input.day_attempt_id)
+        .await?
+        .into_iter()
+        .find(|item| item.checklist_item_id == input.checklist_item_id)
+        .ok_or_else(|| AppError::NotFound {
+            entity: "ChecklistItem".to_string(),
+            id: input.checklist_item_id.clone(),
+        })?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(item)
+}
+
+#[tauri::command]
+pub async fn validate_attempt_submission(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<SubmissionCheck, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let check = checklist_gate::check_submission(&mut conn, &day_attempt_id).await?;
+
+    Ok(check)
+}
@@ -0,0 +1,70 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::{attempt_state, code_analyzer, score_suggestions, scoring};
+use sqlx::SqlitePool;
+use std::path::Path;
+use tauri::State;
+
+// Code Analysis Commands (2 commands)
+
+#[tauri::command]
+pub async fn analyze_attempt_code(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<AttemptScoreSuggestion>, String> {
+    let (attempt, entries, program_id) = {
+        let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+        let attempt = sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+            .bind(&day_attempt_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "DayAttempt".to_string(),
+                id: day_attempt_id.clone(),
+            })?;
+        attempt_state::ensure_editable(&attempt.status)?;
+
+        let entries = sqlx::query_as::<_, ExerciseEntry>(
+            "SELECT * FROM exercise_entries WHERE day_attempt_id = ? ORDER BY order_index"
+        )
+        .bind(&day_attempt_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let program_id: String =
+            sqlx::query_scalar("SELECT program_id FROM day_plans WHERE id = ?")
+                .bind(&attempt.day_plan_id)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+
+        (attempt, entries, program_id)
+    };
+
+    // Linters can run for minutes on a project, so no connection is held meanwhile
+    let project_path = attempt.project_path.as_deref().map(Path::new);
+    let analysis = code_analyzer::analyze(&entries, project_path).await?;
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+    // The attempt may have been submitted while the linters ran
+    attempt_state::ensure_editable(&attempt_state::current_status(&mut tx, &day_attempt_id).await?)?;
+    let rubric = scoring::rubric_for_program(&mut tx, &program_id).await?;
+    let suggestions =
+        score_suggestions::store(&mut tx, &day_attempt_id, &analysis, &rubric.dimensions).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(suggestions)
+}
+
+#[tauri::command]
+pub async fn list_score_suggestions(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<AttemptScoreSuggestion>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let suggestions = score_suggestions::list(&mut conn, &day_attempt_id).await?;
+
+    Ok(suggestions)
+}
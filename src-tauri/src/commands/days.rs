@@ -1,4 +1,5 @@
 use crate::db::models::*;
+use crate::services::dependency_check;
 use sqlx::SqlitePool;
 use tauri::State;
 use uuid::Uuid;
@@ -404,8 +405,8 @@ pub async fn duplicate_day_plan(
         sqlx::query(
             "INSERT INTO quiz_questions (
                 id, day_plan_id, question_text, question_type, correct_answer,
-                options, points, time_limit_seconds, order_index, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                options, answer_tolerance, points, time_limit_seconds, order_index, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&question_id)
         .bind(&new_id)
@@ -413,6 +414,7 @@ pub async fn duplicate_day_plan(
         .bind(&question.question_type)
         .bind(&question.correct_answer)
         .bind(&question.options)
+        .bind(question.answer_tolerance)
         .bind(question.points)
         .bind(question.time_limit_seconds)
         .bind(question.order_index)
@@ -561,8 +563,8 @@ pub async fn add_quiz_question(
     let question = sqlx::query_as::<_, QuizQuestion>(
         "INSERT INTO quiz_questions (
             id, day_plan_id, question_text, question_type, correct_answer,
-            options, points, time_limit_seconds, order_index, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            options, answer_tolerance, points, time_limit_seconds, order_index, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *"
     )
     .bind(&id)
@@ -571,6 +573,7 @@ pub async fn add_quiz_question(
     .bind(&input.question_type)
     .bind(&input.correct_answer)
     .bind(input.options.unwrap_or_else(|| "[]".to_string()))
+    .bind(input.answer_tolerance.unwrap_or(0.0))
     .bind(input.points)
     .bind(input.time_limit_seconds)
     .bind(next_order)
@@ -594,6 +597,7 @@ pub async fn update_quiz_question(
             question_type = COALESCE(?, question_type),
             correct_answer = COALESCE(?, correct_answer),
             options = COALESCE(?, options),
+            answer_tolerance = COALESCE(?, answer_tolerance),
             points = COALESCE(?, points),
             time_limit_seconds = COALESCE(?, time_limit_seconds)
          WHERE id = ?
@@ -603,6 +607,7 @@ pub async fn update_quiz_question(
     .bind(input.question_type.as_ref())
     .bind(input.correct_answer.as_ref())
     .bind(input.options.as_ref())
+    .bind(input.answer_tolerance)
     .bind(input.points)
     .bind(input.time_limit_seconds)
     .bind(&id)
@@ -768,57 +773,8 @@ pub async fn check_dependencies(
     pool: State<'_, SqlitePool>,
     day_plan_id: String,
 ) -> Result<Vec<DependencyStatus>, String> {
-    let dependencies = sqlx::query_as::<_, DayDependency>(
-        "SELECT * FROM day_dependencies WHERE day_plan_id = ?"
-    )
-    .bind(&day_plan_id)
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
-    
-    let mut statuses = Vec::new();
-    
-    for dep in dependencies {
-        let day_info: (String, i32) = sqlx::query_as(
-            "SELECT title, day_number FROM day_plans WHERE id = ?"
-        )
-        .bind(&dep.depends_on_day_plan_id)
-        .fetch_one(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
-        
-        let best_attempt: Option<(i32, String)> = sqlx::query_as(
-            "SELECT total_score, status FROM day_attempts
-             WHERE day_plan_id = ? AND is_draft = 0
-             ORDER BY total_score DESC LIMIT 1"
-        )
-        .bind(&dep.depends_on_day_plan_id)
-        .fetch_optional(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
-        
-        let (best_score, best_status) = match best_attempt {
-            Some((score, status)) => (Some(score), Some(status)),
-            None => (None, None),
-        };
-        
-        let is_met = match dep.dependency_type.as_str() {
-            "prerequisite" => best_score.map_or(false, |s| s >= dep.minimum_score),
-            _ => true, // recommended and related don't block
-        };
-        
-        statuses.push(DependencyStatus {
-            dependency_id: dep.id,
-            depends_on_day_plan_id: dep.depends_on_day_plan_id,
-            depends_on_title: day_info.0,
-            depends_on_day_number: day_info.1,
-            dependency_type: dep.dependency_type,
-            minimum_score: dep.minimum_score,
-            is_met,
-            best_score,
-            best_attempt_status: best_status,
-        });
-    }
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let statuses = dependency_check::dependency_statuses(&mut conn, &day_plan_id).await?;
     
     Ok(statuses)
 }
@@ -0,0 +1,87 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::{attempt_state, exercise_runner};
+use sqlx::SqlitePool;
+use tauri::State;
+use uuid::Uuid;
+
+// Exercise Run Commands (2 commands)
+
+#[tauri::command]
+pub async fn run_exercise_entry(
+    pool: State<'_, SqlitePool>,
+    exercise_entry_id: String,
+) -> Result<ExerciseRun, String> {
+    let entry = sqlx::query_as::<_, ExerciseEntry>("SELECT * FROM exercise_entries WHERE id = ?")
+        .bind(&exercise_entry_id)
+        .fetch_optional(pool.inner())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "ExerciseEntry".to_string(),
+            id: exercise_entry_id.clone(),
+        })?;
+
+    {
+        let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+        let status = attempt_state::current_status(&mut conn, &entry.day_attempt_id).await?;
+        attempt_state::ensure_editable(&status)?;
+    }
+
+    if entry.code.trim().is_empty() {
+        return Err(AppError::Validation("The exercise has no code to run".to_string()).into());
+    }
+
+    // No connection is held while the program runs, since a build can take a while
+    let output = exercise_runner::run(&entry.language, &entry.code).await?;
+
+    let passed = entry.expected_output.as_deref().map(|expected| {
+        output.stage == "run"
+            && !output.timed_out
+            && output.exit_code == Some(0)
+            && exercise_runner::matches_expected(expected, &output.stdout)
+    });
+
+    let run = sqlx::query_as::<_, ExerciseRun>(
+        "INSERT INTO exercise_runs (
+            id, exercise_entry_id, day_attempt_id, language, code, stage, exit_code, timed_out,
+            stdout, stderr, duration_ms, expected_output, passed, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING *"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&entry.id)
+    .bind(&entry.day_attempt_id)
+    .bind(&entry.language)
+    .bind(&entry.code)
+    .bind(output.stage)
+    .bind(output.exit_code)
+    .bind(output.timed_out)
+    .bind(&output.stdout)
+    .bind(&output.stderr)
+    .bind(output.duration_ms)
+    .bind(&entry.expected_output)
+    .bind(passed)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .fetch_one(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(run)
+}
+
+#[tauri::command]
+pub async fn list_exercise_runs(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<ExerciseRun>, String> {
+    let runs = sqlx::query_as::<_, ExerciseRun>(
+        "SELECT * FROM exercise_runs WHERE day_attempt_id = ? ORDER BY created_at DESC"
+    )
+    .bind(&day_attempt_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(runs)
+}
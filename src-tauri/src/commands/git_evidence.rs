@@ -0,0 +1,136 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::{attempt_state, git_evidence};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::path::Path;
+use tauri::State;
+use uuid::Uuid;
+
+// Git Evidence Commands (2 commands)
+
+#[tauri::command]
+pub async fn capture_git_evidence(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<CapturedGitEvidence, String> {
+    let attempt = sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+        .bind(&day_attempt_id)
+        .fetch_optional(pool.inner())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "DayAttempt".to_string(),
+            id: day_attempt_id.clone(),
+        })?;
+    let repository = attempt.project_path.as_deref().ok_or_else(|| {
+        AppError::Validation("Link the attempt to its project repository first".to_string())
+    })?;
+
+    let now = Utc::now();
+    let parse = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(|e| AppError::Validation(format!("Unreadable attempt timestamp: {}", e)))
+    };
+    let window_start = parse(&attempt.started_at)?;
+    let window_end = match attempt.submitted_at.as_deref() {
+        Some(submitted_at) => parse(submitted_at)?,
+        None => now,
+    };
+
+    let capture = git_evidence::capture(Path::new(repository), window_start, window_end).await?;
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let evidence = sqlx::query_as::<_, GitEvidence>(
+        "INSERT INTO git_evidence (
+            id, day_attempt_id, repository_path, branch, head_commit, window_start, window_end,
+            commit_count, files_changed, insertions, deletions, commits_json, tree_json,
+            estimated_minutes, captured_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(day_attempt_id) DO UPDATE SET
+            repository_path = excluded.repository_path,
+            branch = excluded.branch,
+            head_commit = excluded.head_commit,
+            window_start = excluded.window_start,
+            window_end = excluded.window_end,
+            commit_count = excluded.commit_count,
+            files_changed = excluded.files_changed,
+            insertions = excluded.insertions,
+            deletions = excluded.deletions,
+            commits_json = excluded.commits_json,
+            tree_json = excluded.tree_json,
+            estimated_minutes = excluded.estimated_minutes,
+            captured_at = excluded.captured_at
+        RETURNING *"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&day_attempt_id)
+    .bind(&capture.repository_path)
+    .bind(&capture.branch)
+    .bind(&capture.head_commit)
+    .bind(window_start.to_rfc3339())
+    .bind(window_end.to_rfc3339())
+    .bind(capture.commits.len() as i64)
+    .bind(capture.files_changed as i64)
+    .bind(capture.insertions)
+    .bind(capture.deletions)
+    .bind(serde_json::to_string(&capture.commits).map_err(|e| e.to_string())?)
+    .bind(serde_json::to_string(&capture.tree).map_err(|e| e.to_string())?)
+    .bind(capture.estimated_minutes)
+    .bind(now.to_rfc3339())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Only blanks are filled, so nothing the learner entered is overwritten. A submitted attempt
+    // is left as graded and the capture stays on git_evidence alone.
+    let mut applied_fields = Vec::new();
+    let editable = attempt.status == attempt_state::IN_PROGRESS;
+    if editable && attempt.code_snapshot.trim().is_empty() && !capture.code_snapshot.is_empty() {
+        applied_fields.push("code_snapshot".to_string());
+    }
+    if editable && attempt.actual_minutes == 0 && capture.estimated_minutes > 0 {
+        applied_fields.push("actual_minutes".to_string());
+    }
+    if !applied_fields.is_empty() {
+        sqlx::query(
+            "UPDATE day_attempts SET
+                code_snapshot = CASE WHEN TRIM(code_snapshot) = '' THEN ? ELSE code_snapshot END,
+                actual_minutes = CASE WHEN actual_minutes = 0 THEN ? ELSE actual_minutes END,
+                updated_at = ?
+             WHERE id = ?"
+        )
+        .bind(&capture.code_snapshot)
+        .bind(capture.estimated_minutes)
+        .bind(now.to_rfc3339())
+        .bind(&day_attempt_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(CapturedGitEvidence {
+        evidence,
+        applied_fields,
+    })
+}
+
+#[tauri::command]
+pub async fn get_git_evidence(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Option<GitEvidence>, String> {
+    let evidence = sqlx::query_as::<_, GitEvidence>(
+        "SELECT * FROM git_evidence WHERE day_attempt_id = ?"
+    )
+    .bind(&day_attempt_id)
+    .fetch_optional(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(evidence)
+}
@@ -1,4 +1,9 @@
-use crate::db::models::import::{GeneratedPlan, ImportJob, ImportJobSummary};
+use crate::db::models::import::{
+    CapacityPlan, ExtractedSection, GeneratedPlan, ImportJob, ImportJobSummary, ImportPlanEdit,
+    ImportQualityReport, PlanEditOperation, ProjectedCalendar, ReimportMergePlan,
+};
+use crate::db::models::UserCapacityProfile;
+use crate::services::{capacity_planner, import_report, plan_editor, plan_merger};
 use sqlx::{Pool, Sqlite};
 use tauri::AppHandle;
 use uuid::Uuid;
@@ -81,21 +86,232 @@ pub async fn update_import_preview(
     job_id: String,
     reviewed_plan_json: String,
 ) -> Result<(), String> {
+    let replacement: GeneratedPlan = serde_json::from_str(&reviewed_plan_json)
+        .map_err(|e| format!("Failed to parse plan: {}", e))?;
+
+    // Logged like any other edit, so it can be undone and gets the same dependency check
+    let operation = PlanEditOperation::ReplacePlan {
+        plan: Box::new(replacement),
+    };
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+    let edited = plan_editor::apply_edit(&plan, &operation).map_err(|e| e.to_string())?;
+
+    record_edit(pool.inner(), &job_id, &plan, &edited, &operation).await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_import_report(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+) -> Result<ImportQualityReport, String> {
+    let job: ImportJob = sqlx::query_as(
+        "SELECT * FROM import_jobs WHERE id = ?"
+    )
+    .bind(&job_id)
+    .fetch_one(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+
+    // Jobs that never got past extraction store an empty string here
+    let sections: Vec<ExtractedSection> = if job.extracted_sections_json.trim().is_empty() {
+        Vec::new()
+    } else {
+        serde_json::from_str(&job.extracted_sections_json)
+            .map_err(|e| format!("Failed to parse extracted sections: {}", e))?
+    };
+
+    Ok(import_report::build_report(&job_id, &plan, &sections))
+}
+
+#[tauri::command]
+pub async fn apply_import_edit(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+    operation: PlanEditOperation,
+) -> Result<GeneratedPlan, String> {
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+    let edited = plan_editor::apply_edit(&plan, &operation).map_err(|e| e.to_string())?;
+
+    record_edit(pool.inner(), &job_id, &plan, &edited, &operation).await?;
+
+    Ok(edited)
+}
+
+#[tauri::command]
+pub async fn generate_capacity_plan(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+    start_date: Option<String>,
+) -> Result<CapacityPlan, String> {
+    let profile = sqlx::query_as::<_, UserCapacityProfile>(
+        "SELECT * FROM user_capacity_profiles WHERE user_id = 'default' LIMIT 1"
+    )
+    .fetch_optional(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Capacity profile not found".to_string())?;
+
+    let start = match start_date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid start date: {}", e))?,
+        None => chrono::Utc::now().date_naive(),
+    };
+
+    // Fitting goes through the edit log so it can be undone like any other edit
+    let operation = PlanEditOperation::FitToCapacity {
+        daily_minutes: i64::from(profile.default_daily_minutes),
+    };
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+    let fitted = plan_editor::apply_edit(&plan, &operation).map_err(|e| e.to_string())?;
+
+    record_edit(pool.inner(), &job_id, &plan, &fitted, &operation).await?;
+
+    let calendar = capacity_planner::project_calendar(&fitted, &profile, start);
+    store_calendar(pool.inner(), &job_id, &calendar).await?;
+
+    Ok(CapacityPlan { plan: fitted, calendar })
+}
+
+async fn store_calendar(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    calendar: &ProjectedCalendar,
+) -> Result<(), String> {
+    let calendar_json = serde_json::to_string(calendar).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "UPDATE import_jobs SET projected_calendar_json = ?, projected_end_date = ?, updated_at = ?
+         WHERE id = ?"
+    )
+    .bind(&calendar_json)
+    .bind(&calendar.end_date)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(job_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+async fn record_edit(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    plan_before: &GeneratedPlan,
+    plan_after: &GeneratedPlan,
+    operation: &PlanEditOperation,
+) -> Result<(), String> {
+    let plan_before_json = serde_json::to_string(plan_before).map_err(|e| e.to_string())?;
+    let plan_after_json = serde_json::to_string(plan_after).map_err(|e| e.to_string())?;
+    let operation_json = serde_json::to_string(operation).map_err(|e| e.to_string())?;
     let now = chrono::Utc::now().to_rfc3339();
-    
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let sequence: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(sequence), 0) + 1 FROM import_plan_edits WHERE job_id = ?"
+    )
+    .bind(job_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "INSERT INTO import_plan_edits (id, job_id, sequence, operation_json, plan_before_json, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(job_id)
+    .bind(sequence)
+    .bind(&operation_json)
+    .bind(&plan_before_json)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
     sqlx::query(
         "UPDATE import_jobs SET reviewed_plan_json = ?, updated_at = ? WHERE id = ?"
     )
-    .bind(&reviewed_plan_json)
+    .bind(&plan_after_json)
     .bind(&now)
-    .bind(&job_id)
-    .execute(pool.inner())
+    .bind(job_id)
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
-    
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
+#[tauri::command]
+pub async fn undo_import_edit(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+) -> Result<GeneratedPlan, String> {
+    let last: Option<(String, String)> = sqlx::query_as(
+        "SELECT id, plan_before_json FROM import_plan_edits
+         WHERE job_id = ?
+         ORDER BY sequence DESC
+         LIMIT 1"
+    )
+    .bind(&job_id)
+    .fetch_optional(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (edit_id, plan_before_json) = last.ok_or("Nothing to undo")?;
+    let plan: GeneratedPlan = serde_json::from_str(&plan_before_json)
+        .map_err(|e| format!("Failed to parse plan: {}", e))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "UPDATE import_jobs SET reviewed_plan_json = ?, updated_at = ? WHERE id = ?"
+    )
+    .bind(&plan_before_json)
+    .bind(&now)
+    .bind(&job_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query("DELETE FROM import_plan_edits WHERE id = ?")
+        .bind(&edit_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(plan)
+}
+
+#[tauri::command]
+pub async fn list_import_edits(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+) -> Result<Vec<ImportPlanEdit>, String> {
+    let edits: Vec<ImportPlanEdit> = sqlx::query_as(
+        "SELECT id, job_id, sequence, operation_json, created_at
+         FROM import_plan_edits
+         WHERE job_id = ?
+         ORDER BY sequence"
+    )
+    .bind(&job_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(edits)
+}
+
 #[tauri::command]
 pub async fn apply_import(
     pool: tauri::State<'_, Pool<Sqlite>>,
@@ -113,6 +329,55 @@ pub async fn apply_import(
     Err("Import application not yet implemented".to_string())
 }
 
+async fn load_job_plan(pool: &Pool<Sqlite>, job_id: &str) -> Result<GeneratedPlan, String> {
+    let job: ImportJob = sqlx::query_as(
+        "SELECT * FROM import_jobs WHERE id = ?"
+    )
+    .bind(job_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Prefer the user's reviewed edits over the raw generated plan
+    let plan_json = match job.reviewed_plan_json.as_deref() {
+        Some(json) if !json.is_empty() => json,
+        _ if !job.generated_plan_json.is_empty() => job.generated_plan_json.as_str(),
+        _ => return Err("No generated plan available".to_string()),
+    };
+
+    serde_json::from_str(plan_json).map_err(|e| format!("Failed to parse plan: {}", e))
+}
+
+#[tauri::command]
+pub async fn preview_reimport(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+    program_id: String,
+) -> Result<ReimportMergePlan, String> {
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+
+    plan_merger::build_merge_plan(pool.inner(), &job_id, &program_id, &plan)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn apply_reimport(
+    pool: tauri::State<'_, Pool<Sqlite>>,
+    job_id: String,
+    merge_plan: ReimportMergePlan,
+) -> Result<crate::db::models::Program, String> {
+    if merge_plan.job_id != job_id {
+        return Err("Merge plan was built for a different import job".to_string());
+    }
+
+    let plan = load_job_plan(pool.inner(), &job_id).await?;
+
+    plan_merger::apply_merge_plan(pool.inner(), &plan, &merge_plan)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn cancel_import(
     pool: tauri::State<'_, Pool<Sqlite>>,
@@ -0,0 +1,219 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::similarity::line_diff;
+use crate::services::{attempt_state, memory_rebuild, quiz_grader};
+use chrono::Duration;
+use sqlx::{SqliteConnection, SqlitePool};
+use tauri::State;
+use uuid::Uuid;
+
+// Memory Rebuild Commands (4 commands)
+
+#[tauri::command]
+pub async fn start_memory_rebuild(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<MemoryRebuildSession, String> {
+    let now = chrono::Utc::now();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let (day_plan_id, status, time_limit_minutes): (String, String, i32) = sqlx::query_as(
+        "SELECT da.day_plan_id, da.status, dp.memory_rebuild_minutes
+         FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE da.id = ?"
+    )
+    .bind(&day_attempt_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| AppError::NotFound {
+        entity: "DayAttempt".to_string(),
+        id: day_attempt_id.clone(),
+    })?;
+    attempt_state::ensure_editable(&status)?;
+
+    // Resume a running session rather than restarting the clock
+    let open = sqlx::query_as::<_, MemoryRebuildSession>(
+        "SELECT * FROM memory_rebuild_sessions WHERE day_attempt_id = ? AND status = 'in_progress'"
+    )
+    .bind(&day_attempt_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if let Some(session) = open {
+        tx.commit().await.map_err(|e| e.to_string())?;
+        return Ok(session);
+    }
+
+    let (reference_attempt_id, reference_code) =
+        memory_rebuild::reference_attempt(&mut tx, &day_plan_id, &day_attempt_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Validation(
+                    "No earlier submitted attempt of this day has a code snapshot to rebuild \
+                     from memory yet"
+                        .to_string(),
+                )
+            })?;
+
+    let deadline = (time_limit_minutes > 0)
+        .then(|| now + Duration::minutes(i64::from(time_limit_minutes)));
+    let session = sqlx::query_as::<_, MemoryRebuildSession>(
+        "INSERT INTO memory_rebuild_sessions (
+            id, day_attempt_id, reference_attempt_id, reference_code, status,
+            time_limit_minutes, started_at, deadline_at, created_at
+        ) VALUES (?, ?, ?, ?, 'in_progress', ?, ?, ?, ?)
+        RETURNING *"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&day_attempt_id)
+    .bind(&reference_attempt_id)
+    .bind(&reference_code)
+    .bind(time_limit_minutes.max(0))
+    .bind(now.to_rfc3339())
+    .bind(deadline.map(|d| d.to_rfc3339()))
+    .bind(now.to_rfc3339())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(session)
+}
+
+#[tauri::command]
+pub async fn submit_memory_rebuild(
+    pool: State<'_, SqlitePool>,
+    session_id: String,
+    rebuilt_code: String,
+) -> Result<MemoryRebuildResult, String> {
+    let now = chrono::Utc::now();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let session = fetch_session(&mut tx, &session_id).await?;
+    if session.status != "in_progress" {
+        return Err(AppError::InvalidStateTransition(
+            "This memory rebuild has already been submitted".to_string(),
+        )
+        .into());
+    }
+
+    let syntax_targets: String = sqlx::query_scalar(
+        "SELECT dp.syntax_targets FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE da.id = ?"
+    )
+    .bind(&session.day_attempt_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let overtime = quiz_grader::is_past_deadline(session.deadline_at.as_deref(), now);
+    let evaluation =
+        memory_rebuild::evaluate(&session.reference_code, &rebuilt_code, &syntax_targets, overtime);
+
+    let elapsed_seconds = chrono::DateTime::parse_from_rfc3339(&session.started_at)
+        .map(|started| (now - started.with_timezone(&chrono::Utc)).num_seconds())
+        .ok();
+
+    sqlx::query(
+        "UPDATE memory_rebuild_sessions SET
+            status = ?,
+            submitted_at = ?,
+            elapsed_seconds = ?,
+            rebuilt_code = ?,
+            token_similarity = ?,
+            target_coverage = ?,
+            targets_json = ?,
+            missing_identifiers_json = ?,
+            suggested_pass = ?,
+            evidence = ?
+         WHERE id = ?"
+    )
+    .bind(if overtime { "expired" } else { "submitted" })
+    .bind(now.to_rfc3339())
+    .bind(elapsed_seconds)
+    .bind(&rebuilt_code)
+    .bind(evaluation.token_similarity)
+    .bind(evaluation.target_coverage)
+    .bind(serde_json::to_string(&evaluation.targets).map_err(|e| e.to_string())?)
+    .bind(serde_json::to_string(&evaluation.missing_identifiers).map_err(|e| e.to_string())?)
+    .bind(evaluation.suggested_pass)
+    .bind(&evaluation.evidence)
+    .bind(&session_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let result = load_result(&mut tx, &session_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_memory_rebuild_result(
+    pool: State<'_, SqlitePool>,
+    session_id: String,
+) -> Result<MemoryRebuildResult, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let result = load_result(&mut conn, &session_id).await?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_memory_rebuild_sessions(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<MemoryRebuildSession>, String> {
+    let sessions = sqlx::query_as::<_, MemoryRebuildSession>(
+        "SELECT * FROM memory_rebuild_sessions WHERE day_attempt_id = ? ORDER BY started_at DESC"
+    )
+    .bind(&day_attempt_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(sessions)
+}
+
+async fn fetch_session(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<MemoryRebuildSession, AppError> {
+    sqlx::query_as::<_, MemoryRebuildSession>("SELECT * FROM memory_rebuild_sessions WHERE id = ?")
+        .bind(session_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "MemoryRebuildSession".to_string(),
+            id: session_id.to_string(),
+        })
+}
+
+async fn load_result(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<MemoryRebuildResult, AppError> {
+    let session = fetch_session(conn, session_id).await?;
+    let targets: Vec<SyntaxTargetCoverage> = serde_json::from_str(&session.targets_json)?;
+    let missing_identifiers: Vec<String> =
+        serde_json::from_str(&session.missing_identifiers_json)?;
+
+    // The reference stays hidden while the rebuild is running
+    let diff = if session.status == "in_progress" {
+        Vec::new()
+    } else {
+        line_diff(&session.reference_code, &session.rebuilt_code)
+    };
+
+    Ok(MemoryRebuildResult {
+        session,
+        targets,
+        missing_identifiers,
+        diff,
+    })
+}
@@ -6,6 +6,20 @@ pub mod intelligence;
 pub mod capacity;
 pub mod sessions;
 pub mod import;
+pub mod rubrics;
+pub mod quizzes;
+pub mod checklists;
+pub mod snapshots;
+pub mod exercise_runs;
+pub mod code_analysis;
+pub mod git_evidence;
+pub mod memory_rebuild;
+pub mod artifact_store;
+pub mod bug_analytics;
+pub mod review_scheduler;
+pub mod review_queue;
+pub mod quiz_cards;
+pub mod badges;
 
 pub use programs::*;
 pub use modules::*;
@@ -15,3 +29,17 @@ pub use intelligence::*;
 pub use capacity::*;
 pub use sessions::*;
 pub use import::*;
+pub use rubrics::*;
+pub use quizzes::*;
+pub use checklists::*;
+pub use snapshots::*;
+pub use exercise_runs::*;
+pub use code_analysis::*;
+pub use git_evidence::*;
+pub use memory_rebuild::*;
+pub use artifact_store::*;
+pub use bug_analytics::*;
+pub use review_scheduler::*;
+pub use review_queue::*;
+pub use quiz_cards::*;
+pub use badges::*;
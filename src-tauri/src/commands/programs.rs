@@ -1,4 +1,4 @@
-use crate::db::models::{Program, CreateProgramInput, UpdateProgramInput};
+use crate::db::models::{Program, CreateProgramInput, UpdateProgramInput, DimensionAverage};
 use sqlx::SqlitePool;
 use tauri::State;
 use uuid::Uuid;
@@ -201,6 +201,24 @@ pub async fn get_program_stats(
     .await
     .map_err(|e| e.to_string())?;
     
+    let dimension_averages = sqlx::query_as::<_, DimensionAverage>(
+        "SELECT
+            ads.dimension_key,
+            MAX(ads.label) as label,
+            AVG(100.0 * ads.score / ads.max_points) as average_percent,
+            COUNT(*) as attempt_count
+         FROM attempt_dimension_scores ads
+         JOIN day_attempts da ON da.id = ads.day_attempt_id
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE dp.program_id = ? AND da.status != 'in_progress'
+         GROUP BY ads.dimension_key
+         ORDER BY ads.dimension_key"
+    )
+    .bind(&id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
     let stats = if let Some((total_days, completed_days, blocked_days, average_score, total_time_minutes)) = row {
         crate::db::models::ProgramStats {
             total_days,
@@ -209,6 +227,7 @@ pub async fn get_program_stats(
             average_score,
             current_streak: 0,
             total_time_minutes,
+            dimension_averages,
         }
     } else {
         crate::db::models::ProgramStats {
@@ -218,6 +237,7 @@ pub async fn get_program_stats(
             average_score: 0.0,
             current_streak: 0,
             total_time_minutes: 0,
+            dimension_averages,
         }
     };
     
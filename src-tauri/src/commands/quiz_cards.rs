@@ -0,0 +1,70 @@
+use crate::db::models::*;
+use crate::services::{quiz_cards, streaks};
+use crate::services::scheduler::SchedulerConfig;
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Quiz Card Commands (5 commands)
+
+#[tauri::command]
+pub async fn sync_quiz_cards(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<QuizCardSyncResult, String> {
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+    let result = quiz_cards::sync_cards(&mut tx, &program_id, chrono::Utc::now()).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_due_quiz_cards(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<Vec<QuizCardPrompt>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut conn).await?;
+    let cards = quiz_cards::due_cards(&mut conn, &config, &program_id, chrono::Utc::now()).await?;
+
+    Ok(cards)
+}
+
+#[tauri::command]
+pub async fn answer_quiz_card(
+    pool: State<'_, SqlitePool>,
+    input: AnswerQuizCardInput,
+) -> Result<QuizCardReviewResult, String> {
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut tx).await?;
+    let now = chrono::Utc::now();
+    let result = quiz_cards::answer_card(&mut tx, &config, &input, now).await?;
+    streaks::record_activity(&mut tx, &result.card.day_plan_id, streaks::Activity::Review, now)
+        .await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_leech_cards(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<Vec<LeechCard>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let leeches = quiz_cards::leeches(&mut conn, &program_id).await?;
+
+    Ok(leeches)
+}
+
+#[tauri::command]
+pub async fn set_quiz_card_suspended(
+    pool: State<'_, SqlitePool>,
+    id: String,
+    suspended: bool,
+) -> Result<QuizCard, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let card = quiz_cards::set_suspended(&mut conn, &id, suspended, chrono::Utc::now()).await?;
+
+    Ok(card)
+}
@@ -0,0 +1,305 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::{attempt_state, quiz_grader, scoring};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use tauri::State;
+use uuid::Uuid;
+
+// Quiz Engine Commands (4 commands)
+
+#[tauri::command]
+pub async fn start_quiz(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<StartedQuiz, String> {
+    let now = chrono::Utc::now();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let (day_plan_id, status): (String, String) = sqlx::query_as(
+        "SELECT day_plan_id, status FROM day_attempts WHERE id = ?"
+    )
+    .bind(&day_attempt_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| AppError::NotFound {
+        entity: "DayAttempt".to_string(),
+        id: day_attempt_id.clone(),
+    })?;
+
+    attempt_state::ensure_editable(&status)?;
+
+    let questions = load_questions(&mut tx, &day_plan_id).await?;
+    if questions.is_empty() {
+        return Err(AppError::Validation("This day has no quiz questions".to_string()).into());
+    }
+
+    // Resume a running session; one left past its deadline is closed out with no credit
+    let open = sqlx::query_as::<_, QuizSession>(
+        "SELECT * FROM quiz_sessions WHERE day_attempt_id = ? AND status = 'in_progress'"
+    )
+    .bind(&day_attempt_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let session = match open {
+        Some(session) if !quiz_grader::is_past_deadline(session.deadline_at.as_deref(), now) => {
+            session
+        }
+        stale => {
+            if let Some(stale) = stale {
+                sqlx::query(
+                    "UPDATE quiz_sessions SET status = 'expired', submitted_at = ?,
+                        points_earned = 0, points_possible = ?, score_percent = 0
+                     WHERE id = ?"
+                )
+                .bind(now.to_rfc3339())
+                .bind(gradable_points(&questions))
+                .bind(&stale.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+
+            let deadline = quiz_grader::session_deadline(now, &questions);
+            sqlx::query_as::<_, QuizSession>(
+                "INSERT INTO quiz_sessions (id, day_attempt_id, status, started_at, deadline_at, created_at)
+                 VALUES (?, ?, 'in_progress', ?, ?, ?)
+                 RETURNING *"
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&day_attempt_id)
+            .bind(now.to_rfc3339())
+            .bind(deadline.map(|d| d.to_rfc3339()))
+            .bind(now.to_rfc3339())
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+        }
+    };
+
+    let prompts = sqlx::query_as::<_, QuizPrompt>(
+        "SELECT id, question_text, question_type, options, points, time_limit_seconds, order_index
+         FROM quiz_questions WHERE day_plan_id = ? ORDER BY order_index"
+    )
+    .bind(&day_plan_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(StartedQuiz { session, questions: prompts })
+}
+
+#[tauri::command]
+pub async fn submit_quiz_answers(
+    pool: State<'_, SqlitePool>,
+    session_id: String,
+    answers: Vec<QuizAnswerInput>,
+) -> Result<QuizResult, String> {
+    let now = chrono::Utc::now();
+    let submitted_at = now.to_rfc3339();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let session = fetch_session(&mut tx, &session_id).await?;
+    if session.status != "in_progress" {
+        return Err(AppError::InvalidStateTransition(
+            "This quiz has already been submitted".to_string(),
+        )
+        .into());
+    }
+    // A graded attempt keeps its quiz score
+    attempt_state::ensure_editable(
+        &attempt_state::current_status(&mut tx, &session.day_attempt_id).await?,
+    )?;
+
+    let (day_plan_id, program_id): (String, String) = sqlx::query_as(
+        "SELECT dp.id, dp.program_id FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE da.id = ?"
+    )
+    .bind(&session.day_attempt_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let questions = load_questions(&mut tx, &day_plan_id).await?;
+
+    let mut given: HashMap<&str, &QuizAnswerInput> = HashMap::new();
+    for answer in &answers {
+        if !questions.iter().any(|q| q.id == answer.quiz_question_id) {
+            return Err(AppError::Validation(format!(
+                "Question {} is not part of this quiz",
+                answer.quiz_question_id
+            ))
+            .into());
+        }
+        given.insert(answer.quiz_question_id.as_str(), answer);
+    }
+
+    // Past the session deadline every answer is late, whatever the per-question timings say
+    let expired = quiz_grader::is_past_deadline(session.deadline_at.as_deref(), now);
+
+    let mut points_earned = 0.0;
+    let mut points_possible = 0;
+    for question in &questions {
+        let (answer, time_taken) = given
+            .get(question.id.as_str())
+            .map_or(("", 0), |a| (a.answer.as_str(), a.time_taken_seconds.max(0)));
+
+        let timed_out = expired
+            || (question.time_limit_seconds > 0 && time_taken > question.time_limit_seconds);
+        let credit = quiz_grader::grade_answer(question, answer);
+        let earned = match credit {
+            Some(credit) if !timed_out => credit * f64::from(question.points),
+            _ => 0.0,
+        };
+        let possible = if credit.is_some() { question.points } else { 0 };
+        points_earned += earned;
+        points_possible += possible;
+
+        sqlx::query(
+            "INSERT INTO quiz_attempts (
+                id, quiz_session_id, day_attempt_id, quiz_question_id, answer, is_correct,
+                points_earned, points_possible, time_taken_seconds, timed_out, submitted_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&session.id)
+        .bind(&session.day_attempt_id)
+        .bind(&question.id)
+        .bind(answer)
+        .bind(credit.map(|credit| !timed_out && credit >= 1.0))
+        .bind(earned)
+        .bind(possible)
+        .bind(time_taken)
+        .bind(timed_out)
+        .bind(&submitted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let percent = quiz_grader::score_percent(points_earned, points_possible);
+    sqlx::query(
+        "UPDATE quiz_sessions SET status = ?, submitted_at = ?,
+            points_earned = ?, points_possible = ?, score_percent = ?
+         WHERE id = ?"
+    )
+    .bind(if expired { "expired" } else { "submitted" })
+    .bind(&submitted_at)
+    .bind(points_earned)
+    .bind(points_possible)
+    .bind(percent)
+    .bind(&session.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Only quizzes with something auto-graded replace the quiz score
+    if points_possible > 0 {
+        let rubric = scoring::rubric_for_program(&mut tx, &program_id).await?;
+        sqlx::query("UPDATE day_attempts SET score_quiz = ?, updated_at = ? WHERE id = ?")
+            .bind(quiz_grader::quiz_dimension_score(percent, &rubric.dimensions))
+            .bind(&submitted_at)
+            .bind(&session.day_attempt_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let result = load_result(&mut tx, &session_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_quiz_result(
+    pool: State<'_, SqlitePool>,
+    session_id: String,
+) -> Result<QuizResult, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let result = load_result(&mut conn, &session_id).await?;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_quiz_sessions(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<QuizSession>, String> {
+    let sessions = sqlx::query_as::<_, QuizSession>(
+        "SELECT * FROM quiz_sessions WHERE day_attempt_id = ? ORDER BY started_at DESC"
+    )
+    .bind(&day_attempt_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(sessions)
+}
+
+async fn load_questions(
+    conn: &mut SqliteConnection,
+    day_plan_id: &str,
+) -> Result<Vec<QuizQuestion>, AppError> {
+    let questions = sqlx::query_as::<_, QuizQuestion>(
+        "SELECT * FROM quiz_questions WHERE day_plan_id = ? ORDER BY order_index",
+    )
+    .bind(day_plan_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(questions)
+}
+
+fn gradable_points(questions: &[QuizQuestion]) -> i32 {
+    questions
+        .iter()
+        .filter(|q| quiz_grader::grade_answer(q, "").is_some())
+        .map(|q| q.points)
+        .sum()
+}
+
+async fn fetch_session(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<QuizSession, AppError> {
+    sqlx::query_as::<_, QuizSession>("SELECT * FROM quiz_sessions WHERE id = ?")
+        .bind(session_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "QuizSession".to_string(),
+            id: session_id.to_string(),
+        })
+}
+
+async fn load_result(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<QuizResult, AppError> {
+    let session = fetch_session(conn, session_id).await?;
+
+    let answers = sqlx::query_as::<_, QuizAttempt>(
+        "SELECT qa.* FROM quiz_attempts qa
+         JOIN quiz_questions qq ON qq.id = qa.quiz_question_id
+         WHERE qa.quiz_session_id = ?
+         ORDER BY qq.order_index",
+    )
+    .bind(session_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let score_quiz: i32 = sqlx::query_scalar("SELECT score_quiz FROM day_attempts WHERE id = ?")
+        .bind(&session.day_attempt_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(QuizResult { session, answers, score_quiz })
+}
@@ -0,0 +1,38 @@
+use crate::db::models::*;
+use crate::services::review_queue;
+use crate::services::scheduler::SchedulerConfig;
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Review Queue Commands (2 commands)
+
+#[tauri::command]
+pub async fn build_review_session(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<ReviewSession, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut conn).await?;
+    let session =
+        review_queue::build_session(&mut conn, &config, &program_id, chrono::Utc::now()).await?;
+
+    Ok(session)
+}
+
+#[tauri::command]
+pub async fn get_review_forecast(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+    days: Option<i32>,
+) -> Result<Vec<ReviewForecastDay>, String> {
+    let days = days
+        .unwrap_or(review_queue::DEFAULT_FORECAST_DAYS)
+        .clamp(1, review_queue::MAX_FORECAST_DAYS);
+
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut conn).await?;
+    let forecast =
+        review_queue::forecast(&mut conn, &config, &program_id, days, chrono::Utc::now()).await?;
+
+    Ok(forecast)
+}
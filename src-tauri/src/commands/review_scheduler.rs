@@ -0,0 +1,173 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use crate::services::fsrs_optimizer;
+use crate::services::scheduler::{self, SchedulerConfig, SchedulerKind};
+use crate::services::settings::set_setting;
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Review Scheduler Commands (4 commands)
+
+#[tauri::command]
+pub async fn get_review_scheduler_settings(
+    pool: State<'_, SqlitePool>,
+) -> Result<ReviewSchedulerSettings, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut conn).await?;
+
+    Ok(config.settings())
+}
+
+#[tauri::command]
+pub async fn update_review_scheduler_settings(
+    pool: State<'_, SqlitePool>,
+    input: UpdateReviewSchedulerInput,
+) -> Result<ReviewSchedulerSettings, String> {
+    if let Some(kind) = &input.scheduler {
+        if SchedulerKind::parse(kind).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown review scheduler {}, expected sm2 or fsrs",
+                kind
+            ))
+            .into());
+        }
+    }
+    if let Some(retention) = input.desired_retention {
+        if !scheduler::DESIRED_RETENTION_RANGE.contains(&retention) {
+            return Err(AppError::Validation(format!(
+                "Desired retention must be between {} and {}",
+                scheduler::DESIRED_RETENTION_RANGE.start(),
+                scheduler::DESIRED_RETENTION_RANGE.end()
+            ))
+            .into());
+        }
+    }
+    if let Some(days) = input.maximum_interval_days {
+        if !scheduler::MAXIMUM_INTERVAL_RANGE.contains(&days) {
+            return Err(AppError::Validation(format!(
+                "Maximum interval must be between {} and {} days",
+                scheduler::MAXIMUM_INTERVAL_RANGE.start(),
+                scheduler::MAXIMUM_INTERVAL_RANGE.end()
+            ))
+            .into());
+        }
+    }
+    if let Some(cap) = input.daily_review_cap {
+        if !scheduler::DAILY_REVIEW_CAP_RANGE.contains(&cap) {
+            return Err(AppError::Validation(format!(
+                "Daily review cap must be between {} and {}",
+                scheduler::DAILY_REVIEW_CAP_RANGE.start(),
+                scheduler::DAILY_REVIEW_CAP_RANGE.end()
+            ))
+            .into());
+        }
+    }
+
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+    if let Some(kind) = &input.scheduler {
+        set_setting(&mut tx, scheduler::SCHEDULER_SETTING, kind).await?;
+    }
+    if let Some(retention) = input.desired_retention {
+        set_setting(&mut tx, scheduler::DESIRED_RETENTION_SETTING, &retention).await?;
+    }
+    if let Some(days) = input.maximum_interval_days {
+        set_setting(&mut tx, scheduler::MAXIMUM_INTERVAL_SETTING, &days).await?;
+    }
+    if let Some(cap) = input.daily_review_cap {
+        set_setting(&mut tx, scheduler::DAILY_REVIEW_CAP_SETTING, &cap).await?;
+    }
+    if input.reset_fsrs_parameters {
+        sqlx::query("DELETE FROM settings WHERE key = ?")
+            .bind(scheduler::PARAMETERS_SETTING)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let config = SchedulerConfig::load(&mut tx).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(config.settings())
+}
+
+#[tauri::command]
+pub async fn list_review_log(
+    pool: State<'_, SqlitePool>,
+    day_plan_id: String,
+    concept_tag_id: String,
+) -> Result<Vec<ReviewLog>, String> {
+    let logs = sqlx::query_as::<_, ReviewLog>(
+        "SELECT * FROM review_log
+         WHERE day_plan_id = ? AND concept_tag_id = ?
+         ORDER BY reviewed_at DESC"
+    )
+    .bind(&day_plan_id)
+    .bind(&concept_tag_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(logs)
+}
+
+/// Fits FSRS parameters to the whole review log, quiz card reviews included. Runs locally;
+/// `apply` saves them when they predict recall better than the current ones.
+#[tauri::command]
+pub async fn optimize_fsrs_parameters(
+    pool: State<'_, SqlitePool>,
+    apply: bool,
+) -> Result<FsrsOptimizationReport, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let config = SchedulerConfig::load(&mut conn).await?;
+    let mut logs = sqlx::query_as::<_, ReviewLog>(
+        "SELECT * FROM review_log ORDER BY day_plan_id, concept_tag_id, reviewed_at, id"
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    // Quiz card reviews are keyed by card, so each card stays one contiguous history after
+    // the concept reviews.
+    let card_logs = sqlx::query_as::<_, ReviewLog>(
+        "SELECT id, quiz_card_id AS day_plan_id, '' AS concept_tag_id, scheduler, score, rating,
+            elapsed_days, scheduled_days, stability, difficulty, reviewed_at
+         FROM quiz_card_reviews
+         ORDER BY quiz_card_id, reviewed_at, id"
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    logs.extend(card_logs);
+
+    let histories = fsrs_optimizer::histories(&logs);
+    let (log_loss_before, review_count) = fsrs_optimizer::log_loss(&config.parameters, &histories);
+    if review_count < fsrs_optimizer::MIN_REVIEWS {
+        return Err(AppError::Validation(format!(
+            "Optimizing needs at least {} repeat reviews in the log, found {}",
+            fsrs_optimizer::MIN_REVIEWS,
+            review_count
+        ))
+        .into());
+    }
+
+    let start = config.parameters;
+    let (parameters, histories) = tokio::task::spawn_blocking(move || {
+        let parameters = fsrs_optimizer::fit(&start, &histories);
+        (parameters, histories)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let (log_loss_after, _) = fsrs_optimizer::log_loss(&parameters, &histories);
+
+    let applied = apply && log_loss_after < log_loss_before;
+    if applied {
+        set_setting(&mut conn, scheduler::PARAMETERS_SETTING, &parameters.to_vec()).await?;
+    }
+
+    Ok(FsrsOptimizationReport {
+        item_count: histories.len() as i32,
+        review_count: review_count as i32,
+        log_loss_before,
+        log_loss_after,
+        parameters: parameters.to_vec(),
+        applied,
+    })
+}
@@ -0,0 +1,136 @@
+use crate::db::models::*;
+use crate::services::scoring;
+use sqlx::SqlitePool;
+use tauri::State;
+use uuid::Uuid;
+
+// Scoring Rubric Commands (4 commands)
+
+#[tauri::command]
+pub async fn get_program_rubric(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<RubricWithDimensions, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let rubric = scoring::rubric_for_program(&mut conn, &program_id).await?;
+
+    Ok(rubric)
+}
+
+#[tauri::command]
+pub async fn save_program_rubric(
+    pool: State<'_, SqlitePool>,
+    input: SaveRubricInput,
+) -> Result<RubricWithDimensions, String> {
+    scoring::validate_rubric(input.pass_threshold, input.mastery_threshold, &input.dimensions)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let existing_id: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM scoring_rubrics WHERE program_id = ?"
+    )
+    .bind(&input.program_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let rubric_id = if let Some(id) = existing_id {
+        sqlx::query(
+            "UPDATE scoring_rubrics SET name = ?, pass_threshold = ?, mastery_threshold = ?, updated_at = ?
+             WHERE id = ?"
+        )
+        .bind(&input.name)
+        .bind(input.pass_threshold)
+        .bind(input.mastery_threshold)
+        .bind(&now)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        id
+    } else {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO scoring_rubrics (id, program_id, name, pass_threshold, mastery_threshold, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(&input.program_id)
+        .bind(&input.name)
+        .bind(input.pass_threshold)
+        .bind(input.mastery_threshold)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        id
+    };
+
+    // Past attempts keep their own copy of max points and weights, so replacing is safe
+    sqlx::query("DELETE FROM rubric_dimensions WHERE rubric_id = ?")
+        .bind(&rubric_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for (order_index, dimension) in (0i32..).zip(&input.dimensions) {
+        sqlx::query(
+            "INSERT INTO rubric_dimensions (id, rubric_id, key, label, max_points, weight, order_index)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&rubric_id)
+        .bind(dimension.key.trim())
+        .bind(dimension.label.trim())
+        .bind(dimension.max_points)
+        .bind(dimension.weight)
+        .bind(order_index)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let rubric = scoring::rubric_for_program(&mut tx, &input.program_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(rubric)
+}
+
+#[tauri::command]
+pub async fn reset_program_rubric(
+    pool: State<'_, SqlitePool>,
+    program_id: String,
+) -> Result<RubricWithDimensions, String> {
+    sqlx::query("DELETE FROM scoring_rubrics WHERE program_id = ?")
+        .bind(&program_id)
+        .execute(pool.inner())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let rubric = scoring::rubric_for_program(&mut conn, &program_id).await?;
+
+    Ok(rubric)
+}
+
+#[tauri::command]
+pub async fn get_attempt_dimension_scores(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<AttemptDimensionScore>, String> {
+    let scores = sqlx::query_as::<_, AttemptDimensionScore>(
+        "SELECT ads.* FROM attempt_dimension_scores ads
+         JOIN day_attempts da ON da.id = ads.day_attempt_id
+         LEFT JOIN rubric_dimensions rd ON rd.rubric_id = da.rubric_id AND rd.key = ads.dimension_key
+         WHERE ads.day_attempt_id = ?
+         ORDER BY COALESCE(rd.order_index, 999), ads.dimension_key"
+    )
+    .bind(&day_attempt_id)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(scores)
+}
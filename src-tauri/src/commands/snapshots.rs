@@ -0,0 +1,75 @@
+use crate::db::models::*;
+use crate::services::attempt_state;
+use crate::services::snapshots::{self, DraftState};
+use sqlx::SqlitePool;
+use tauri::State;
+
+// Autosave Snapshot Commands (4 commands)
+
+#[tauri::command]
+pub async fn list_attempt_snapshots(
+    pool: State<'_, SqlitePool>,
+    day_attempt_id: String,
+) -> Result<Vec<AttemptSnapshotSummary>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let summaries = snapshots::list_summaries(&mut conn, &day_attempt_id).await?;
+
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub async fn preview_attempt_snapshot(
+    pool: State<'_, SqlitePool>,
+    snapshot_id: String,
+) -> Result<SnapshotPreview, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+
+    let snapshot = snapshots::fetch_snapshot(&mut conn, &snapshot_id).await?;
+    let attempt = sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+        .bind(&snapshot.day_attempt_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let draft = DraftState::from_snapshot(&snapshot)?;
+    let saved = snapshots::saved_state(&mut conn, &attempt).await?;
+
+    Ok(SnapshotPreview {
+        changed_fields: draft.changed_fields(&saved),
+        exercise_entries: draft.exercise_entries,
+        snapshot,
+    })
+}
+
+#[tauri::command]
+pub async fn restore_attempt_snapshot(
+    pool: State<'_, SqlitePool>,
+    snapshot_id: String,
+) -> Result<DayAttempt, String> {
+    let mut tx = pool.inner().begin().await.map_err(|e| e.to_string())?;
+
+    let snapshot = snapshots::fetch_snapshot(&mut tx, &snapshot_id).await?;
+    let attempt = sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+        .bind(&snapshot.day_attempt_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    attempt_state::ensure_editable(&attempt.status)?;
+
+    let draft = DraftState::from_snapshot(&snapshot)?;
+    let restored = snapshots::restore(&mut tx, &attempt, &draft).await?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn find_recoverable_drafts(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<RecoverableDraft>, String> {
+    let mut conn = pool.inner().acquire().await.map_err(|e| e.to_string())?;
+    let drafts = snapshots::find_recoverable(&mut conn).await?;
+
+    Ok(drafts)
+}
@@ -1,7 +1,7 @@
 CREATE TABLE IF NOT EXISTS import_jobs (
     id TEXT PRIMARY KEY NOT NULL,
     program_id TEXT REFERENCES programs(id) ON DELETE SET NULL,
-    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'extracting', 'analyzing', 'generating', 'review', 'applying', 'completed', 'failed')),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'extracting', 'analyzing', 'generating', 'review', 'applying', 'completed', 'failed', 'cancelled')),
     source_type TEXT NOT NULL CHECK (source_type IN ('pdf', 'markdown', 'text', 'multi_file')),
     
     source_files_json TEXT NOT NULL DEFAULT '[]',
@@ -29,6 +29,6 @@ CREATE TABLE IF NOT EXISTS import_jobs (
     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
 );
 
-CREATE INDEX idx_import_jobs_status ON import_jobs(status);
-CREATE INDEX idx_import_jobs_program ON import_jobs(program_id);
-CREATE INDEX idx_import_jobs_created ON import_jobs(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_import_jobs_status ON import_jobs(status);
+CREATE INDEX IF NOT EXISTS idx_import_jobs_program ON import_jobs(program_id);
+CREATE INDEX IF NOT EXISTS idx_import_jobs_created ON import_jobs(created_at DESC);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS import_plan_edits (
+    id TEXT PRIMARY KEY NOT NULL,
+    job_id TEXT NOT NULL REFERENCES import_jobs(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    operation_json TEXT NOT NULL,
+    plan_before_json TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(job_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_import_plan_edits_job ON import_plan_edits(job_id, sequence);
@@ -15,4 +15,4 @@ CREATE TABLE IF NOT EXISTS user_capacity_profiles (
 INSERT OR IGNORE INTO user_capacity_profiles (id, user_id) 
 VALUES ('default-profile', 'default');
 
-CREATE INDEX idx_capacity_user ON user_capacity_profiles(user_id);
+CREATE INDEX IF NOT EXISTS idx_capacity_user ON user_capacity_profiles(user_id);
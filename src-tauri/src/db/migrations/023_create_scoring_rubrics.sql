@@ -0,0 +1,132 @@
+CREATE TABLE IF NOT EXISTS scoring_rubrics (
+    id TEXT PRIMARY KEY NOT NULL,
+    program_id TEXT REFERENCES programs(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    pass_threshold INTEGER NOT NULL DEFAULT 70 CHECK (pass_threshold >= 0 AND pass_threshold <= 100),
+    mastery_threshold INTEGER NOT NULL DEFAULT 95 CHECK (mastery_threshold >= 0 AND mastery_threshold <= 100),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    CHECK (mastery_threshold >= pass_threshold)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_scoring_rubrics_program ON scoring_rubrics(program_id);
+
+CREATE TABLE IF NOT EXISTS rubric_dimensions (
+    id TEXT PRIMARY KEY NOT NULL,
+    rubric_id TEXT NOT NULL REFERENCES scoring_rubrics(id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    label TEXT NOT NULL,
+    max_points INTEGER NOT NULL CHECK (max_points > 0),
+    weight REAL NOT NULL DEFAULT 1.0 CHECK (weight >= 0),
+    order_index INTEGER NOT NULL DEFAULT 0,
+    UNIQUE(rubric_id, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rubric_dimensions_rubric ON rubric_dimensions(rubric_id, order_index);
+
+-- The built-in rubric used by programs without their own. Weights equal max points,
+-- so the weighted total matches the old fixed 40/20/15/15/10 sum.
+INSERT OR IGNORE INTO scoring_rubrics (id, program_id, name, pass_threshold, mastery_threshold)
+VALUES ('default-rubric', NULL, 'Default', 70, 95);
+
+INSERT OR IGNORE INTO rubric_dimensions (id, rubric_id, key, label, max_points, weight, order_index) VALUES
+    ('default-rubric-implementation', 'default-rubric', 'implementation', 'Implementation', 40, 40, 0),
+    ('default-rubric-code-quality', 'default-rubric', 'code_quality', 'Code Quality', 20, 20, 1),
+    ('default-rubric-accessibility', 'default-rubric', 'accessibility', 'Accessibility', 15, 15, 2),
+    ('default-rubric-performance', 'default-rubric', 'performance', 'Performance', 15, 15, 3),
+    ('default-rubric-quiz', 'default-rubric', 'quiz', 'Quiz', 10, 10, 4);
+
+-- Rebuild day_attempts: per-dimension limits now live in the rubric, and total_score is
+-- computed from it instead of being a generated column.
+CREATE TABLE day_attempts_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    day_plan_version INTEGER NOT NULL DEFAULT 1,
+    attempt_number INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN ('in_progress', 'submitted', 'blocked', 'passed', 'mastery')),
+    rubric_id TEXT REFERENCES scoring_rubrics(id) ON DELETE SET NULL,
+    score_implementation INTEGER NOT NULL DEFAULT 0,
+    score_code_quality INTEGER NOT NULL DEFAULT 0,
+    score_accessibility INTEGER NOT NULL DEFAULT 0,
+    score_performance INTEGER NOT NULL DEFAULT 0,
+    score_quiz INTEGER NOT NULL DEFAULT 0,
+    total_score INTEGER NOT NULL DEFAULT 0 CHECK (total_score >= 0 AND total_score <= 100),
+    memory_rebuild_completed INTEGER NOT NULL DEFAULT 0,
+    memory_rebuild_passed INTEGER NOT NULL DEFAULT 0,
+    memory_rebuild_notes TEXT NOT NULL DEFAULT '',
+    what_broke TEXT NOT NULL DEFAULT '',
+    why_broke TEXT NOT NULL DEFAULT '',
+    how_fixed TEXT NOT NULL DEFAULT '',
+    refactor_tomorrow TEXT NOT NULL DEFAULT '',
+    daily_summary TEXT NOT NULL DEFAULT '',
+    what_went_well TEXT NOT NULL DEFAULT '',
+    what_to_improve TEXT NOT NULL DEFAULT '',
+    key_learnings TEXT NOT NULL DEFAULT '',
+    exercise_notes TEXT NOT NULL DEFAULT '',
+    code_snapshot TEXT NOT NULL DEFAULT '',
+    started_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    submitted_at TEXT,
+    actual_minutes INTEGER NOT NULL DEFAULT 0,
+    is_draft INTEGER NOT NULL DEFAULT 1,
+    last_autosave TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(day_plan_id, attempt_number)
+);
+
+INSERT INTO day_attempts_new (
+    id, day_plan_id, day_plan_version, attempt_number, status, rubric_id,
+    score_implementation, score_code_quality, score_accessibility, score_performance, score_quiz,
+    total_score, memory_rebuild_completed, memory_rebuild_passed, memory_rebuild_notes,
+    what_broke, why_broke, how_fixed, refactor_tomorrow, daily_summary, exercise_notes,
+    code_snapshot, started_at, submitted_at, actual_minutes, is_draft, last_autosave,
+    created_at, updated_at
+)
+SELECT
+    id, day_plan_id, day_plan_version, attempt_number, status,
+    CASE WHEN status = 'in_progress' THEN NULL ELSE 'default-rubric' END,
+    score_implementation, score_code_quality, score_accessibility, score_performance, score_quiz,
+    total_score, memory_rebuild_completed, memory_rebuild_passed, memory_rebuild_notes,
+    what_broke, why_broke, how_fixed, refactor_tomorrow, daily_summary, exercise_notes,
+    code_snapshot, started_at, submitted_at, actual_minutes, is_draft, last_autosave,
+    created_at, updated_at
+FROM day_attempts;
+
+DROP TABLE day_attempts;
+
+ALTER TABLE day_attempts_new RENAME TO day_attempts;
+
+CREATE INDEX IF NOT EXISTS idx_day_attempts_plan ON day_attempts(day_plan_id);
+CREATE INDEX IF NOT EXISTS idx_day_attempts_status ON day_attempts(status);
+CREATE INDEX IF NOT EXISTS idx_day_attempts_score ON day_attempts(total_score);
+CREATE INDEX IF NOT EXISTS idx_day_attempts_submitted ON day_attempts(submitted_at);
+
+CREATE TABLE IF NOT EXISTS attempt_dimension_scores (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    dimension_key TEXT NOT NULL,
+    label TEXT NOT NULL,
+    score INTEGER NOT NULL CHECK (score >= 0),
+    max_points INTEGER NOT NULL CHECK (max_points > 0),
+    weight REAL NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(day_attempt_id, dimension_key),
+    CHECK (score <= max_points)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attempt_dimension_scores_attempt ON attempt_dimension_scores(day_attempt_id);
+
+-- Carry scores of already-submitted attempts over as default-rubric dimension scores
+INSERT OR IGNORE INTO attempt_dimension_scores (id, day_attempt_id, dimension_key, label, score, max_points, weight)
+SELECT da.id || '-' || rd.key, da.id, rd.key, rd.label,
+    CASE rd.key
+        WHEN 'implementation' THEN da.score_implementation
+        WHEN 'code_quality' THEN da.score_code_quality
+        WHEN 'accessibility' THEN da.score_accessibility
+        WHEN 'performance' THEN da.score_performance
+        ELSE da.score_quiz
+    END,
+    rd.max_points, rd.weight
+FROM day_attempts da
+JOIN rubric_dimensions rd ON rd.rubric_id = 'default-rubric'
+WHERE da.status != 'in_progress'
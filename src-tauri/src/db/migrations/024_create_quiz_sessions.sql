@@ -0,0 +1,66 @@
+-- Rebuild quiz_questions: the column is `options` everywhere in the code, the auto-graded
+-- types need room in the CHECK, and numeric questions carry their own tolerance.
+CREATE TABLE quiz_questions_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    question_text TEXT NOT NULL,
+    question_type TEXT NOT NULL DEFAULT 'short_answer' CHECK (question_type IN ('short_answer', 'multiple_choice', 'multi_select', 'true_false', 'numeric', 'code_prompt', 'reflection')),
+    correct_answer TEXT NOT NULL DEFAULT '',
+    options TEXT NOT NULL DEFAULT '[]',
+    answer_tolerance REAL NOT NULL DEFAULT 0 CHECK (answer_tolerance >= 0),
+    order_index INTEGER NOT NULL DEFAULT 0,
+    points INTEGER NOT NULL DEFAULT 1,
+    time_limit_seconds INTEGER NOT NULL DEFAULT 120,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT INTO quiz_questions_new (
+    id, day_plan_id, question_text, question_type, correct_answer, options,
+    order_index, points, time_limit_seconds, created_at
+)
+SELECT
+    id, day_plan_id, question_text, question_type, correct_answer, options_json,
+    order_index, points, time_limit_seconds, created_at
+FROM quiz_questions;
+
+-- quiz_attempts was never written to, so it is recreated rather than copied
+DROP TABLE IF EXISTS quiz_attempts;
+
+DROP TABLE quiz_questions;
+
+ALTER TABLE quiz_questions_new RENAME TO quiz_questions;
+
+CREATE INDEX IF NOT EXISTS idx_quiz_questions_plan ON quiz_questions(day_plan_id);
+
+CREATE TABLE IF NOT EXISTS quiz_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN ('in_progress', 'submitted', 'expired')),
+    started_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deadline_at TEXT,
+    submitted_at TEXT,
+    points_earned REAL NOT NULL DEFAULT 0,
+    points_possible INTEGER NOT NULL DEFAULT 0,
+    score_percent INTEGER NOT NULL DEFAULT 0 CHECK (score_percent >= 0 AND score_percent <= 100),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_sessions_attempt ON quiz_sessions(day_attempt_id, started_at);
+
+CREATE TABLE IF NOT EXISTS quiz_attempts (
+    id TEXT PRIMARY KEY NOT NULL,
+    quiz_session_id TEXT NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    quiz_question_id TEXT NOT NULL REFERENCES quiz_questions(id) ON DELETE CASCADE,
+    answer TEXT NOT NULL DEFAULT '',
+    is_correct INTEGER,
+    points_earned REAL NOT NULL DEFAULT 0,
+    points_possible INTEGER NOT NULL DEFAULT 0,
+    time_taken_seconds INTEGER NOT NULL DEFAULT 0,
+    timed_out INTEGER NOT NULL DEFAULT 0,
+    submitted_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(quiz_session_id, quiz_question_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_attempts_attempt ON quiz_attempts(day_attempt_id);
+CREATE INDEX IF NOT EXISTS idx_quiz_attempts_question ON quiz_attempts(quiz_question_id);
@@ -0,0 +1,27 @@
+-- Rebuild attempt_checklist around the model's column names, with timestamps and an
+-- optional artifact backing up each checked item.
+CREATE TABLE attempt_checklist_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    checklist_item_id TEXT NOT NULL REFERENCES checklist_items(id) ON DELETE CASCADE,
+    is_completed INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    artifact_id TEXT REFERENCES artifacts(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(day_attempt_id, checklist_item_id)
+);
+
+INSERT INTO attempt_checklist_new (id, day_attempt_id, checklist_item_id, is_completed, completed_at)
+SELECT id, attempt_id, checklist_item_id, is_completed, completed_at
+FROM attempt_checklist;
+
+DROP TABLE attempt_checklist;
+
+ALTER TABLE attempt_checklist_new RENAME TO attempt_checklist;
+
+CREATE INDEX IF NOT EXISTS idx_attempt_checklist_attempt ON attempt_checklist(day_attempt_id);
+CREATE INDEX IF NOT EXISTS idx_attempt_checklist_artifact ON attempt_checklist(artifact_id);
+
+-- 'warn' lets submission through with unchecked required items, 'block' refuses it
+INSERT OR IGNORE INTO settings (key, value) VALUES ('checklist_enforcement', '"warn"');
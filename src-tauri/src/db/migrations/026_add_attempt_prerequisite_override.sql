@@ -0,0 +1,3 @@
+-- Why an attempt was started before its prerequisites were met, when the learner chose to
+-- skip ahead anyway
+ALTER TABLE day_attempts ADD COLUMN prerequisite_override_reason TEXT
@@ -0,0 +1,45 @@
+CREATE TABLE IF NOT EXISTS attempt_snapshots (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    daily_summary TEXT NOT NULL DEFAULT '',
+    what_went_well TEXT NOT NULL DEFAULT '',
+    what_to_improve TEXT NOT NULL DEFAULT '',
+    key_learnings TEXT NOT NULL DEFAULT '',
+    memory_rebuild_notes TEXT NOT NULL DEFAULT '',
+    code_snapshot TEXT NOT NULL DEFAULT '',
+    exercise_entries_json TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(day_attempt_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attempt_snapshots_attempt ON attempt_snapshots(day_attempt_id, sequence);
+
+-- How many autosave snapshots each attempt keeps before the oldest are dropped
+INSERT OR IGNORE INTO settings (key, value) VALUES ('autosave_snapshot_limit', '20');
+
+-- Snapshots round-trip exercise entries, so the table has to match the model: one code
+-- buffer plus free-form notes per entry.
+CREATE TABLE exercise_entries_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    language TEXT NOT NULL DEFAULT '',
+    code TEXT NOT NULL DEFAULT '',
+    notes TEXT NOT NULL DEFAULT '',
+    order_index INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT INTO exercise_entries_new (id, day_attempt_id, language, code, notes, order_index, created_at, updated_at)
+SELECT id, attempt_id, COALESCE(language, ''),
+    CASE WHEN entry_type = 'markdown' THEN '' ELSE content END,
+    CASE WHEN entry_type = 'markdown' THEN content ELSE title END,
+    order_index, created_at, updated_at
+FROM exercise_entries;
+
+DROP TABLE exercise_entries;
+
+ALTER TABLE exercise_entries_new RENAME TO exercise_entries;
+
+CREATE INDEX IF NOT EXISTS idx_exercise_entries_attempt ON exercise_entries(day_attempt_id);
@@ -0,0 +1,32 @@
+-- Attempt comparison reads bug logs through the model, so bring the table in line with it:
+-- fix/prevention columns renamed, plus time spent and an update timestamp.
+CREATE TABLE bug_logs_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    category TEXT NOT NULL DEFAULT 'general',
+    severity TEXT NOT NULL DEFAULT 'medium' CHECK (severity IN ('low', 'medium', 'high', 'critical')),
+    symptom TEXT NOT NULL,
+    root_cause TEXT NOT NULL DEFAULT '',
+    fix_applied TEXT NOT NULL DEFAULT '',
+    prevention_strategy TEXT NOT NULL DEFAULT '',
+    time_to_fix_minutes INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT INTO bug_logs_new (
+    id, day_attempt_id, category, severity, symptom, root_cause, fix_applied,
+    prevention_strategy, created_at, updated_at
+)
+SELECT
+    id, attempt_id, category, severity, symptom, root_cause, fix,
+    prevention_rule, created_at, created_at
+FROM bug_logs;
+
+DROP TABLE bug_logs;
+
+ALTER TABLE bug_logs_new RENAME TO bug_logs;
+
+CREATE INDEX IF NOT EXISTS idx_bug_logs_attempt ON bug_logs(day_attempt_id);
+CREATE INDEX IF NOT EXISTS idx_bug_logs_category ON bug_logs(category);
+CREATE INDEX IF NOT EXISTS idx_bug_logs_severity ON bug_logs(severity);
@@ -0,0 +1,22 @@
+-- What a correct run prints, compared against stdout when the exercise is run
+ALTER TABLE exercise_entries ADD COLUMN expected_output TEXT;
+
+CREATE TABLE IF NOT EXISTS exercise_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    exercise_entry_id TEXT NOT NULL REFERENCES exercise_entries(id) ON DELETE CASCADE,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    language TEXT NOT NULL,
+    code TEXT NOT NULL,
+    stage TEXT NOT NULL CHECK (stage IN ('compile', 'run')),
+    exit_code INTEGER,
+    timed_out INTEGER NOT NULL DEFAULT 0,
+    stdout TEXT NOT NULL DEFAULT '',
+    stderr TEXT NOT NULL DEFAULT '',
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    expected_output TEXT,
+    passed INTEGER,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_exercise_runs_attempt ON exercise_runs(day_attempt_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_exercise_runs_entry ON exercise_runs(exercise_entry_id, created_at);
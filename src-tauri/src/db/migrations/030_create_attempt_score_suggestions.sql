@@ -0,0 +1,20 @@
+-- The learner's working directory for the day's project, analyzed alongside the exercises
+ALTER TABLE day_attempts ADD COLUMN project_path TEXT;
+
+CREATE TABLE IF NOT EXISTS attempt_score_suggestions (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    dimension_key TEXT NOT NULL,
+    suggested_score INTEGER NOT NULL,
+    max_points INTEGER NOT NULL,
+    explanation TEXT NOT NULL DEFAULT '',
+    findings_json TEXT NOT NULL DEFAULT '[]',
+    metrics_json TEXT NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'overridden')),
+    final_score INTEGER,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    resolved_at TEXT,
+    UNIQUE(day_attempt_id, dimension_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attempt_score_suggestions_attempt ON attempt_score_suggestions(day_attempt_id);
@@ -0,0 +1,19 @@
+-- Commits read from the attempt's project repository, one record per attempt that is
+-- replaced on every capture
+CREATE TABLE IF NOT EXISTS git_evidence (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL UNIQUE REFERENCES day_attempts(id) ON DELETE CASCADE,
+    repository_path TEXT NOT NULL,
+    branch TEXT NOT NULL DEFAULT '',
+    head_commit TEXT,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    commit_count INTEGER NOT NULL DEFAULT 0,
+    files_changed INTEGER NOT NULL DEFAULT 0,
+    insertions INTEGER NOT NULL DEFAULT 0,
+    deletions INTEGER NOT NULL DEFAULT 0,
+    commits_json TEXT NOT NULL DEFAULT '[]',
+    tree_json TEXT NOT NULL DEFAULT '[]',
+    estimated_minutes INTEGER NOT NULL DEFAULT 0,
+    captured_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
@@ -0,0 +1,22 @@
+-- Timed rebuilds of the day's code from memory, checked against the learner's best attempt
+CREATE TABLE IF NOT EXISTS memory_rebuild_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    reference_attempt_id TEXT REFERENCES day_attempts(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'in_progress' CHECK (status IN ('in_progress', 'submitted', 'expired')),
+    time_limit_minutes INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT NOT NULL,
+    deadline_at TEXT,
+    submitted_at TEXT,
+    elapsed_seconds INTEGER,
+    rebuilt_code TEXT NOT NULL DEFAULT '',
+    token_similarity REAL,
+    target_coverage REAL,
+    targets_json TEXT NOT NULL DEFAULT '[]',
+    missing_identifiers_json TEXT NOT NULL DEFAULT '[]',
+    suggested_pass INTEGER,
+    evidence TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_rebuild_sessions_attempt ON memory_rebuild_sessions(day_attempt_id, started_at);
@@ -0,0 +1,46 @@
+-- Rebuild artifacts around the model's column names, with the content hash and store
+-- location of imported files. Imported files live under app_data/artifacts at a path
+-- derived from their SHA-256, so identical files are stored once.
+CREATE TABLE artifacts_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    artifact_type TEXT NOT NULL CHECK (artifact_type IN ('file', 'screenshot', 'link', 'code', 'note')),
+    title TEXT NOT NULL DEFAULT '',
+    content TEXT NOT NULL DEFAULT '',
+    file_path TEXT,
+    file_size INTEGER,
+    mime_type TEXT,
+    url TEXT,
+    sha256 TEXT,
+    storage_path TEXT,
+    thumbnail_path TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT INTO artifacts_new (
+    id, day_attempt_id, artifact_type, title, content, file_path, file_size, mime_type, url, created_at
+)
+SELECT
+    id,
+    attempt_id,
+    CASE artifact_type
+        WHEN 'code_snippet' THEN 'code'
+        WHEN 'markdown_note' THEN 'note'
+        ELSE artifact_type
+    END,
+    title,
+    COALESCE(code_content, markdown_content, description, ''),
+    file_path,
+    file_size_bytes,
+    mime_type,
+    url,
+    created_at
+FROM artifacts;
+
+DROP TABLE artifacts;
+
+ALTER TABLE artifacts_new RENAME TO artifacts;
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_attempt ON artifacts(day_attempt_id);
+CREATE INDEX IF NOT EXISTS idx_artifacts_type ON artifacts(artifact_type);
+CREATE INDEX IF NOT EXISTS idx_artifacts_sha256 ON artifacts(sha256);
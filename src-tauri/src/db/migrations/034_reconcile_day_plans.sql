@@ -0,0 +1,9 @@
+-- Time budget columns DayPlan reads and the import and editor services write, which only
+-- the legacy migration tree defined
+ALTER TABLE day_plans ADD COLUMN min_minutes INTEGER NOT NULL DEFAULT 90;
+ALTER TABLE day_plans ADD COLUMN recommended_minutes INTEGER NOT NULL DEFAULT 120;
+ALTER TABLE day_plans ADD COLUMN deep_minutes INTEGER NOT NULL DEFAULT 180;
+ALTER TABLE day_plans ADD COLUMN complexity_level INTEGER NOT NULL DEFAULT 3 CHECK (complexity_level >= 1 AND complexity_level <= 5);
+ALTER TABLE day_plans ADD COLUMN focus_blocks TEXT NOT NULL DEFAULT '[]';
+
+CREATE INDEX IF NOT EXISTS idx_day_plans_complexity ON day_plans(complexity_level);
@@ -0,0 +1,53 @@
+-- Rebuild day_plan_tags and spaced_repetition around the model's column names. Review
+-- state is tracked per day and concept, so rows without a concept tag are not carried over.
+CREATE TABLE day_plan_tags_new (
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    concept_tag_id TEXT NOT NULL REFERENCES concept_tags(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY(day_plan_id, concept_tag_id)
+);
+
+INSERT INTO day_plan_tags_new (day_plan_id, concept_tag_id)
+SELECT day_plan_id, tag_id FROM day_plan_tags;
+
+DROP TABLE day_plan_tags;
+
+ALTER TABLE day_plan_tags_new RENAME TO day_plan_tags;
+
+CREATE INDEX IF NOT EXISTS idx_day_plan_tags_plan ON day_plan_tags(day_plan_id);
+CREATE INDEX IF NOT EXISTS idx_day_plan_tags_tag ON day_plan_tags(concept_tag_id);
+
+CREATE TABLE spaced_repetition_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    concept_tag_id TEXT NOT NULL REFERENCES concept_tags(id) ON DELETE CASCADE,
+    easiness_factor REAL NOT NULL DEFAULT 2.5,
+    interval_days INTEGER NOT NULL DEFAULT 1,
+    repetition_count INTEGER NOT NULL DEFAULT 0,
+    last_review_date TEXT NOT NULL,
+    next_review_date TEXT NOT NULL,
+    last_score INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(day_plan_id, concept_tag_id)
+);
+
+-- last_quality is the 0-5 SM-2 grade, last_score the 0-100 attempt score it came from
+INSERT INTO spaced_repetition_new (
+    id, day_plan_id, concept_tag_id, easiness_factor, interval_days, repetition_count,
+    last_review_date, next_review_date, last_score, created_at, updated_at
+)
+SELECT
+    id, day_plan_id, tag_id, easiness_factor, interval_days, repetitions,
+    COALESCE(last_review_date, created_at), next_review_date, COALESCE(last_quality, 0) * 20,
+    created_at, updated_at
+FROM spaced_repetition
+WHERE tag_id IS NOT NULL;
+
+DROP TABLE spaced_repetition;
+
+ALTER TABLE spaced_repetition_new RENAME TO spaced_repetition;
+
+CREATE INDEX IF NOT EXISTS idx_spaced_repetition_next_review ON spaced_repetition(next_review_date);
+CREATE INDEX IF NOT EXISTS idx_spaced_repetition_plan ON spaced_repetition(day_plan_id);
+CREATE INDEX IF NOT EXISTS idx_spaced_repetition_tag ON spaced_repetition(concept_tag_id);
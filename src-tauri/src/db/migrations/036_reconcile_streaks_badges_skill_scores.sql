@@ -0,0 +1,60 @@
+-- Rebuild streaks around the model's column names and add the freeze log it writes to
+CREATE TABLE streaks_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    program_id TEXT NOT NULL UNIQUE REFERENCES programs(id) ON DELETE CASCADE,
+    current_streak INTEGER NOT NULL DEFAULT 0,
+    longest_streak INTEGER NOT NULL DEFAULT 0,
+    last_activity_date TEXT NOT NULL DEFAULT '',
+    freezes_available INTEGER NOT NULL DEFAULT 2,
+    freezes_used_this_month INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT INTO streaks_new (
+    id, program_id, current_streak, longest_streak, last_activity_date,
+    freezes_available, freezes_used_this_month, created_at, updated_at
+)
+SELECT
+    id, program_id, current_streak, longest_streak, COALESCE(last_active_date, ''),
+    streak_freezes_available, streak_freezes_used, created_at, updated_at
+FROM streaks;
+
+DROP TABLE streaks;
+
+ALTER TABLE streaks_new RENAME TO streaks;
+
+CREATE TABLE IF NOT EXISTS streak_freezes (
+    id TEXT PRIMARY KEY NOT NULL,
+    program_id TEXT NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
+    used_date TEXT NOT NULL,
+    reason TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_streak_freezes_program ON streak_freezes(program_id, used_date);
+
+ALTER TABLE badges RENAME COLUMN metadata_json TO metadata;
+
+-- Scores are whole numbers averaged with integer arithmetic, so the column holds integers
+-- that the model can decode
+CREATE TABLE skill_scores_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    program_id TEXT NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
+    domain TEXT NOT NULL,
+    score INTEGER NOT NULL DEFAULT 0 CHECK (score >= 0 AND score <= 100),
+    total_attempts INTEGER NOT NULL DEFAULT 0,
+    last_updated TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(program_id, domain)
+);
+
+INSERT INTO skill_scores_new (id, program_id, domain, score, total_attempts, last_updated, created_at)
+SELECT id, program_id, domain, CAST(ROUND(score) AS INTEGER), data_points, last_assessed, created_at
+FROM skill_scores;
+
+DROP TABLE skill_scores;
+
+ALTER TABLE skill_scores_new RENAME TO skill_scores;
+
+CREATE INDEX IF NOT EXISTS idx_skill_scores_program ON skill_scores(program_id);
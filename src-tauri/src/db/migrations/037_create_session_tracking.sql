@@ -0,0 +1,70 @@
+-- Session tracking tables that only the legacy migration tree defined
+CREATE TABLE IF NOT EXISTS day_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_attempt_id TEXT NOT NULL REFERENCES day_attempts(id) ON DELETE CASCADE,
+    session_type TEXT NOT NULL CHECK (session_type IN ('learn', 'build', 'debug', 'rebuild', 'quiz', 'review')),
+    planned_minutes INTEGER NOT NULL DEFAULT 0,
+    actual_minutes INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT,
+    ended_at TEXT,
+    status TEXT NOT NULL DEFAULT 'planned' CHECK (status IN ('planned', 'in_progress', 'done', 'skipped')),
+    notes TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_day_sessions_attempt ON day_sessions(day_attempt_id);
+CREATE INDEX IF NOT EXISTS idx_day_sessions_type ON day_sessions(session_type);
+CREATE INDEX IF NOT EXISTS idx_day_sessions_status ON day_sessions(status);
+CREATE INDEX IF NOT EXISTS idx_day_sessions_started ON day_sessions(started_at);
+
+CREATE TABLE IF NOT EXISTS session_interruptions (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL REFERENCES day_sessions(id) ON DELETE CASCADE,
+    interruption_type TEXT NOT NULL CHECK (interruption_type IN ('external', 'mental', 'technical', 'break')),
+    duration_seconds INTEGER NOT NULL DEFAULT 0,
+    notes TEXT NOT NULL DEFAULT '',
+    occurred_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_interruptions_session ON session_interruptions(session_id);
+CREATE INDEX IF NOT EXISTS idx_interruptions_type ON session_interruptions(interruption_type);
+CREATE INDEX IF NOT EXISTS idx_interruptions_occurred ON session_interruptions(occurred_at);
+
+CREATE TABLE IF NOT EXISTS focus_metrics_daily (
+    id TEXT PRIMARY KEY NOT NULL,
+    date TEXT NOT NULL,
+    user_id TEXT NOT NULL DEFAULT 'default',
+    total_planned_minutes INTEGER NOT NULL DEFAULT 0,
+    total_actual_minutes INTEGER NOT NULL DEFAULT 0,
+    variance_percentage REAL NOT NULL DEFAULT 0.0,
+    completion_rate REAL NOT NULL DEFAULT 0.0,
+    focus_efficiency REAL NOT NULL DEFAULT 0.0,
+    deep_work_minutes INTEGER NOT NULL DEFAULT 0,
+    interruption_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE(date, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_focus_metrics_date ON focus_metrics_daily(date);
+CREATE INDEX IF NOT EXISTS idx_focus_metrics_user ON focus_metrics_daily(user_id);
+
+CREATE TABLE IF NOT EXISTS time_recommendations (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL DEFAULT 'default',
+    recommendation_type TEXT NOT NULL CHECK (recommendation_type IN ('increase_build', 'decrease_deep', 'add_buffer', 'adjust_break')),
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    data_source TEXT NOT NULL,
+    confidence_score REAL NOT NULL DEFAULT 0.0 CHECK (confidence_score >= 0.0 AND confidence_score <= 1.0),
+    is_applied INTEGER NOT NULL DEFAULT 0,
+    is_dismissed INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    applied_at TEXT,
+    dismissed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_recommendations_user ON time_recommendations(user_id);
+CREATE INDEX IF NOT EXISTS idx_time_recommendations_type ON time_recommendations(recommendation_type);
+CREATE INDEX IF NOT EXISTS idx_time_recommendations_active ON time_recommendations(is_applied, is_dismissed);
@@ -0,0 +1,28 @@
+-- Every recorded review, so schedulers can be compared and FSRS parameters fitted to the
+-- learner's own history. stability and difficulty are the FSRS memory state after the review.
+CREATE TABLE IF NOT EXISTS review_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    concept_tag_id TEXT NOT NULL REFERENCES concept_tags(id) ON DELETE CASCADE,
+    scheduler TEXT NOT NULL CHECK (scheduler IN ('sm2', 'fsrs')),
+    score INTEGER NOT NULL,
+    rating INTEGER NOT NULL CHECK (rating BETWEEN 1 AND 4),
+    elapsed_days INTEGER NOT NULL DEFAULT 0,
+    scheduled_days INTEGER NOT NULL,
+    stability REAL NOT NULL,
+    difficulty REAL NOT NULL,
+    reviewed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_log_item ON review_log(day_plan_id, concept_tag_id, reviewed_at);
+CREATE INDEX IF NOT EXISTS idx_review_log_reviewed_at ON review_log(reviewed_at);
+
+-- FSRS memory state is kept for every item whichever scheduler is selected, so switching
+-- schedulers doesn't start from scratch. NULL until the item's next review.
+ALTER TABLE spaced_repetition ADD COLUMN stability REAL;
+ALTER TABLE spaced_repetition ADD COLUMN difficulty REAL;
+
+INSERT OR IGNORE INTO settings (key, value) VALUES
+    ('review_scheduler', '"sm2"'),
+    ('fsrs_desired_retention', '0.9'),
+    ('review_maximum_interval_days', '365');
@@ -0,0 +1,2 @@
+-- Review sessions hand out at most this many reviews a day, the rest wait for later days
+INSERT OR IGNORE INTO settings (key, value) VALUES ('daily_review_cap', '30');
@@ -0,0 +1,42 @@
+-- Auto-graded quiz questions turned into individually scheduled review cards. Scheduling
+-- columns mirror spaced_repetition. A never-reviewed card has no last_review_date.
+CREATE TABLE IF NOT EXISTS quiz_cards (
+    id TEXT PRIMARY KEY NOT NULL,
+    quiz_question_id TEXT NOT NULL UNIQUE REFERENCES quiz_questions(id) ON DELETE CASCADE,
+    day_plan_id TEXT NOT NULL REFERENCES day_plans(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'suspended')),
+    easiness_factor REAL NOT NULL DEFAULT 2.5,
+    interval_days INTEGER NOT NULL DEFAULT 0,
+    repetition_count INTEGER NOT NULL DEFAULT 0,
+    stability REAL,
+    difficulty REAL,
+    last_review_date TEXT,
+    next_review_date TEXT NOT NULL,
+    last_score INTEGER,
+    review_count INTEGER NOT NULL DEFAULT 0,
+    lapse_count INTEGER NOT NULL DEFAULT 0,
+    is_leech BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_cards_next_review ON quiz_cards(next_review_date);
+CREATE INDEX IF NOT EXISTS idx_quiz_cards_plan ON quiz_cards(day_plan_id);
+
+CREATE TABLE IF NOT EXISTS quiz_card_reviews (
+    id TEXT PRIMARY KEY NOT NULL,
+    quiz_card_id TEXT NOT NULL REFERENCES quiz_cards(id) ON DELETE CASCADE,
+    scheduler TEXT NOT NULL CHECK (scheduler IN ('sm2', 'fsrs')),
+    answer TEXT NOT NULL,
+    is_correct BOOLEAN NOT NULL,
+    score INTEGER NOT NULL,
+    rating INTEGER NOT NULL CHECK (rating BETWEEN 1 AND 4),
+    time_taken_seconds INTEGER NOT NULL DEFAULT 0,
+    elapsed_days INTEGER NOT NULL DEFAULT 0,
+    scheduled_days INTEGER NOT NULL,
+    stability REAL NOT NULL,
+    difficulty REAL NOT NULL,
+    reviewed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_card_reviews_card ON quiz_card_reviews(quiz_card_id, reviewed_at);
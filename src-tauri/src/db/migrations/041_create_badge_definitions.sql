@@ -0,0 +1,43 @@
+-- Badges as data. Each definition names a metric the badge engine knows how to compute and
+-- the value that earns it. A NULL threshold means every day of the program.
+CREATE TABLE IF NOT EXISTS badge_definitions (
+    badge_type TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    icon TEXT NOT NULL DEFAULT 'ph:trophy-bold',
+    metric TEXT NOT NULL,
+    threshold INTEGER CHECK (threshold IS NULL OR threshold > 0),
+    metadata TEXT NOT NULL DEFAULT '{}',
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+INSERT OR IGNORE INTO badge_definitions (badge_type, title, description, icon, metric, threshold, metadata, sort_order) VALUES
+    ('first_step', 'First Step', 'Submitted your first day', 'ph:flag-bold', 'submitted_attempts', 1, '{"category":"milestone"}', 10),
+    ('streak_7', 'Week Warrior', 'Studied 7 days in a row', 'ph:fire-bold', 'study_day_streak', 7, '{"category":"streak"}', 20),
+    ('streak_14', 'Two-Week Titan', 'Studied 14 days in a row', 'ph:fire-bold', 'study_day_streak', 14, '{"category":"streak"}', 21),
+    ('streak_30', 'Monthly Machine', 'Studied 30 days in a row', 'ph:fire-bold', 'study_day_streak', 30, '{"category":"streak"}', 22),
+    ('perfect_week', 'Perfect Week', 'Scored 85 or more on 7 consecutive days of the program', 'ph:star-bold', 'consecutive_strong_days', 7, '{"category":"quality"}', 30),
+    ('zero_blockers_14', 'Clean Sheet', 'Submitted 14 attempts in a row without a blocker', 'ph:shield-check-bold', 'unblocked_attempt_run', 14, '{"category":"quality"}', 31),
+    ('mastery_day', 'Mastery Achieved', 'Reached mastery (95+) on a day', 'ph:crown-bold', 'mastered_days', 1, '{"category":"mastery"}', 40),
+    ('mastery_5', 'Mastery Streak', 'Reached mastery on 5 days', 'ph:crown-bold', 'mastered_days', 5, '{"category":"mastery"}', 41),
+    ('perfect_quiz', 'Flawless Recall', 'Scored 100% on a day quiz', 'ph:check-circle-bold', 'perfect_quizzes', 1, '{"category":"quiz"}', 50),
+    ('bug_hunter_100', 'Bug Hunter', 'Logged 100 bugs along with their fix', 'ph:bug-bold', 'fixed_bugs', 100, '{"category":"bugs"}', 60),
+    ('memory_master', 'Memory Master', 'Passed 10 memory rebuilds in a row', 'ph:brain-bold', 'memory_rebuild_run', 10, '{"category":"memory"}', 70),
+    ('full_evidence', 'Evidence King', 'Submitted 3 or more artifacts on 20 days', 'ph:files-bold', 'well_evidenced_days', 20, '{"category":"evidence"}', 80),
+    ('speed_demon', 'Speed Demon', 'Finished a day under its estimate with a score of 85 or more', 'ph:lightning-bold', 'fast_strong_days', 1, '{"category":"speed"}', 90),
+    ('comeback', 'Comeback', 'Scored 85 or more on a day that had blocked you', 'ph:arrow-u-up-right-bold', 'comeback_days', 1, '{"category":"resilience"}', 100),
+    ('program_complete', 'Program Complete', 'Passed every day of the program', 'ph:trophy-bold', 'passed_days', NULL, '{"category":"milestone"}', 110);
+
+-- Awarding is idempotent per program, so keep the earliest copy of any badge awarded twice
+DELETE FROM badges
+WHERE rowid NOT IN (
+    SELECT rowid FROM (
+        SELECT rowid, ROW_NUMBER() OVER (PARTITION BY program_id, badge_type ORDER BY earned_at, rowid) AS copy
+        FROM badges
+    )
+    WHERE copy = 1
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_badges_program_type ON badges(program_id, badge_type);
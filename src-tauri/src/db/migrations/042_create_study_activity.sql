@@ -0,0 +1,53 @@
+-- One row per program per day with study activity. Streaks are recomputed from this history
+-- and the heatmap reads it directly.
+CREATE TABLE IF NOT EXISTS study_activity (
+    program_id TEXT NOT NULL REFERENCES programs(id) ON DELETE CASCADE,
+    activity_date TEXT NOT NULL,
+    attempts_submitted INTEGER NOT NULL DEFAULT 0,
+    reviews_completed INTEGER NOT NULL DEFAULT 0,
+    minutes_studied INTEGER NOT NULL DEFAULT 0,
+    last_activity_at TEXT NOT NULL,
+    PRIMARY KEY (program_id, activity_date)
+);
+
+INSERT INTO study_activity (program_id, activity_date, attempts_submitted, minutes_studied, last_activity_at)
+SELECT dp.program_id, substr(da.submitted_at, 1, 10), COUNT(*), SUM(da.actual_minutes), MAX(da.submitted_at)
+FROM day_attempts da
+JOIN day_plans dp ON da.day_plan_id = dp.id
+WHERE da.is_draft = 0 AND da.submitted_at IS NOT NULL
+GROUP BY dp.program_id, substr(da.submitted_at, 1, 10);
+
+INSERT INTO study_activity (program_id, activity_date, reviews_completed, last_activity_at)
+SELECT dp.program_id, substr(rl.reviewed_at, 1, 10), COUNT(*), MAX(rl.reviewed_at)
+FROM review_log rl
+JOIN day_plans dp ON rl.day_plan_id = dp.id
+WHERE true
+GROUP BY dp.program_id, substr(rl.reviewed_at, 1, 10)
+ON CONFLICT(program_id, activity_date) DO UPDATE SET
+    reviews_completed = reviews_completed + excluded.reviews_completed,
+    last_activity_at = max(last_activity_at, excluded.last_activity_at);
+
+INSERT INTO study_activity (program_id, activity_date, reviews_completed, last_activity_at)
+SELECT dp.program_id, substr(qr.reviewed_at, 1, 10), COUNT(*), MAX(qr.reviewed_at)
+FROM quiz_card_reviews qr
+JOIN quiz_cards qc ON qr.quiz_card_id = qc.id
+JOIN day_plans dp ON qc.day_plan_id = dp.id
+WHERE true
+GROUP BY dp.program_id, substr(qr.reviewed_at, 1, 10)
+ON CONFLICT(program_id, activity_date) DO UPDATE SET
+    reviews_completed = reviews_completed + excluded.reviews_completed,
+    last_activity_at = max(last_activity_at, excluded.last_activity_at);
+
+-- A day is frozen at most once, keep the earliest freeze of any day frozen twice
+DELETE FROM streak_freezes
+WHERE rowid NOT IN (
+    SELECT rowid FROM (
+        SELECT rowid, ROW_NUMBER() OVER (PARTITION BY program_id, used_date ORDER BY created_at, rowid) AS copy
+        FROM streak_freezes
+    )
+    WHERE copy = 1
+);
+
+DROP INDEX IF EXISTS idx_streak_freezes_program;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_streak_freezes_program_date ON streak_freezes(program_id, used_date);
@@ -0,0 +1,4 @@
+-- The projected calendar is stored with the generated plan so the review screen and the
+-- eventual program start from the same dates. Both stay NULL until a plan is projected.
+ALTER TABLE import_jobs ADD COLUMN projected_calendar_json TEXT;
+ALTER TABLE import_jobs ADD COLUMN projected_end_date TEXT;
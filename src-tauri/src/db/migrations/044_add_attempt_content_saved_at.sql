@@ -0,0 +1,8 @@
+-- When an attempt's content was last saved explicitly. Crash recovery compares snapshots
+-- against this instead of updated_at, which unrelated writes such as scores and project
+-- links also bump. Both sides use the same RFC 3339 shape so they compare as text.
+ALTER TABLE day_attempts ADD COLUMN content_saved_at TEXT NOT NULL DEFAULT '';
+
+UPDATE day_attempts SET content_saved_at = strftime('%Y-%m-%dT%H:%M:%fZ', updated_at);
+
+UPDATE attempt_snapshots SET created_at = strftime('%Y-%m-%dT%H:%M:%fZ', created_at);
@@ -0,0 +1,8 @@
+-- The reference code is copied into the session when it starts, so later edits to the
+-- reference attempt can't change what a rebuild is compared against.
+ALTER TABLE memory_rebuild_sessions ADD COLUMN reference_code TEXT NOT NULL DEFAULT '';
+
+UPDATE memory_rebuild_sessions SET reference_code = COALESCE(
+    (SELECT code_snapshot FROM day_attempts WHERE day_attempts.id = memory_rebuild_sessions.reference_attempt_id),
+    ''
+);
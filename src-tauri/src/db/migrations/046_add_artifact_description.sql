@@ -0,0 +1,2 @@
+-- Artifact descriptions get their own column instead of sharing the content field.
+ALTER TABLE artifacts ADD COLUMN description TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,4 @@
+-- Mastery is the attempt's graded status rather than a fixed score, so the description no
+-- longer names one.
+UPDATE badge_definitions SET description = 'Reached mastery on a day'
+WHERE badge_type = 'mastery_day' AND description = 'Reached mastery (95+) on a day';
@@ -1,6 +1,8 @@
-use sqlx::SqlitePool;
+use sqlx::{Connection, SqlitePool};
+use std::collections::HashSet;
 
 pub mod models;
+pub mod schema_check;
 
 pub async fn run_migrations(pool: &SqlitePool) {
     let migrations_dir = std::path::Path::new("src/db/migrations");
@@ -16,37 +18,112 @@ pub async fn run_migrations(pool: &SqlitePool) {
         .filter(|e| {
             e.path()
                 .extension()
-                .map_or(false, |ext| ext == "sql")
+                .is_some_and(|ext| ext == "sql")
         })
         .collect();
 
     entries.sort_by_key(|e| e.file_name());
 
+    // Everything runs on one connection: PRAGMA foreign_keys is per-connection, and table
+    // rebuilds need it off while the old table is dropped.
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire connection for migrations");
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version TEXT PRIMARY KEY NOT NULL,
+            applied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+        )",
+    )
+    .execute(&mut *conn)
+    .await
+    .expect("Failed to create schema_migrations table");
+
+    let applied: HashSet<String> = sqlx::query_scalar("SELECT version FROM schema_migrations")
+        .fetch_all(&mut *conn)
+        .await
+        .expect("Failed to read applied migrations")
+        .into_iter()
+        .collect();
+
+    // Databases from before migrations were tracked already hold the objects the early files
+    // create, so the first tracked run adopts them instead of failing on them
+    let adopting = applied.is_empty()
+        && sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'programs'",
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .expect("Failed to inspect existing schema")
+            > 0;
+
+    sqlx::query("PRAGMA foreign_keys=OFF")
+        .execute(&mut *conn)
+        .await
+        .expect("Failed to disable foreign keys for migrations");
+
     for entry in entries {
+        let version = entry.file_name().to_string_lossy().into_owned();
+        if applied.contains(&version) {
+            continue;
+        }
+
         let sql = std::fs::read_to_string(entry.path())
             .unwrap_or_else(|_| panic!("Failed to read migration: {:?}", entry.path()));
 
         tracing::info!("Running migration: {:?}", entry.file_name());
 
+        let mut tx = conn
+            .begin()
+            .await
+            .expect("Failed to start migration transaction");
+
         // Split by semicolons and execute each statement
         // (SQLx execute doesn't support multi-statement by default for some drivers)
         for statement in sql.split(';') {
             let trimmed = statement.trim();
-            if !trimmed.is_empty() {
-                sqlx::query(trimmed)
-                    .execute(pool)
-                    .await
-                    .unwrap_or_else(|e| {
-                        panic!(
-                            "Migration failed: {:?} — Error: {} — Statement: {}",
-                            entry.file_name(),
-                            e,
-                            &trimmed[..trimmed.len().min(100)]
-                        );
-                    });
+            if trimmed.is_empty() {
+                continue;
+            }
+            match sqlx::query(trimmed).execute(&mut *tx).await {
+                Ok(_) => {}
+                Err(e) if adopting && already_applied(&e) => {
+                    tracing::info!(
+                        "Keeping existing schema object from {:?}: {}",
+                        entry.file_name(),
+                        e
+                    );
+                }
+                Err(e) => panic!(
+                    "Migration failed: {:?} — Error: {} — Statement: {}",
+                    entry.file_name(),
+                    e,
+                    &trimmed[..trimmed.len().min(100)]
+                ),
             }
         }
+
+        sqlx::query("INSERT INTO schema_migrations (version) VALUES (?)")
+            .bind(&version)
+            .execute(&mut *tx)
+            .await
+            .expect("Failed to record migration");
+
+        tx.commit().await.expect("Failed to commit migration");
     }
 
+    sqlx::query("PRAGMA foreign_keys=ON")
+        .execute(&mut *conn)
+        .await
+        .expect("Failed to re-enable foreign keys after migrations");
+
     tracing::info!("All migrations completed successfully");
 }
+
+/// Whether `error` says the statement's table, index or column is already there.
+fn already_applied(error: &sqlx::Error) -> bool {
+    let message = error.to_string();
+    message.contains("already exists") || message.contains("duplicate column name")
+}
@@ -7,11 +7,17 @@ pub struct Artifact {
     pub day_attempt_id: String,
     pub artifact_type: String,
     pub title: String,
+    pub description: String,
     pub content: String,
     pub file_path: Option<String>,
-    pub file_size: Option<i32>,
+    pub file_size: Option<i64>,
     pub mime_type: Option<String>,
     pub url: Option<String>,
+    /// Content hash of an imported file; `None` for links, notes and unmanaged paths
+    pub sha256: Option<String>,
+    /// Blob location relative to the artifact store root
+    pub storage_path: Option<String>,
+    pub thumbnail_path: Option<String>,
     pub created_at: String,
 }
 
@@ -20,9 +26,11 @@ pub struct CreateArtifactInput {
     pub day_attempt_id: String,
     pub artifact_type: String,
     pub title: String,
+    #[serde(default)]
+    pub description: String,
     pub content: String,
     pub file_path: Option<String>,
-    pub file_size: Option<i32>,
+    pub file_size: Option<i64>,
     pub mime_type: Option<String>,
     pub url: Option<String>,
 }
@@ -30,5 +38,52 @@ pub struct CreateArtifactInput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateArtifactInput {
     pub title: Option<String>,
+    pub description: Option<String>,
     pub content: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportArtifactFileInput {
+    pub day_attempt_id: String,
+    pub source_path: String,
+    /// Defaults to "screenshot" for images and "file" otherwise
+    pub artifact_type: Option<String>,
+    /// Defaults to the file name
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedArtifact {
+    pub artifact: Artifact,
+    /// The store already held an identical file, so nothing new was written
+    pub deduplicated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactBlobIssue {
+    pub artifact_id: String,
+    pub storage_path: String,
+    pub expected_sha256: String,
+    /// "missing" or "corrupted"
+    pub problem: String,
+    /// Hash of the file found on disk, for corrupted blobs
+    pub actual_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactIntegrityReport {
+    pub checked: i64,
+    pub healthy: i64,
+    pub issues: Vec<ArtifactBlobIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactGcReport {
+    pub removed_blobs: i64,
+    pub removed_thumbnails: i64,
+    pub reclaimed_bytes: i64,
+}
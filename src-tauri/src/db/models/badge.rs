@@ -23,3 +23,19 @@ pub struct BadgeProgress {
     pub progress_percent: f64,
     pub is_earned: bool,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BadgeDefinition {
+    pub badge_type: String,
+    pub title: String,
+    pub description: String,
+    pub icon: String,
+    /// Key of the metric the badge engine computes for this badge
+    pub metric: String,
+    /// `None` means every day of the program
+    pub threshold: Option<i32>,
+    pub metadata: String,
+    pub sort_order: i32,
+    pub is_active: bool,
+    pub created_at: String,
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BugCluster {
+    pub category: String,
+    /// Root cause (or symptom, when no root cause was logged) of the cluster's first bug,
+    /// which later bugs are matched against
+    pub representative: String,
+    /// Oldest first
+    pub bug_ids: Vec<String>,
+    pub occurrences: i32,
+    pub attempt_count: i32,
+    pub program_ids: Vec<String>,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub occurrences_this_month: i32,
+    /// Over the bugs with a logged fix time; `None` when none have one
+    pub mean_time_to_fix_minutes: Option<f64>,
+    pub highest_severity: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatOffender {
+    pub category: String,
+    pub representative: String,
+    pub occurrences_this_month: i32,
+    pub lifetime_occurrences: i32,
+    /// e.g. "Third lifetime bug this month"
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyTimeToFix {
+    /// "YYYY-MM"
+    pub month: String,
+    pub bug_count: i32,
+    pub mean_minutes: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeToFixTrend {
+    pub category: String,
+    pub months: Vec<MonthlyTimeToFix>,
+    /// Mean fix time of the older and newer half of the category's bugs
+    pub earlier_mean_minutes: Option<f64>,
+    pub recent_mean_minutes: Option<f64>,
+    pub change_percent: Option<f64>,
+    /// "improving", "worsening", "steady" or "insufficient_data"
+    pub direction: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreventionOutcome {
+    pub category: String,
+    pub representative: String,
+    /// The bug that first logged a prevention strategy for its cluster
+    pub bug_id: String,
+    pub prevention_strategy: String,
+    pub logged_at: String,
+    /// Occurrences up to and including the bug that logged the strategy
+    pub occurrences_before: i32,
+    pub occurrences_after: i32,
+    pub rate_before_per_30_days: f64,
+    pub rate_after_per_30_days: f64,
+    pub days_observed: i64,
+    /// "reduced", "not_reduced" or "too_early"
+    pub verdict: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BugAnalytics {
+    /// `None` when the analysis covers every program
+    pub program_id: Option<String>,
+    pub total_bugs: i32,
+    /// Most occurrences first
+    pub clusters: Vec<BugCluster>,
+    pub repeat_offenders: Vec<RepeatOffender>,
+    pub time_to_fix_trends: Vec<TimeToFixTrend>,
+    pub prevention_outcomes: Vec<PreventionOutcome>,
+}
@@ -38,3 +38,30 @@ pub struct UpdateBugLogInput {
     pub prevention_strategy: Option<String>,
     pub time_to_fix_minutes: Option<i32>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindSimilarBugsInput {
+    /// The attempt the new bug is being logged on; its day's concept tags boost matches
+    pub day_attempt_id: Option<String>,
+    pub symptom: String,
+    #[serde(default)]
+    pub root_cause: String,
+    /// Left out of the results, when an existing bug is being edited
+    pub exclude_bug_id: Option<String>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarBug {
+    /// Includes the earlier fix and prevention strategy
+    pub bug: BugLog,
+    pub program_id: String,
+    pub day_plan_id: String,
+    pub day_title: String,
+    pub day_number: i32,
+    /// BM25 relevance of symptom and root cause
+    pub text_score: f64,
+    pub shared_concept_tags: Vec<String>,
+    /// `text_score` with the concept tag boost applied; results are ordered by it
+    pub score: f64,
+}
@@ -1,3 +1,4 @@
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -15,6 +16,14 @@ pub struct UserCapacityProfile {
     pub updated_at: String,
 }
 
+impl UserCapacityProfile {
+    /// Study days are the first `weekly_study_days` days of the week, counting from Monday.
+    pub fn is_study_day(&self, date: NaiveDate) -> bool {
+        let study_days = self.weekly_study_days.clamp(1, 7).unsigned_abs();
+        date.weekday().num_days_from_monday() < study_days
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateCapacityInput {
     pub default_daily_minutes: Option<i32>,
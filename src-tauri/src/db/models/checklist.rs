@@ -31,4 +31,37 @@ pub struct AttemptChecklist {
     pub checklist_item_id: String,
     pub is_completed: bool,
     pub completed_at: Option<String>,
+    pub artifact_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A day plan checklist item together with its progress on one attempt.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttemptChecklistItem {
+    pub checklist_item_id: String,
+    pub label: String,
+    pub is_required: bool,
+    pub order_index: i32,
+    pub is_completed: bool,
+    pub completed_at: Option<String>,
+    pub artifact_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToggleChecklistItemInput {
+    pub day_attempt_id: String,
+    pub checklist_item_id: String,
+    pub is_completed: bool,
+    pub artifact_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionCheck {
+    /// The `checklist_enforcement` setting in effect: "warn" or "block"
+    pub enforcement: String,
+    pub can_submit: bool,
+    pub unchecked_required: Vec<AttemptChecklistItem>,
+    pub completed_count: usize,
+    pub total_count: usize,
 }
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use super::BugLog;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffLine {
+    /// "same", "added" or "removed"
+    pub kind: String,
+    pub text: String,
+    /// 1-based line number in the base text, for "same" and "removed" lines
+    pub base_line: Option<usize>,
+    /// 1-based line number in the target text, for "same" and "added" lines
+    pub target_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparedAttempt {
+    pub id: String,
+    pub attempt_number: i32,
+    pub status: String,
+    pub total_score: i32,
+    pub submitted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionDelta {
+    pub dimension_key: String,
+    pub label: String,
+    pub base_score: Option<i32>,
+    pub base_max_points: Option<i32>,
+    pub target_score: Option<i32>,
+    pub target_max_points: Option<i32>,
+    /// Change in percent of the dimension's max points, so rubric edits between attempts
+    /// still compare; `None` unless both attempts were scored on it
+    pub delta_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeDelta {
+    pub base_minutes: i32,
+    pub target_minutes: i32,
+    pub delta_minutes: i32,
+    /// Wall-clock minutes from start to submission
+    pub base_elapsed_minutes: Option<i64>,
+    pub target_elapsed_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExerciseDiff {
+    pub position: usize,
+    pub base_entry_id: Option<String>,
+    pub target_entry_id: Option<String>,
+    pub base_language: Option<String>,
+    pub target_language: Option<String>,
+    pub code_diff: Vec<DiffLine>,
+    pub notes_diff: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextFieldDiff {
+    pub field: String,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttemptComparison {
+    pub day_plan_id: String,
+    pub base: ComparedAttempt,
+    pub target: ComparedAttempt,
+    pub total_score_delta: i32,
+    pub dimensions: Vec<DimensionDelta>,
+    pub time: TimeDelta,
+    pub code_diff: Vec<DiffLine>,
+    pub exercise_diffs: Vec<ExerciseDiff>,
+    /// Bugs logged on the target with no similar bug on the base
+    pub bugs_added: Vec<BugLog>,
+    /// Bugs logged on the base that didn't come back on the target
+    pub bugs_removed: Vec<BugLog>,
+    /// Only the reflection fields that changed
+    pub reflection_diffs: Vec<TextFieldDiff>,
+}
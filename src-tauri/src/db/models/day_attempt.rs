@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use super::DimensionScoreInput;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct DayAttempt {
     pub id: String,
@@ -8,6 +10,8 @@ pub struct DayAttempt {
     pub attempt_number: i32,
     pub status: String,
     pub is_draft: bool,
+    pub rubric_id: Option<String>,
+    pub prerequisite_override_reason: Option<String>,
     pub score_implementation: i32,
     pub score_code_quality: i32,
     pub score_accessibility: i32,
@@ -20,10 +24,13 @@ pub struct DayAttempt {
     pub key_learnings: String,
     pub memory_rebuild_passed: bool,
     pub memory_rebuild_notes: String,
+    pub code_snapshot: String,
     pub actual_minutes: i32,
+    pub project_path: Option<String>,
     pub started_at: String,
     pub submitted_at: Option<String>,
     pub last_autosave: String,
+    pub content_saved_at: String,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -31,6 +38,9 @@ pub struct DayAttempt {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateDayAttemptInput {
     pub day_plan_id: String,
+    /// Required to start while prerequisite dependencies are still unmet
+    #[serde(default)]
+    pub prerequisite_override_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,15 +51,29 @@ pub struct UpdateDayAttemptInput {
     pub key_learnings: Option<String>,
     pub memory_rebuild_passed: Option<bool>,
     pub memory_rebuild_notes: Option<String>,
+    #[serde(default)]
+    pub code_snapshot: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubmitScoresInput {
+    /// Scores keyed by rubric dimension. When empty, the legacy fixed fields below are
+    /// mapped onto the matching dimensions instead.
+    #[serde(default)]
+    pub scores: Vec<DimensionScoreInput>,
+    #[serde(default)]
     pub score_implementation: i32,
+    #[serde(default)]
     pub score_code_quality: i32,
+    #[serde(default)]
     pub score_accessibility: i32,
+    #[serde(default)]
     pub score_performance: i32,
+    #[serde(default)]
     pub score_quiz: i32,
+    /// Take the pending analyzer suggestions in place of the submitted scores for their dimensions
+    #[serde(default)]
+    pub accept_suggestions: bool,
     pub daily_summary: String,
     pub what_went_well: String,
     pub what_to_improve: String,
@@ -8,6 +8,7 @@ pub struct ExerciseEntry {
     pub language: String,
     pub code: String,
     pub notes: String,
+    pub expected_output: Option<String>,
     pub order_index: i32,
     pub created_at: String,
     pub updated_at: String,
@@ -19,6 +20,8 @@ pub struct CreateExerciseEntryInput {
     pub language: String,
     pub code: String,
     pub notes: String,
+    #[serde(default)]
+    pub expected_output: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,4 +29,27 @@ pub struct UpdateExerciseEntryInput {
     pub language: Option<String>,
     pub code: Option<String>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub expected_output: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExerciseRun {
+    pub id: String,
+    pub exercise_entry_id: String,
+    pub day_attempt_id: String,
+    pub language: String,
+    /// The code as it was when run, since the entry keeps changing
+    pub code: String,
+    /// "compile" when the run stopped at a failed build, otherwise "run"
+    pub stage: String,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: i64,
+    pub expected_output: Option<String>,
+    /// `None` when the entry has no expected output to compare against
+    pub passed: Option<bool>,
+    pub created_at: String,
 }
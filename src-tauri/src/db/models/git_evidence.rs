@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GitEvidence {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub repository_path: String,
+    pub branch: String,
+    /// Last commit inside the window; the tree snapshot is taken from it
+    pub head_commit: Option<String>,
+    pub window_start: String,
+    pub window_end: String,
+    pub commit_count: i32,
+    /// Distinct files touched across all commits in the window
+    pub files_changed: i32,
+    pub insertions: i32,
+    pub deletions: i32,
+    /// `Vec<GitCommit>` as JSON, oldest first
+    pub commits_json: String,
+    /// `Vec<GitTreeEntry>` as JSON
+    pub tree_json: String,
+    pub estimated_minutes: i32,
+    pub captured_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub author: String,
+    pub committed_at: String,
+    pub message: String,
+    pub files: Vec<GitFileChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitFileChange {
+    pub path: String,
+    /// `None` for binary files
+    pub insertions: Option<i32>,
+    pub deletions: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitTreeEntry {
+    pub path: String,
+    pub object_hash: String,
+    pub size_bytes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedGitEvidence {
+    pub evidence: GitEvidence,
+    /// Which attempt fields were filled from the evidence ("code_snapshot", "actual_minutes")
+    pub applied_fields: Vec<String>,
+}
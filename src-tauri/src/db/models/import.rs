@@ -13,6 +13,8 @@ pub struct ImportJob {
     pub ai_analysis_json: String,
     pub generated_plan_json: String,
     pub reviewed_plan_json: Option<String>,
+    pub projected_calendar_json: Option<String>,
+    pub projected_end_date: Option<String>,
     pub total_pages: i64,
     pub total_tokens: i64,
     pub total_days_generated: i64,
@@ -105,6 +107,8 @@ pub struct GeneratedPlan {
     pub tag_assignments: Vec<(usize, String)>,
     pub dependencies: Vec<DependencyDraft>,
     pub validation_warnings: Vec<String>,
+    #[serde(default)]
+    pub inferred_dependencies: Vec<InferredDependency>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -174,6 +178,17 @@ pub struct DependencyDraft {
     pub minimum_score: i64,
 }
 
+/// A prerequisite edge derived from concept tag flow rather than emitted by the model.
+/// Only accepted edges are copied into `dependencies`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredDependency {
+    pub day_index: usize,
+    pub depends_on_day_number: i64,
+    pub minimum_score: i64,
+    pub tag_names: Vec<String>,
+    pub status: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ImportJobSummary {
     pub id: String,
@@ -248,3 +263,186 @@ pub struct AiDependency {
     pub dependency_type: String,
     pub minimum_score: i64,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReimportMergePlan {
+    pub job_id: String,
+    pub program_id: String,
+    pub modules: Vec<ModuleMatch>,
+    pub added: Vec<AddedDay>,
+    pub changed: Vec<DayMatch>,
+    pub moved: Vec<DayMatch>,
+    pub unchanged: Vec<DayMatch>,
+    pub removed: Vec<RemovedDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleMatch {
+    pub module_index: usize,
+    pub title: String,
+    pub existing_module_id: Option<String>,
+    pub existing_title: Option<String>,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayMatch {
+    pub day_index: usize,
+    pub existing_day_plan_id: String,
+    pub existing_title: String,
+    pub new_title: String,
+    pub old_day_number: i64,
+    pub new_day_number: i64,
+    pub old_module_id: String,
+    pub new_module_index: usize,
+    pub similarity: f64,
+    pub changed_fields: Vec<String>,
+    pub attempt_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddedDay {
+    pub day_index: usize,
+    pub title: String,
+    pub day_number: i64,
+    pub module_index: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedDay {
+    pub day_plan_id: String,
+    pub title: String,
+    pub day_number: i64,
+    pub attempt_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PlanEditOperation {
+    RenameModule {
+        module_index: usize,
+        title: String,
+    },
+    MoveDay {
+        day_index: usize,
+        module_index: usize,
+    },
+    MergeDays {
+        day_index: usize,
+        into_day_index: usize,
+    },
+    SplitDay {
+        day_index: usize,
+        second_title: String,
+    },
+    DeleteQuizQuestion {
+        question_index: usize,
+    },
+    Retag {
+        day_index: usize,
+        tags: Vec<ConceptTagDraft>,
+    },
+    FitToCapacity {
+        daily_minutes: i64,
+    },
+    InferDependencies,
+    AcceptInferredDependency {
+        index: usize,
+    },
+    RejectInferredDependency {
+        index: usize,
+    },
+    /// The whole plan as edited in the review form
+    ReplacePlan {
+        plan: Box<GeneratedPlan>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImportPlanEdit {
+    pub id: String,
+    pub job_id: String,
+    pub sequence: i64,
+    pub operation_json: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportQualityReport {
+    pub job_id: String,
+    pub score: i64,
+    pub total_sections: usize,
+    pub covered_sections: Vec<SectionCoverage>,
+    pub orphaned_sections: Vec<SectionCoverage>,
+    pub days_without_quiz: Vec<DayIssue>,
+    pub days_without_checklist: Vec<DayIssue>,
+    pub estimated_total_days: i64,
+    pub generated_days: usize,
+    pub total_estimated_minutes: i64,
+    pub estimate_clash: Option<String>,
+    pub duplicate_titles: Vec<DuplicateTitle>,
+    pub complexity_distribution: Vec<ComplexityBucket>,
+    pub validation_warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionCoverage {
+    pub heading: String,
+    pub page_number: usize,
+    pub covered_by_days: Vec<i64>,
+    pub best_match: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayIssue {
+    pub day_index: usize,
+    pub day_number: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateTitle {
+    pub title: String,
+    pub day_numbers: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityBucket {
+    pub level: i64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityPlan {
+    pub plan: GeneratedPlan,
+    pub calendar: ProjectedCalendar,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectedCalendar {
+    pub start_date: String,
+    pub end_date: String,
+    pub study_days: usize,
+    pub idle_study_days: usize,
+    pub entries: Vec<CalendarEntry>,
+    /// Deep days that can't go anywhere because the profile allows none per week
+    #[serde(default)]
+    pub unplaceable: Vec<UnplaceableDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarEntry {
+    pub day_index: usize,
+    pub day_number: i64,
+    pub title: String,
+    pub date: String,
+    pub estimated_minutes: i64,
+    pub is_deep: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnplaceableDay {
+    pub day_index: usize,
+    pub day_number: i64,
+    pub title: String,
+}
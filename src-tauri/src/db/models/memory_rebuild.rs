@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::DiffLine;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MemoryRebuildSession {
+    pub id: String,
+    pub day_attempt_id: String,
+    /// The best submitted attempt with a code snapshot when the session started
+    pub reference_attempt_id: Option<String>,
+    /// That attempt's code as of the start; never sent, the result's diff shows it once
+    /// the rebuild is submitted
+    #[serde(skip_serializing)]
+    pub reference_code: String,
+    /// "in_progress", "submitted" or "expired" (submitted past the deadline)
+    pub status: String,
+    /// 0 means untimed
+    pub time_limit_minutes: i32,
+    pub started_at: String,
+    pub deadline_at: Option<String>,
+    pub submitted_at: Option<String>,
+    pub elapsed_seconds: Option<i64>,
+    pub rebuilt_code: String,
+    /// Token-sequence similarity to the reference, `0.0..=1.0`
+    pub token_similarity: Option<f64>,
+    /// Share of the day's syntax targets used in the rebuild; `None` when it has none
+    pub target_coverage: Option<f64>,
+    /// `Vec<SyntaxTargetCoverage>` as JSON
+    pub targets_json: String,
+    /// `Vec<String>` as JSON: reference identifiers the rebuild never uses
+    pub missing_identifiers_json: String,
+    pub suggested_pass: Option<bool>,
+    /// One sentence per check, explaining the suggestion
+    pub evidence: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntaxTargetCoverage {
+    pub target: String,
+    pub covered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRebuildResult {
+    pub session: MemoryRebuildSession,
+    pub targets: Vec<SyntaxTargetCoverage>,
+    pub missing_identifiers: Vec<String>,
+    /// Reference against rebuild, only once the session is over
+    pub diff: Vec<DiffLine>,
+}
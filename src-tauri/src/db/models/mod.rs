@@ -10,6 +10,9 @@ pub mod exercise;
 pub mod artifact;
 pub mod bug_log;
 pub mod spaced_repetition;
+pub mod review_log;
+pub mod review_queue;
+pub mod quiz_card;
 pub mod streak;
 pub mod badge;
 pub mod skill_score;
@@ -17,6 +20,13 @@ pub mod capacity;
 pub mod session;
 pub mod recommendation;
 pub mod import;
+pub mod rubric;
+pub mod snapshot;
+pub mod comparison;
+pub mod score_suggestion;
+pub mod git_evidence;
+pub mod memory_rebuild;
+pub mod bug_analytics;
 
 pub use program::*;
 pub use module::*;
@@ -30,6 +40,9 @@ pub use exercise::*;
 pub use artifact::*;
 pub use bug_log::*;
 pub use spaced_repetition::*;
+pub use review_log::*;
+pub use review_queue::*;
+pub use quiz_card::*;
 pub use streak::*;
 pub use badge::*;
 pub use skill_score::*;
@@ -37,3 +50,10 @@ pub use capacity::*;
 pub use session::*;
 pub use recommendation::*;
 pub use import::*;
+pub use rubric::*;
+pub use snapshot::*;
+pub use comparison::*;
+pub use score_suggestion::*;
+pub use git_evidence::*;
+pub use memory_rebuild::*;
+pub use bug_analytics::*;
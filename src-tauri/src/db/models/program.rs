@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use super::DimensionAverage;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Program {
     pub id: String,
@@ -34,6 +36,7 @@ pub struct ProgramStats {
     pub average_score: f64,
     pub current_streak: i32,
     pub total_time_minutes: i32,
+    pub dimension_averages: Vec<DimensionAverage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
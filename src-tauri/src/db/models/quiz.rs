@@ -9,6 +9,7 @@ pub struct QuizQuestion {
     pub question_type: String,
     pub correct_answer: String,
     pub options: String,
+    pub answer_tolerance: f64,
     pub points: i32,
     pub time_limit_seconds: i32,
     pub order_index: i32,
@@ -22,6 +23,7 @@ pub struct CreateQuizQuestionInput {
     pub question_type: String,
     pub correct_answer: String,
     pub options: Option<String>,
+    pub answer_tolerance: Option<f64>,
     pub points: i32,
     pub time_limit_seconds: i32,
 }
@@ -32,6 +34,7 @@ pub struct UpdateQuizQuestionInput {
     pub question_type: Option<String>,
     pub correct_answer: Option<String>,
     pub options: Option<String>,
+    pub answer_tolerance: Option<f64>,
     pub points: Option<i32>,
     pub time_limit_seconds: Option<i32>,
 }
@@ -39,10 +42,62 @@ pub struct UpdateQuizQuestionInput {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct QuizAttempt {
     pub id: String,
+    pub quiz_session_id: String,
     pub day_attempt_id: String,
     pub quiz_question_id: String,
     pub answer: String,
-    pub is_correct: bool,
+    /// `None` for question types that aren't auto-graded (code prompts, reflections)
+    pub is_correct: Option<bool>,
+    pub points_earned: f64,
+    pub points_possible: i32,
     pub time_taken_seconds: i32,
+    pub timed_out: bool,
     pub submitted_at: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizSession {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub status: String,
+    pub started_at: String,
+    pub deadline_at: Option<String>,
+    pub submitted_at: Option<String>,
+    pub points_earned: f64,
+    pub points_possible: i32,
+    pub score_percent: i32,
+    pub created_at: String,
+}
+
+/// A question as shown while the quiz is running, without its answer.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizPrompt {
+    pub id: String,
+    pub question_text: String,
+    pub question_type: String,
+    pub options: String,
+    pub points: i32,
+    pub time_limit_seconds: i32,
+    pub order_index: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartedQuiz {
+    pub session: QuizSession,
+    pub questions: Vec<QuizPrompt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizAnswerInput {
+    pub quiz_question_id: String,
+    pub answer: String,
+    pub time_taken_seconds: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizResult {
+    pub session: QuizSession,
+    pub answers: Vec<QuizAttempt>,
+    /// The quiz dimension score written to the attempt, on the rubric's scale
+    pub score_quiz: i32,
+}
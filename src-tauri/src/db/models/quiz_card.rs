@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::QuizPrompt;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizCard {
+    pub id: String,
+    pub quiz_question_id: String,
+    pub day_plan_id: String,
+    /// "active" or "suspended"
+    pub status: String,
+    pub easiness_factor: f64,
+    pub interval_days: i32,
+    pub repetition_count: i32,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+    /// `None` until the card's first review
+    pub last_review_date: Option<String>,
+    pub next_review_date: String,
+    pub last_score: Option<i32>,
+    pub review_count: i32,
+    /// Reviews answered wrong after the card had been learned
+    pub lapse_count: i32,
+    pub is_leech: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuizCardReview {
+    pub id: String,
+    pub quiz_card_id: String,
+    pub scheduler: String,
+    pub answer: String,
+    pub is_correct: bool,
+    pub score: i32,
+    pub rating: i32,
+    pub time_taken_seconds: i32,
+    pub elapsed_days: i32,
+    pub scheduled_days: i32,
+    pub stability: f64,
+    pub difficulty: f64,
+    pub reviewed_at: String,
+}
+
+/// A due card as presented for review, without the answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizCardPrompt {
+    pub card: QuizCard,
+    pub question: QuizPrompt,
+    pub day_title: String,
+    pub day_number: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerQuizCardInput {
+    pub quiz_card_id: String,
+    pub answer: String,
+    pub time_taken_seconds: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeechSuggestion {
+    /// "rewrite_question" when the question itself seems to be the problem, "replay_day" when
+    /// the day's other cards are failing too
+    pub action: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizCardReviewResult {
+    pub card: QuizCard,
+    pub review: QuizCardReview,
+    pub correct_answer: String,
+    /// Set when the card is a leech
+    pub leech: Option<LeechSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeechCard {
+    pub card: QuizCard,
+    pub question_text: String,
+    pub day_title: String,
+    pub day_number: i32,
+    pub suggestion: LeechSuggestion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizCardSyncResult {
+    pub created: i32,
+    /// Questions that need a human to mark them and so can't become cards
+    pub skipped_ungraded: i32,
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReviewLog {
+    pub id: String,
+    pub day_plan_id: String,
+    pub concept_tag_id: String,
+    /// "sm2" or "fsrs": the scheduler that picked `scheduled_days`
+    pub scheduler: String,
+    pub score: i32,
+    /// 1 = again, 2 = hard, 3 = good, 4 = easy
+    pub rating: i32,
+    /// Days since the previous review, 0 for the first one
+    pub elapsed_days: i32,
+    pub scheduled_days: i32,
+    /// FSRS memory state after the review
+    pub stability: f64,
+    pub difficulty: f64,
+    pub reviewed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewSchedulerSettings {
+    /// "sm2" or "fsrs"
+    pub scheduler: String,
+    /// Recall probability FSRS schedules for, `0.7..=0.97`
+    pub desired_retention: f64,
+    pub maximum_interval_days: i32,
+    /// Most reviews a day's review sessions hand out
+    pub daily_review_cap: i32,
+    pub fsrs_parameters: Vec<f64>,
+    /// Whether `fsrs_parameters` were fitted to the review log rather than the defaults
+    pub parameters_fitted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReviewSchedulerInput {
+    pub scheduler: Option<String>,
+    pub desired_retention: Option<f64>,
+    pub maximum_interval_days: Option<i32>,
+    pub daily_review_cap: Option<i32>,
+    /// Drops fitted FSRS parameters and goes back to the defaults
+    #[serde(default)]
+    pub reset_fsrs_parameters: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsrsOptimizationReport {
+    pub item_count: i32,
+    /// Reviews with a recall prediction to check, i.e. every review after an item's first
+    pub review_count: i32,
+    /// Mean log loss of recall predictions with the current and the fitted parameters
+    pub log_loss_before: f64,
+    pub log_loss_after: f64,
+    pub parameters: Vec<f64>,
+    /// Whether the fitted parameters were saved; only done when asked and they predict better
+    pub applied: bool,
+}
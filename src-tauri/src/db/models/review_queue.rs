@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedReview {
+    pub id: String,
+    pub day_plan_id: String,
+    pub day_title: String,
+    pub day_number: i32,
+    pub concept_tag_id: String,
+    pub concept_name: String,
+    pub domain: String,
+    pub next_review_date: String,
+    pub interval_days: i32,
+    /// Days past the due date, 0 when due today
+    pub overdue_days: i32,
+    /// Recall probability today, from the item's FSRS memory state
+    pub predicted_recall: f64,
+    /// Higher goes first; see `review_queue::priority`
+    pub priority: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewSession {
+    pub program_id: String,
+    pub date: String,
+    pub daily_review_cap: i32,
+    pub reviewed_today: i32,
+    /// Everything due today or earlier, including what doesn't fit under the cap
+    pub due_count: i32,
+    /// Due reviews left for later days because of the cap
+    pub deferred_count: i32,
+    /// Highest priority first, with concept domains interleaved
+    pub reviews: Vec<QueuedReview>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewForecastDay {
+    /// "YYYY-MM-DD"
+    pub date: String,
+    /// Reviews falling due that day; the first day also counts everything overdue
+    pub due_count: i32,
+    /// Reviews done that day if the daily cap is worked through every day
+    pub projected_reviews: i32,
+    /// Due reviews still waiting at the end of the day
+    pub projected_backlog: i32,
+}
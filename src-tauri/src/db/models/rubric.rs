@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ScoringRubric {
+    pub id: String,
+    pub program_id: Option<String>,
+    pub name: String,
+    pub pass_threshold: i32,
+    pub mastery_threshold: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RubricDimension {
+    pub id: String,
+    pub rubric_id: String,
+    pub key: String,
+    pub label: String,
+    pub max_points: i32,
+    pub weight: f64,
+    pub order_index: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricWithDimensions {
+    pub rubric: ScoringRubric,
+    pub dimensions: Vec<RubricDimension>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveRubricInput {
+    pub program_id: String,
+    pub name: String,
+    pub pass_threshold: i32,
+    pub mastery_threshold: i32,
+    pub dimensions: Vec<RubricDimensionInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricDimensionInput {
+    pub key: String,
+    pub label: String,
+    pub max_points: i32,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionScoreInput {
+    pub dimension_key: String,
+    pub score: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttemptDimensionScore {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub dimension_key: String,
+    pub label: String,
+    pub score: i32,
+    pub max_points: i32,
+    pub weight: f64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DimensionAverage {
+    pub dimension_key: String,
+    pub label: String,
+    pub average_percent: f64,
+    pub attempt_count: i32,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttemptScoreSuggestion {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub dimension_key: String,
+    pub suggested_score: i32,
+    pub max_points: i32,
+    /// One sentence per factor that moved the score
+    pub explanation: String,
+    /// `Vec<AnalyzerFinding>` as JSON, only the findings counted for this dimension
+    pub findings_json: String,
+    /// `AnalysisMetrics` as JSON
+    pub metrics_json: String,
+    /// "pending" until submission, then "accepted" or "overridden"
+    pub status: String,
+    pub final_score: Option<i32>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzerFinding {
+    /// "clippy", "eslint" or "ruff"
+    pub tool: String,
+    /// "quality" or "performance"
+    pub category: String,
+    /// "error", "warning" or "info"
+    pub severity: String,
+    pub rule: Option<String>,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisMetrics {
+    pub files_analyzed: usize,
+    pub code_lines: usize,
+    /// Branch points (if, loops, match arms, boolean operators) across all files
+    pub branches: usize,
+    pub max_nesting: usize,
+    pub max_loop_nesting: usize,
+    pub tools_run: Vec<String>,
+    /// Linters that were wanted but missing or failed, with the reason
+    pub tools_skipped: Vec<String>,
+}
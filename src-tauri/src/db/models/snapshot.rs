@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttemptSnapshot {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub sequence: i32,
+    pub daily_summary: String,
+    pub what_went_well: String,
+    pub what_to_improve: String,
+    pub key_learnings: String,
+    pub memory_rebuild_notes: String,
+    pub code_snapshot: String,
+    /// `Vec<ExerciseEntryDraft>` as JSON
+    pub exercise_entries_json: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttemptSnapshotSummary {
+    pub id: String,
+    pub day_attempt_id: String,
+    pub sequence: i32,
+    pub code_length: i32,
+    pub exercise_count: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExerciseEntryDraft {
+    pub id: Option<String>,
+    pub language: String,
+    pub code: String,
+    pub notes: String,
+    #[serde(default)]
+    pub expected_output: Option<String>,
+}
+
+/// Draft state sent by the editor on every autosave. Fields left out carry over from the
+/// previous snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutosaveAttemptInput {
+    pub daily_summary: Option<String>,
+    pub what_went_well: Option<String>,
+    pub what_to_improve: Option<String>,
+    pub key_learnings: Option<String>,
+    pub memory_rebuild_notes: Option<String>,
+    pub code_snapshot: Option<String>,
+    pub exercise_entries: Option<Vec<ExerciseEntryDraft>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotPreview {
+    pub snapshot: AttemptSnapshot,
+    pub exercise_entries: Vec<ExerciseEntryDraft>,
+    /// Fields where the snapshot differs from the attempt's saved state
+    pub changed_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RecoverableDraft {
+    pub day_attempt_id: String,
+    pub day_plan_id: String,
+    pub day_title: String,
+    pub snapshot_id: String,
+    pub snapshot_created_at: String,
+    pub saved_at: String,
+}
@@ -12,6 +12,10 @@ pub struct SpacedRepetition {
     pub last_review_date: String,
     pub next_review_date: String,
     pub last_score: i32,
+    /// FSRS memory state, kept up to date whichever scheduler is selected; `None` until the
+    /// first review recorded with it
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -34,3 +38,30 @@ pub struct DueReview {
     pub next_review_date: String,
     pub interval_days: i32,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgettingAlert {
+    pub id: String,
+    pub day_plan_id: String,
+    pub day_title: String,
+    pub day_number: i32,
+    pub concept_tag_id: String,
+    pub concept_name: String,
+    pub last_review_date: String,
+    pub next_review_date: String,
+    pub elapsed_days: i32,
+    /// Estimated probability of recalling the concept today
+    pub predicted_recall: f64,
+    /// `1 - predicted_recall`
+    pub risk: f64,
+    /// 1 plus the days that depend on this day, directly or through other days
+    pub importance: f64,
+    pub dependent_day_count: i32,
+    /// Dependent days without a passing attempt yet
+    pub upcoming_dependent_count: i32,
+    /// `risk * importance`; alerts are ordered by it
+    pub priority: f64,
+    /// "review", "memory_rebuild" or "replay_day"
+    pub suggested_action: String,
+    pub message: String,
+}
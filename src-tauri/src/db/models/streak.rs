@@ -22,3 +22,27 @@ pub struct StreakFreeze {
     pub reason: String,
     pub created_at: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StudyActivity {
+    pub program_id: String,
+    /// `YYYY-MM-DD`
+    pub activity_date: String,
+    pub attempts_submitted: i32,
+    pub reviews_completed: i32,
+    pub minutes_studied: i32,
+    pub last_activity_at: String,
+}
+
+/// One cell of the contribution heatmap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityHeatmapDay {
+    pub date: String,
+    /// "studied", "frozen", "rest", "missed" or "none"
+    pub status: String,
+    /// 0 without study, otherwise 1-4 by effort relative to the busiest day shown
+    pub level: i32,
+    pub attempts_submitted: i32,
+    pub reviews_completed: i32,
+    pub minutes_studied: i32,
+}
@@ -0,0 +1,189 @@
+use crate::db::models::*;
+use crate::error::AppError;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use sqlx::{Executor, SqliteConnection};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A model field or table the migrated schema doesn't have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub model: &'static str,
+    pub table: &'static str,
+    pub problem: String,
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.model, self.table, self.problem)
+    }
+}
+
+struct TableModel {
+    model: &'static str,
+    table: &'static str,
+    fields: &'static [&'static str],
+}
+
+fn table<T: DeserializeOwned>(table: &'static str) -> TableModel {
+    let path = std::any::type_name::<T>();
+    TableModel {
+        model: path.rsplit("::").next().unwrap_or(path),
+        table,
+        fields: field_names::<T>(),
+    }
+}
+
+/// Models read with `SELECT *` from a single table. Summaries and other join results are
+/// covered by preparing their statements instead.
+fn table_models() -> Vec<TableModel> {
+    vec![
+        table::<Program>("programs"),
+        table::<Module>("modules"),
+        table::<DayPlan>("day_plans"),
+        table::<ChecklistItem>("checklist_items"),
+        table::<AttemptChecklist>("attempt_checklist"),
+        table::<QuizQuestion>("quiz_questions"),
+        table::<QuizAttempt>("quiz_attempts"),
+        table::<QuizSession>("quiz_sessions"),
+        table::<ConceptTag>("concept_tags"),
+        table::<DayPlanTag>("day_plan_tags"),
+        table::<DayDependency>("day_dependencies"),
+        table::<DayAttempt>("day_attempts"),
+        table::<ExerciseEntry>("exercise_entries"),
+        table::<ExerciseRun>("exercise_runs"),
+        table::<Artifact>("artifacts"),
+        table::<BugLog>("bug_logs"),
+        table::<SpacedRepetition>("spaced_repetition"),
+        table::<ReviewLog>("review_log"),
+        table::<QuizCard>("quiz_cards"),
+        table::<QuizCardReview>("quiz_card_reviews"),
+        table::<Streak>("streaks"),
+        table::<StreakFreeze>("streak_freezes"),
+        table::<StudyActivity>("study_activity"),
+        table::<Badge>("badges"),
+        table::<BadgeDefinition>("badge_definitions"),
+        table::<SkillScore>("skill_scores"),
+        table::<UserCapacityProfile>("user_capacity_profiles"),
+        table::<DaySession>("day_sessions"),
+        table::<SessionInterruption>("session_interruptions"),
+        table::<TimeRecommendation>("time_recommendations"),
+        table::<ImportJob>("import_jobs"),
+        table::<ImportPlanEdit>("import_plan_edits"),
+        table::<ScoringRubric>("scoring_rubrics"),
+        table::<RubricDimension>("rubric_dimensions"),
+        table::<AttemptDimensionScore>("attempt_dimension_scores"),
+        table::<AttemptSnapshot>("attempt_snapshots"),
+        table::<AttemptScoreSuggestion>("attempt_score_suggestions"),
+        table::<GitEvidence>("git_evidence"),
+        table::<MemoryRebuildSession>("memory_rebuild_sessions"),
+    ]
+}
+
+/// Compares every table model's fields with the columns of its table.
+pub async fn check_models(conn: &mut SqliteConnection) -> Result<Vec<SchemaDrift>, AppError> {
+    let mut drift = Vec::new();
+
+    for model in table_models() {
+        let columns: HashSet<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+                .bind(model.table)
+                .fetch_all(&mut *conn)
+                .await?
+                .into_iter()
+                .collect();
+
+        if columns.is_empty() {
+            drift.push(SchemaDrift {
+                model: model.model,
+                table: model.table,
+                problem: "table does not exist".to_string(),
+            });
+            continue;
+        }
+        if model.fields.is_empty() {
+            drift.push(SchemaDrift {
+                model: model.model,
+                table: model.table,
+                problem: "model fields could not be read".to_string(),
+            });
+            continue;
+        }
+
+        for field in model.fields {
+            if !columns.contains(*field) {
+                drift.push(SchemaDrift {
+                    model: model.model,
+                    table: model.table,
+                    problem: format!("field `{}` has no column", field),
+                });
+            }
+        }
+    }
+
+    Ok(drift)
+}
+
+/// Prepares `sql` without running it, which makes SQLite resolve every table and column it
+/// names.
+pub async fn check_statement(conn: &mut SqliteConnection, sql: &str) -> Result<(), AppError> {
+    conn.prepare(sql).await?;
+    Ok(())
+}
+
+/// The field names serde derives for `T`, read by asking it to deserialize from a
+/// deserializer that stops at the struct description.
+fn field_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    match T::deserialize(FieldProbe) {
+        Err(Probe::Fields(fields)) => fields,
+        _ => &[],
+    }
+}
+
+#[derive(Debug)]
+enum Probe {
+    Fields(&'static [&'static str]),
+    Unsupported(String),
+}
+
+impl fmt::Display for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Probe::Fields(fields) => write!(f, "struct with {} fields", fields.len()),
+            Probe::Unsupported(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Probe {}
+
+impl de::Error for Probe {
+    fn custom<M: fmt::Display>(message: M) -> Self {
+        Probe::Unsupported(message.to_string())
+    }
+}
+
+struct FieldProbe;
+
+impl<'de> Deserializer<'de> for FieldProbe {
+    type Error = Probe;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Probe> {
+        Err(Probe::Unsupported("not a struct".to_string()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Probe> {
+        Err(Probe::Fields(fields))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
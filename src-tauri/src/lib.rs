@@ -4,7 +4,7 @@
 pub mod db;
 pub mod error;
 pub mod commands;
-// pub mod services;  // Phase 12 - PDF import
+pub mod services;
 
 use sqlx::sqlite::SqlitePoolOptions;
 use tauri::Manager;
@@ -57,10 +57,35 @@ pub fn run() {
                 // Run migrations
                 db::run_migrations(&pool).await;
 
+                // Drafts whose autosave is newer than their last save survived a crash
+                if let Ok(mut conn) = pool.acquire().await {
+                    match services::snapshots::find_recoverable(&mut conn).await {
+                        Ok(drafts) if !drafts.is_empty() => tracing::warn!(
+                            "{} attempt draft(s) can be recovered from autosave",
+                            drafts.len()
+                        ),
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!("Draft recovery check failed: {}", e),
+                    }
+
+                    // Models that no longer match the schema fail at runtime on first use
+                    match db::schema_check::check_models(&mut conn).await {
+                        Ok(drift) => {
+                            for mismatch in &drift {
+                                tracing::error!("Schema drift: {}", mismatch);
+                            }
+                        }
+                        Err(e) => tracing::warn!("Schema drift check failed: {}", e),
+                    }
+                }
+
                 pool
             });
 
             app.manage(AppState { db: pool });
+            app.manage(services::artifact_store::ArtifactStore::new(
+                app_data_dir.join("artifacts"),
+            ));
 
             tracing::info!("BuildOps 40 initialized successfully");
 
@@ -104,12 +129,14 @@ pub fn run() {
             crate::commands::add_dependency,
             crate::commands::remove_dependency,
             crate::commands::check_dependencies,
-            // Phase 3: Attempts & Working Screen (20 commands)
+            // Phase 3: Attempts & Working Screen (23 commands)
             crate::commands::start_attempt,
             crate::commands::get_attempt,
             crate::commands::get_current_attempt,
             crate::commands::list_attempts,
+            crate::commands::compare_attempts,
             crate::commands::update_attempt,
+            crate::commands::set_attempt_project_path,
             crate::commands::autosave_attempt,
             crate::commands::submit_attempt,
             crate::commands::delete_attempt,
@@ -125,13 +152,15 @@ pub fn run() {
             crate::commands::update_bug_log,
             crate::commands::list_bug_logs,
             crate::commands::delete_bug_log,
-            // Phase 4: Intelligence Layer (10 commands)
+            crate::commands::find_similar_bugs,
+            // Phase 4: Intelligence Layer (11 commands)
             crate::commands::get_due_reviews,
             crate::commands::record_review,
             crate::commands::get_forgetting_curve_alerts,
             crate::commands::get_streak,
             crate::commands::update_streak,
             crate::commands::use_streak_freeze,
+            crate::commands::get_activity_heatmap,
             crate::commands::get_badges,
             crate::commands::check_and_award_badges,
             crate::commands::get_skill_radar,
@@ -145,15 +174,79 @@ pub fn run() {
             crate::commands::pause_session,
             crate::commands::complete_session,
             crate::commands::list_sessions,
-            // Import Pipeline (8 commands)
+            // Import Pipeline (15 commands)
             crate::commands::start_import,
             crate::commands::get_import_job,
             crate::commands::get_import_preview,
             crate::commands::update_import_preview,
+            crate::commands::get_import_report,
+            crate::commands::apply_import_edit,
+            crate::commands::undo_import_edit,
+            crate::commands::list_import_edits,
+            crate::commands::generate_capacity_plan,
             crate::commands::apply_import,
             crate::commands::cancel_import,
             crate::commands::list_import_jobs,
             crate::commands::delete_import_job,
+            crate::commands::preview_reimport,
+            crate::commands::apply_reimport,
+            // Scoring Rubrics (4 commands)
+            crate::commands::get_program_rubric,
+            crate::commands::save_program_rubric,
+            crate::commands::reset_program_rubric,
+            crate::commands::get_attempt_dimension_scores,
+            // Quiz Engine (4 commands)
+            crate::commands::start_quiz,
+            crate::commands::submit_quiz_answers,
+            crate::commands::get_quiz_result,
+            crate::commands::list_quiz_sessions,
+            // Attempt Checklists (3 commands)
+            crate::commands::get_attempt_checklist,
+            crate::commands::toggle_checklist_item,
+            crate::commands::validate_attempt_submission,
+            // Autosave Snapshots (4 commands)
+            crate::commands::list_attempt_snapshots,
+            crate::commands::preview_attempt_snapshot,
+            crate::commands::restore_attempt_snapshot,
+            crate::commands::find_recoverable_drafts,
+            // Exercise Runs (2 commands)
+            crate::commands::run_exercise_entry,
+            crate::commands::list_exercise_runs,
+            // Code Analysis (2 commands)
+            crate::commands::analyze_attempt_code,
+            crate::commands::list_score_suggestions,
+            // Git Evidence (2 commands)
+            crate::commands::capture_git_evidence,
+            crate::commands::get_git_evidence,
+            // Memory Rebuild (4 commands)
+            crate::commands::start_memory_rebuild,
+            crate::commands::submit_memory_rebuild,
+            crate::commands::get_memory_rebuild_result,
+            crate::commands::list_memory_rebuild_sessions,
+            // Artifact Store (3 commands)
+            crate::commands::import_artifact_file,
+            crate::commands::verify_artifact_blobs,
+            crate::commands::collect_artifact_garbage,
+            // Bug Analytics (1 command)
+            crate::commands::get_bug_analytics,
+            // Review Scheduler (4 commands)
+            crate::commands::get_review_scheduler_settings,
+            crate::commands::update_review_scheduler_settings,
+            crate::commands::list_review_log,
+            crate::commands::optimize_fsrs_parameters,
+            // Review Queue (2 commands)
+            crate::commands::build_review_session,
+            crate::commands::get_review_forecast,
+            // Quiz Cards (5 commands)
+            crate::commands::sync_quiz_cards,
+            crate::commands::get_due_quiz_cards,
+            crate::commands::answer_quiz_card,
+            crate::commands::list_leech_cards,
+            crate::commands::set_quiz_card_suspended,
+            // Badge Rules (3 commands)
+            crate::commands::list_badge_definitions,
+            crate::commands::get_badge_progress,
+            crate::commands::backfill_badges,
         ])
         .run(tauri::generate_context!())
         .expect("Error running BuildOps 40");
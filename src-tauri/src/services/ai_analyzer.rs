@@ -235,7 +235,7 @@ Analyze the provided content and generate a complete learning program with the f
           "quiz_questions": [
             {
               "question_text": "string",
-              "question_type": "short_answer | multiple_choice | code_prompt | reflection",
+              "question_type": "short_answer | multiple_choice | multi_select | true_false | numeric | code_prompt | reflection",
               "correct_answer": "string",
               "options": ["string"] | [],
               "points": number,
@@ -0,0 +1,369 @@
+use crate::db::models::ArtifactGcReport;
+use crate::error::AppError;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+const BLOB_DIR: &str = "blobs";
+const THUMBNAIL_DIR: &str = "thumbnails";
+const STAGING_DIR: &str = "staging";
+const THUMBNAIL_SIZE: u32 = 320;
+/// Larger images are stored without a thumbnail rather than decoded in full.
+const MAX_THUMBNAIL_SOURCE_BYTES: u64 = 64 * 1024 * 1024;
+/// Garbage collection leaves younger files alone, since an import writes its blob before
+/// the artifact row that references it.
+const GC_GRACE_PERIOD: Duration = Duration::from_secs(15 * 60);
+const SNIFF_BYTES: usize = 512;
+
+/// Files imported as artifacts, stored once per distinct content at
+/// `blobs/<first two hex digits>/<sha256>` under the app data `artifacts` directory.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    root: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredBlob {
+    pub sha256: String,
+    /// Relative to the store root
+    pub storage_path: String,
+    pub absolute_path: PathBuf,
+    pub size_bytes: i64,
+    pub mime_type: String,
+    pub thumbnail_path: Option<PathBuf>,
+    /// An identical blob was already stored
+    pub deduplicated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobStatus {
+    Healthy,
+    Missing,
+    Corrupted { actual_sha256: String },
+}
+
+impl ArtifactStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Copies `source` into the store, hashing it on the way.
+    pub async fn import(&self, source: &Path) -> Result<StoredBlob, AppError> {
+        let root = self.root.clone();
+        let source = source.to_path_buf();
+        blocking(move || import_file(&root, &source)).await
+    }
+
+    /// Re-hashes a stored blob and compares it with the hash recorded at import.
+    pub async fn verify(&self, storage_path: &str, expected_sha256: &str) -> Result<BlobStatus, AppError> {
+        let path = self.root.join(storage_path);
+        let expected = expected_sha256.to_string();
+        blocking(move || {
+            if !path.is_file() {
+                return Ok(BlobStatus::Missing);
+            }
+            let (actual_sha256, _) = hash_file(&path)?;
+            Ok(if actual_sha256 == expected {
+                BlobStatus::Healthy
+            } else {
+                BlobStatus::Corrupted { actual_sha256 }
+            })
+        })
+        .await
+    }
+
+    /// Removes blobs and thumbnails whose hash is not in `referenced`, along with staging
+    /// files left behind by interrupted imports.
+    pub async fn collect_garbage(&self, referenced: HashSet<String>) -> Result<ArtifactGcReport, AppError> {
+        let root = self.root.clone();
+        blocking(move || collect_garbage(&root, &referenced)).await
+    }
+}
+
+async fn blocking<T, F>(work: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, AppError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| AppError::FileSystem(io::Error::other(e)))?
+}
+
+fn import_file(root: &Path, source: &Path) -> Result<StoredBlob, AppError> {
+    if !source.is_file() {
+        return Err(AppError::Validation(format!("{} is not a file", source.display())));
+    }
+
+    let staging_dir = root.join(STAGING_DIR);
+    fs::create_dir_all(&staging_dir)?;
+    let staging = staging_dir.join(Uuid::new_v4().to_string());
+
+    let copied = copy_hashing(source, &staging);
+    let (sha256, size_bytes, head) = match copied {
+        Ok(copied) => copied,
+        Err(e) => {
+            let _ = fs::remove_file(&staging);
+            return Err(e.into());
+        }
+    };
+
+    let storage_path = format!("{}/{}/{}", BLOB_DIR, &sha256[..2], sha256);
+    let absolute_path = root.join(&storage_path);
+
+    // A damaged stored blob is replaced by the fresh copy
+    let deduplicated = absolute_path.is_file()
+        && hash_file(&absolute_path).is_ok_and(|(existing, _)| existing == sha256);
+    if deduplicated {
+        fs::remove_file(&staging)?;
+    } else {
+        if let Some(shard) = absolute_path.parent() {
+            fs::create_dir_all(shard)?;
+        }
+        fs::rename(&staging, &absolute_path)?;
+    }
+
+    let mime_type = detect_mime(&head, source).to_string();
+    let thumbnail_path = if is_thumbnailable(&mime_type) && size_bytes <= MAX_THUMBNAIL_SOURCE_BYTES {
+        let target = root.join(THUMBNAIL_DIR).join(format!("{}.png", sha256));
+        if target.is_file() {
+            Some(target)
+        } else {
+            match write_thumbnail(&absolute_path, &target, &staging_dir) {
+                Ok(()) => Some(target),
+                Err(e) => {
+                    tracing::warn!("No thumbnail for {}: {}", source.display(), e);
+                    None
+                }
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(StoredBlob {
+        sha256,
+        storage_path,
+        absolute_path,
+        size_bytes: i64::try_from(size_bytes).unwrap_or(i64::MAX),
+        mime_type,
+        thumbnail_path,
+        deduplicated,
+    })
+}
+
+/// Copies `source` to `target` and returns the content hash, the size and the first bytes
+/// for MIME sniffing.
+fn copy_hashing(source: &Path, target: &Path) -> io::Result<(String, u64, Vec<u8>)> {
+    let mut reader = File::open(source)?;
+    let mut writer = File::create(target)?;
+    let mut hasher = Sha256::new();
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    let mut size = 0u64;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        hasher.update(chunk);
+        writer.write_all(chunk)?;
+        if head.len() < SNIFF_BYTES {
+            let take = (SNIFF_BYTES - head.len()).min(read);
+            head.extend_from_slice(&chunk[..take]);
+        }
+        size += read as u64;
+    }
+    writer.sync_all()?;
+
+    Ok((to_hex(&hasher.finalize()), size, head))
+}
+
+fn hash_file(path: &Path) -> io::Result<(String, u64)> {
+    let mut reader = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let size = io::copy(&mut reader, &mut hasher)?;
+    Ok((to_hex(&hasher.finalize()), size))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn is_sha256(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Magic bytes decide first; the extension only names text formats, which have none.
+pub fn detect_mime(head: &[u8], source: &Path) -> &'static str {
+    const SIGNATURES: [(&[u8], &str); 8] = [
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"\x00asm", "application/wasm"),
+    ];
+
+    if let Some((_, mime)) = SIGNATURES.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return mime;
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" {
+        match &head[8..12] {
+            b"WEBP" => return "image/webp",
+            b"WAVE" => return "audio/wav",
+            _ => {}
+        }
+    }
+    if head.len() >= 12 && &head[4..8] == b"ftyp" {
+        return if &head[8..10] == b"qt" { "video/quicktime" } else { "video/mp4" };
+    }
+
+    let extension = source
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let by_extension = match extension.as_str() {
+        "svg" => Some("image/svg+xml"),
+        "md" | "markdown" => Some("text/markdown"),
+        "html" | "htm" => Some("text/html"),
+        "css" => Some("text/css"),
+        "csv" => Some("text/csv"),
+        "json" => Some("application/json"),
+        "js" | "mjs" | "cjs" => Some("text/javascript"),
+        "ts" | "tsx" => Some("text/typescript"),
+        "rs" => Some("text/x-rust"),
+        "py" => Some("text/x-python"),
+        "toml" => Some("application/toml"),
+        "yaml" | "yml" => Some("application/yaml"),
+        _ => None,
+    };
+    if let Some(mime) = by_extension {
+        return mime;
+    }
+
+    // A cut in the middle of a multi-byte character still counts as text
+    let text = match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    };
+    if text && !head.contains(&0) {
+        "text/plain"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+fn is_thumbnailable(mime_type: &str) -> bool {
+    matches!(mime_type, "image/png" | "image/jpeg" | "image/gif" | "image/webp")
+}
+
+/// Writes a PNG no larger than THUMBNAIL_SIZE on either side, via a staging file so a failed
+/// encode never leaves a partial thumbnail behind.
+fn write_thumbnail(blob: &Path, target: &Path, staging_dir: &Path) -> Result<(), AppError> {
+    let bytes = fs::read(blob)?;
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| AppError::Validation(format!("Unreadable image: {}", e)))?;
+
+    let staging = staging_dir.join(format!("{}.png", Uuid::new_v4()));
+    let written = image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .to_rgba8()
+        .save_with_format(&staging, image::ImageFormat::Png);
+    if let Err(e) = written {
+        let _ = fs::remove_file(&staging);
+        return Err(AppError::Validation(format!("Thumbnail encoding failed: {}", e)));
+    }
+
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::rename(&staging, target)?;
+
+    Ok(())
+}
+
+fn collect_garbage(root: &Path, referenced: &HashSet<String>) -> Result<ArtifactGcReport, AppError> {
+    let mut report = ArtifactGcReport {
+        removed_blobs: 0,
+        removed_thumbnails: 0,
+        reclaimed_bytes: 0,
+    };
+
+    for shard in read_dir_if_exists(&root.join(BLOB_DIR))? {
+        if !shard.is_dir() {
+            continue;
+        }
+        for blob in read_dir_if_exists(&shard)? {
+            let Some(name) = blob.file_name().map(|name| name.to_string_lossy().to_string()) else {
+                continue;
+            };
+            // Files the store didn't write are left alone
+            if !is_sha256(&name) || referenced.contains(&name) {
+                continue;
+            }
+            if let Some(size) = remove_if_stale(&blob)? {
+                report.removed_blobs += 1;
+                report.reclaimed_bytes += size;
+            }
+        }
+        // Only succeeds once the shard is empty
+        let _ = fs::remove_dir(&shard);
+    }
+
+    for thumbnail in read_dir_if_exists(&root.join(THUMBNAIL_DIR))? {
+        let Some(stem) = thumbnail.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if !is_sha256(&stem) || referenced.contains(&stem) {
+            continue;
+        }
+        if let Some(size) = remove_if_stale(&thumbnail)? {
+            report.removed_thumbnails += 1;
+            report.reclaimed_bytes += size;
+        }
+    }
+
+    for leftover in read_dir_if_exists(&root.join(STAGING_DIR))? {
+        if let Some(size) = remove_if_stale(&leftover)? {
+            report.reclaimed_bytes += size;
+        }
+    }
+
+    Ok(report)
+}
+
+fn read_dir_if_exists(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|entry| entry.map(|entry| entry.path())).collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Deletes a file older than the grace period and returns its size.
+fn remove_if_stale(path: &Path) -> io::Result<Option<i64>> {
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_file() {
+        return Ok(None);
+    }
+    let age = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .unwrap_or_default();
+    if age < GC_GRACE_PERIOD {
+        return Ok(None);
+    }
+
+    fs::remove_file(path)?;
+    Ok(Some(i64::try_from(metadata.len()).unwrap_or(i64::MAX)))
+}
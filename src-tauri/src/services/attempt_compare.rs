@@ -0,0 +1,220 @@
+use crate::db::models::{
+    AttemptComparison, AttemptDimensionScore, BugLog, ComparedAttempt, DayAttempt,
+    DimensionDelta, ExerciseDiff, ExerciseEntry, TextFieldDiff, TimeDelta,
+};
+use crate::error::AppError;
+use crate::services::similarity::{line_diff, title_similarity};
+use chrono::DateTime;
+use sqlx::SqliteConnection;
+
+/// Symptoms at least this similar (within one category) count as the same bug recurring.
+const SAME_BUG_THRESHOLD: f64 = 0.8;
+
+pub async fn compare(
+    conn: &mut SqliteConnection,
+    base_attempt_id: &str,
+    target_attempt_id: &str,
+) -> Result<AttemptComparison, AppError> {
+    let base = fetch_attempt(conn, base_attempt_id).await?;
+    let target = fetch_attempt(conn, target_attempt_id).await?;
+    if base.day_plan_id != target.day_plan_id {
+        return Err(AppError::Validation(
+            "Only attempts of the same day plan can be compared".to_string(),
+        ));
+    }
+
+    let base_scores = dimension_scores(conn, &base.id).await?;
+    let target_scores = dimension_scores(conn, &target.id).await?;
+    let base_exercises = exercise_entries(conn, &base.id).await?;
+    let target_exercises = exercise_entries(conn, &target.id).await?;
+    let base_bugs = bug_logs(conn, &base.id).await?;
+    let target_bugs = bug_logs(conn, &target.id).await?;
+
+    let recurs = |bug: &BugLog, others: &[BugLog]| {
+        others.iter().any(|other| {
+            other.category == bug.category
+                && title_similarity(&other.symptom, &bug.symptom) >= SAME_BUG_THRESHOLD
+        })
+    };
+    let bugs_added: Vec<BugLog> = target_bugs
+        .iter()
+        .filter(|bug| !recurs(bug, &base_bugs))
+        .cloned()
+        .collect();
+    let bugs_removed: Vec<BugLog> = base_bugs
+        .iter()
+        .filter(|bug| !recurs(bug, &target_bugs))
+        .cloned()
+        .collect();
+
+    let reflection_diffs = [
+        ("daily_summary", &base.daily_summary, &target.daily_summary),
+        ("what_went_well", &base.what_went_well, &target.what_went_well),
+        ("what_to_improve", &base.what_to_improve, &target.what_to_improve),
+        ("key_learnings", &base.key_learnings, &target.key_learnings),
+        ("memory_rebuild_notes", &base.memory_rebuild_notes, &target.memory_rebuild_notes),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(field, old, new)| TextFieldDiff {
+        field: field.to_string(),
+        lines: line_diff(old, new),
+    })
+    .collect();
+
+    Ok(AttemptComparison {
+        day_plan_id: base.day_plan_id.clone(),
+        total_score_delta: target.total_score - base.total_score,
+        dimensions: dimension_deltas(&base_scores, &target_scores),
+        time: TimeDelta {
+            base_minutes: base.actual_minutes,
+            target_minutes: target.actual_minutes,
+            delta_minutes: target.actual_minutes - base.actual_minutes,
+            base_elapsed_minutes: elapsed_minutes(&base),
+            target_elapsed_minutes: elapsed_minutes(&target),
+        },
+        code_diff: line_diff(&base.code_snapshot, &target.code_snapshot),
+        exercise_diffs: exercise_diffs(&base_exercises, &target_exercises),
+        bugs_added,
+        bugs_removed,
+        reflection_diffs,
+        base: compared(&base),
+        target: compared(&target),
+    })
+}
+
+async fn fetch_attempt(conn: &mut SqliteConnection, id: &str) -> Result<DayAttempt, AppError> {
+    sqlx::query_as::<_, DayAttempt>("SELECT * FROM day_attempts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "DayAttempt".to_string(),
+            id: id.to_string(),
+        })
+}
+
+async fn dimension_scores(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<AttemptDimensionScore>, AppError> {
+    let scores = sqlx::query_as::<_, AttemptDimensionScore>(
+        "SELECT * FROM attempt_dimension_scores WHERE day_attempt_id = ? ORDER BY created_at, dimension_key",
+    )
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(scores)
+}
+
+async fn exercise_entries(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<ExerciseEntry>, AppError> {
+    let entries = sqlx::query_as::<_, ExerciseEntry>(
+        "SELECT * FROM exercise_entries WHERE day_attempt_id = ? ORDER BY order_index",
+    )
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(entries)
+}
+
+async fn bug_logs(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<BugLog>, AppError> {
+    let bugs = sqlx::query_as::<_, BugLog>(
+        "SELECT * FROM bug_logs WHERE day_attempt_id = ? ORDER BY created_at",
+    )
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(bugs)
+}
+
+/// One row per dimension scored on either attempt, base order first.
+fn dimension_deltas(
+    base: &[AttemptDimensionScore],
+    target: &[AttemptDimensionScore],
+) -> Vec<DimensionDelta> {
+    let percent = |score: &AttemptDimensionScore| {
+        100.0 * f64::from(score.score) / f64::from(score.max_points.max(1))
+    };
+
+    let mut keys: Vec<&AttemptDimensionScore> = base.iter().collect();
+    keys.extend(
+        target
+            .iter()
+            .filter(|t| !base.iter().any(|b| b.dimension_key == t.dimension_key)),
+    );
+
+    keys.into_iter()
+        .map(|dimension| {
+            let old = base.iter().find(|s| s.dimension_key == dimension.dimension_key);
+            let new = target.iter().find(|s| s.dimension_key == dimension.dimension_key);
+            DimensionDelta {
+                dimension_key: dimension.dimension_key.clone(),
+                label: new.unwrap_or(dimension).label.clone(),
+                base_score: old.map(|s| s.score),
+                base_max_points: old.map(|s| s.max_points),
+                target_score: new.map(|s| s.score),
+                target_max_points: new.map(|s| s.max_points),
+                delta_percent: old
+                    .zip(new)
+                    .map(|(old, new)| ((percent(new) - percent(old)) * 10.0).round() / 10.0),
+            }
+        })
+        .collect()
+}
+
+/// Entries are paired by position; an entry present on one side only diffs against empty text.
+fn exercise_diffs(base: &[ExerciseEntry], target: &[ExerciseEntry]) -> Vec<ExerciseDiff> {
+    (0..base.len().max(target.len()))
+        .filter_map(|position| {
+            let old = base.get(position);
+            let new = target.get(position);
+            let text = |entry: Option<&ExerciseEntry>, pick: fn(&ExerciseEntry) -> &str| {
+                entry.map_or("", pick).to_string()
+            };
+
+            let (old_code, new_code) = (text(old, |e| &e.code), text(new, |e| &e.code));
+            let (old_notes, new_notes) = (text(old, |e| &e.notes), text(new, |e| &e.notes));
+            let unchanged = old.zip(new).is_some_and(|(old, new)| old.language == new.language)
+                && old_code == new_code
+                && old_notes == new_notes;
+            if unchanged {
+                return None;
+            }
+
+            Some(ExerciseDiff {
+                position,
+                base_entry_id: old.map(|e| e.id.clone()),
+                target_entry_id: new.map(|e| e.id.clone()),
+                base_language: old.map(|e| e.language.clone()),
+                target_language: new.map(|e| e.language.clone()),
+                code_diff: line_diff(&old_code, &new_code),
+                notes_diff: line_diff(&old_notes, &new_notes),
+            })
+        })
+        .collect()
+}
+
+fn elapsed_minutes(attempt: &DayAttempt) -> Option<i64> {
+    let started = DateTime::parse_from_rfc3339(&attempt.started_at).ok()?;
+    let submitted = DateTime::parse_from_rfc3339(attempt.submitted_at.as_deref()?).ok()?;
+    Some((submitted - started).num_minutes())
+}
+
+fn compared(attempt: &DayAttempt) -> ComparedAttempt {
+    ComparedAttempt {
+        id: attempt.id.clone(),
+        attempt_number: attempt.attempt_number,
+        status: attempt.status.clone(),
+        total_score: attempt.total_score,
+        submitted_at: attempt.submitted_at.clone(),
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::AppError;
+use sqlx::SqliteConnection;
+
+pub const IN_PROGRESS: &str = "in_progress";
+pub const PASSED: &str = "passed";
+pub const BLOCKED: &str = "blocked";
+pub const MASTERY: &str = "mastery";
+
+/// The attempt lifecycle: work happens while `in_progress`, and submission grades it
+/// straight to `passed`, `blocked` or `mastery`. Graded attempts are final.
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!((from, to), (IN_PROGRESS, PASSED | BLOCKED | MASTERY))
+}
+
+pub fn ensure_transition(from: &str, to: &str) -> Result<(), AppError> {
+    if can_transition(from, to) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidStateTransition(format!(
+            "An attempt cannot go from {} to {}",
+            from, to
+        )))
+    }
+}
+
+/// Notes, scores and autosaves only change while the attempt is still being worked on.
+pub fn ensure_editable(status: &str) -> Result<(), AppError> {
+    if status == IN_PROGRESS {
+        Ok(())
+    } else {
+        Err(AppError::InvalidStateTransition(format!(
+            "A {} attempt can no longer be edited",
+            status
+        )))
+    }
+}
+
+/// A new attempt may follow any graded one, so passed days can be replayed; an open
+/// attempt has to be finished first.
+pub fn ensure_can_start(latest_status: Option<&str>) -> Result<(), AppError> {
+    if latest_status == Some(IN_PROGRESS) {
+        Err(AppError::InvalidStateTransition(
+            "This day already has an attempt in progress".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn current_status(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<String, AppError> {
+    sqlx::query_scalar("SELECT status FROM day_attempts WHERE id = ?")
+        .bind(day_attempt_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "DayAttempt".to_string(),
+            id: day_attempt_id.to_string(),
+        })
+}
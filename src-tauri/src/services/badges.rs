@@ -0,0 +1,400 @@
+use crate::db::models::{Badge, BadgeDefinition, BadgeProgress};
+use crate::error::AppError;
+use crate::services::streaks;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// How a metric has grown: its value now, and when it first reached each value.
+#[derive(Debug, Clone, Default)]
+struct MetricHistory {
+    current: i32,
+    /// `reached_at[n - 1]` is when the metric first reached `n`
+    reached_at: Vec<String>,
+}
+
+impl MetricHistory {
+    /// Counts events given their timestamps in order.
+    fn count(events: Vec<String>) -> Self {
+        Self {
+            current: events.len() as i32,
+            reached_at: events,
+        }
+    }
+
+    /// Runs of consecutive passing rows, given `(timestamp, passed)` in order. A row without
+    /// a timestamp hasn't happened yet and breaks the run, unless nothing after it has
+    /// happened either.
+    fn runs(mut rows: Vec<(Option<String>, bool)>) -> Self {
+        let happened = rows.iter().rposition(|(at, _)| at.is_some()).map_or(0, |last| last + 1);
+        rows.truncate(happened);
+
+        let mut history = Self::default();
+        let mut run = 0;
+        let mut latest: Option<String> = None;
+        for (at, passed) in rows {
+            let Some(at) = at.filter(|_| passed) else {
+                run = 0;
+                latest = None;
+                continue;
+            };
+            run += 1;
+            if latest.as_deref().map_or(true, |latest| at.as_str() > latest) {
+                latest = Some(at);
+            }
+            if run > history.reached_at.len() {
+                history.reached_at.extend(latest.clone());
+            }
+        }
+        history.current = run as i32;
+
+        history
+    }
+
+    /// When the metric first reached `target`, if it has.
+    fn reached(&self, target: i32) -> Option<&String> {
+        self.reached_at.get(usize::try_from(target).ok()?.checked_sub(1)?)
+    }
+}
+
+/// Active badge definitions in display order.
+pub async fn definitions(conn: &mut SqliteConnection) -> Result<Vec<BadgeDefinition>, AppError> {
+    let definitions = sqlx::query_as::<_, BadgeDefinition>(
+        "SELECT * FROM badge_definitions WHERE is_active = 1 ORDER BY sort_order, badge_type",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(definitions)
+}
+
+/// Computes `metric` for `program_id`, or `None` if the engine doesn't know the metric.
+async fn metric_history(
+    conn: &mut SqliteConnection,
+    metric: &str,
+    program_id: &str,
+    today: NaiveDate,
+) -> Result<Option<MetricHistory>, AppError> {
+    let history = match metric {
+        "submitted_attempts" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT da.submitted_at FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                 ORDER BY da.submitted_at",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "study_day_streak" => {
+            let walk = streaks::streak_walk(conn, program_id, today).await?;
+            MetricHistory {
+                current: walk.current,
+                reached_at: walk.reached_at,
+            }
+        }
+        "consecutive_strong_days" => MetricHistory::runs(
+            sqlx::query_as(
+                "SELECT MAX(da.submitted_at), COALESCE(MAX(da.total_score), 0) >= 85
+                 FROM day_plans dp
+                 LEFT JOIN day_attempts da ON da.day_plan_id = dp.id
+                    AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                 WHERE dp.program_id = ?
+                 GROUP BY dp.id
+                 ORDER BY dp.day_number",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "unblocked_attempt_run" => MetricHistory::runs(
+            sqlx::query_as(
+                "SELECT da.submitted_at, da.status != 'blocked' FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                 ORDER BY da.submitted_at",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "memory_rebuild_run" => MetricHistory::runs(
+            sqlx::query_as(
+                "SELECT da.submitted_at, da.memory_rebuild_passed FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                 ORDER BY da.submitted_at",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "mastered_days" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT MIN(da.submitted_at) FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                    AND da.status = 'mastery'
+                 GROUP BY da.day_plan_id
+                 ORDER BY 1",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "passed_days" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT MIN(da.submitted_at) FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                    AND da.status IN ('passed', 'mastery')
+                 GROUP BY da.day_plan_id
+                 ORDER BY 1",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "perfect_quizzes" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT qs.submitted_at FROM quiz_sessions qs
+                 JOIN day_attempts da ON qs.day_attempt_id = da.id
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND qs.status = 'submitted'
+                    AND qs.submitted_at IS NOT NULL AND qs.score_percent = 100
+                 ORDER BY qs.submitted_at",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "fixed_bugs" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT bl.created_at FROM bug_logs bl
+                 JOIN day_attempts da ON bl.day_attempt_id = da.id
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND trim(bl.fix_applied) != ''
+                 ORDER BY bl.created_at",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "well_evidenced_days" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT MIN(da.submitted_at) FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                    AND (SELECT COUNT(*) FROM artifacts a WHERE a.day_attempt_id = da.id) >= 3
+                 GROUP BY da.day_plan_id
+                 ORDER BY 1",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "fast_strong_days" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT MIN(da.submitted_at) FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                    AND da.total_score >= 85 AND da.actual_minutes > 0
+                    AND da.actual_minutes < dp.estimated_minutes
+                 GROUP BY da.day_plan_id
+                 ORDER BY 1",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        "comeback_days" => MetricHistory::count(
+            sqlx::query_scalar(
+                "SELECT MIN(da.submitted_at) FROM day_attempts da
+                 JOIN day_plans dp ON da.day_plan_id = dp.id
+                 WHERE dp.program_id = ? AND da.is_draft = 0 AND da.submitted_at IS NOT NULL
+                    AND da.total_score >= 85
+                    AND EXISTS (
+                        SELECT 1 FROM day_attempts earlier
+                        WHERE earlier.day_plan_id = da.day_plan_id
+                            AND earlier.status = 'blocked'
+                            AND earlier.submitted_at < da.submitted_at
+                    )
+                 GROUP BY da.day_plan_id
+                 ORDER BY 1",
+            )
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?,
+        ),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(history))
+}
+
+/// A definition's target for `program_id`, resolving "every day of the program".
+async fn target(
+    conn: &mut SqliteConnection,
+    definition: &BadgeDefinition,
+    program_id: &str,
+) -> Result<i32, AppError> {
+    match definition.threshold {
+        Some(threshold) => Ok(threshold),
+        None => {
+            let days: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM day_plans WHERE program_id = ?")
+                .bind(program_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            // A program without days can't be completed
+            Ok(days.max(1))
+        }
+    }
+}
+
+struct Evaluation {
+    definition: BadgeDefinition,
+    target: i32,
+    history: MetricHistory,
+}
+
+async fn evaluate(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<Evaluation>, AppError> {
+    let mut histories: HashMap<String, Option<MetricHistory>> = HashMap::new();
+    let mut evaluations = Vec::new();
+
+    for definition in definitions(conn).await? {
+        if !histories.contains_key(&definition.metric) {
+            let history = metric_history(conn, &definition.metric, program_id, now.date_naive()).await?;
+            if history.is_none() {
+                tracing::warn!(
+                    "Badge {} uses unknown metric {}, skipping",
+                    definition.badge_type,
+                    definition.metric
+                );
+            }
+            histories.insert(definition.metric.clone(), history);
+        }
+        let Some(history) = histories[&definition.metric].clone() else { continue };
+
+        evaluations.push(Evaluation {
+            target: target(conn, &definition, program_id).await?,
+            definition,
+            history,
+        });
+    }
+
+    Ok(evaluations)
+}
+
+/// The definition's metadata with the metric and the values that earned the badge added.
+fn badge_metadata(evaluation: &Evaluation) -> String {
+    let mut metadata = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(
+        &evaluation.definition.metadata,
+    )
+    .unwrap_or_default();
+    metadata.insert("metric".to_string(), evaluation.definition.metric.clone().into());
+    metadata.insert("target".to_string(), evaluation.target.into());
+    metadata.insert("value".to_string(), evaluation.history.current.into());
+
+    serde_json::Value::Object(metadata).to_string()
+}
+
+/// Awards every badge `program_id` has earned and doesn't hold yet, dated when it was
+/// earned. Returns only the newly awarded badges, so calling it again awards nothing.
+pub async fn award_badges(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<Badge>, AppError> {
+    let mut awarded = Vec::new();
+
+    for evaluation in evaluate(conn, program_id, now).await? {
+        let Some(earned_at) = evaluation.history.reached(evaluation.target) else { continue };
+        let definition = &evaluation.definition;
+
+        let badge = sqlx::query_as::<_, Badge>(
+            "INSERT INTO badges (id, program_id, badge_type, title, description, icon, earned_at, metadata)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(program_id, badge_type) DO NOTHING
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(program_id)
+        .bind(&definition.badge_type)
+        .bind(&definition.title)
+        .bind(&definition.description)
+        .bind(&definition.icon)
+        .bind(earned_at)
+        .bind(badge_metadata(&evaluation))
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        awarded.extend(badge);
+    }
+
+    Ok(awarded)
+}
+
+/// Awards earned badges for every program from its existing history.
+pub async fn backfill_badges(
+    conn: &mut SqliteConnection,
+    now: DateTime<Utc>,
+) -> Result<Vec<Badge>, AppError> {
+    let program_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM programs ORDER BY created_at")
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut awarded = Vec::new();
+    for program_id in program_ids {
+        awarded.extend(award_badges(conn, &program_id, now).await?);
+    }
+
+    Ok(awarded)
+}
+
+/// Progress toward every active badge. Held badges count as complete, the rest report
+/// the metric's current value against the target.
+pub async fn badge_progress(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<BadgeProgress>, AppError> {
+    let held: HashSet<String> =
+        sqlx::query_scalar("SELECT badge_type FROM badges WHERE program_id = ?")
+            .bind(program_id)
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .collect();
+
+    let progress = evaluate(conn, program_id, now)
+        .await?
+        .into_iter()
+        .map(|evaluation| {
+            let is_earned = held.contains(&evaluation.definition.badge_type);
+            let progress_percent = if is_earned {
+                100.0
+            } else {
+                (f64::from(evaluation.history.current) / f64::from(evaluation.target) * 100.0)
+                    .min(100.0)
+            };
+
+            BadgeProgress {
+                badge_type: evaluation.definition.badge_type,
+                title: evaluation.definition.title,
+                description: evaluation.definition.description,
+                current_value: evaluation.history.current,
+                target_value: evaluation.target,
+                progress_percent,
+                is_earned,
+            }
+        })
+        .collect();
+
+    Ok(progress)
+}
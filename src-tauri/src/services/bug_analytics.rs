@@ -0,0 +1,296 @@
+use crate::db::models::{
+    BugAnalytics, BugCluster, BugLog, MonthlyTimeToFix, PreventionOutcome, RepeatOffender,
+    TimeToFixTrend,
+};
+use crate::error::AppError;
+use crate::services::similarity::title_similarity;
+use chrono::{DateTime, Datelike, Utc};
+use sqlx::SqliteConnection;
+use std::collections::{BTreeMap, HashSet};
+
+/// Root causes at least this similar (within one category) count as the same mistake.
+pub const ROOT_CAUSE_MATCH: f64 = 0.6;
+/// Occurrences in the current month that make a cluster a repeat offender.
+const REPEAT_THRESHOLD: i32 = 2;
+/// A fix-time change smaller than this, in percent, counts as steady.
+const TREND_TOLERANCE_PERCENT: f64 = 10.0;
+/// Bugs with a logged fix time a category needs before a trend is reported.
+const MIN_TREND_BUGS: usize = 4;
+/// Days a prevention strategy has to be in place before its effect is judged.
+const MIN_OBSERVATION_DAYS: i64 = 14;
+/// Rates are per 30 days, and shorter spans before a strategy count as a full window so a
+/// strategy logged on the first occurrence doesn't start from an inflated rate.
+const RATE_WINDOW_DAYS: f64 = 30.0;
+const SEVERITIES: [&str; 4] = ["low", "medium", "high", "critical"];
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BugOccurrence {
+    #[sqlx(flatten)]
+    pub bug: BugLog,
+    pub program_id: String,
+}
+
+impl BugOccurrence {
+    /// What the bug is matched on: the root cause, or the symptom when none was logged.
+    fn cause(&self) -> &str {
+        if self.bug.root_cause.trim().is_empty() {
+            &self.bug.symptom
+        } else {
+            &self.bug.root_cause
+        }
+    }
+
+    fn logged_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.bug.created_at)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+/// Every bug of `program_id`, or of all programs, oldest first.
+pub async fn occurrences(
+    conn: &mut SqliteConnection,
+    program_id: Option<&str>,
+) -> Result<Vec<BugOccurrence>, AppError> {
+    let bugs = sqlx::query_as::<_, BugOccurrence>(
+        "SELECT bl.*, dp.program_id FROM bug_logs bl
+         JOIN day_attempts da ON da.id = bl.day_attempt_id
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE ? IS NULL OR dp.program_id = ?
+         ORDER BY bl.created_at, bl.id",
+    )
+    .bind(program_id)
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(bugs)
+}
+
+/// `bugs` must be oldest first, as `occurrences` returns them.
+pub fn analyze(program_id: Option<String>, bugs: &[BugOccurrence], now: DateTime<Utc>) -> BugAnalytics {
+    let groups = cluster(bugs);
+
+    let mut clusters: Vec<BugCluster> = groups
+        .iter()
+        .map(|members| summarize_cluster(members, now))
+        .collect();
+    let mut prevention_outcomes: Vec<PreventionOutcome> = groups
+        .iter()
+        .filter_map(|members| prevention_outcome(members, now))
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| b.last_seen.cmp(&a.last_seen))
+    });
+    prevention_outcomes.sort_by(|a, b| b.logged_at.cmp(&a.logged_at));
+
+    let mut repeat_offenders: Vec<RepeatOffender> = clusters
+        .iter()
+        .filter(|cluster| cluster.occurrences_this_month >= REPEAT_THRESHOLD)
+        .map(|cluster| RepeatOffender {
+            category: cluster.category.clone(),
+            representative: cluster.representative.clone(),
+            occurrences_this_month: cluster.occurrences_this_month,
+            lifetime_occurrences: cluster.occurrences,
+            message: format!(
+                "{} {} bug this month",
+                ordinal(cluster.occurrences_this_month),
+                cluster.category.replace('_', " ")
+            ),
+        })
+        .collect();
+    repeat_offenders.sort_by_key(|offender| std::cmp::Reverse(offender.occurrences_this_month));
+
+    BugAnalytics {
+        program_id,
+        total_bugs: bugs.len() as i32,
+        clusters,
+        repeat_offenders,
+        time_to_fix_trends: time_to_fix_trends(bugs),
+        prevention_outcomes,
+    }
+}
+
+/// Leader clustering in logging order: each bug joins the first cluster of its category
+/// whose first bug has a similar enough cause, or starts a new one.
+pub fn cluster(bugs: &[BugOccurrence]) -> Vec<Vec<&BugOccurrence>> {
+    let mut clusters: Vec<Vec<&BugOccurrence>> = Vec::new();
+
+    for bug in bugs {
+        let matching = clusters.iter_mut().find(|members| {
+            let leader = members[0];
+            leader.bug.category == bug.bug.category
+                && title_similarity(leader.cause(), bug.cause()) >= ROOT_CAUSE_MATCH
+        });
+        match matching {
+            Some(members) => members.push(bug),
+            None => clusters.push(vec![bug]),
+        }
+    }
+
+    clusters
+}
+
+fn summarize_cluster(members: &[&BugOccurrence], now: DateTime<Utc>) -> BugCluster {
+    let leader = members[0];
+    let mut attempts = HashSet::new();
+    let mut program_ids: Vec<String> = Vec::new();
+    for member in members {
+        attempts.insert(member.bug.day_attempt_id.as_str());
+        if !program_ids.contains(&member.program_id) {
+            program_ids.push(member.program_id.clone());
+        }
+    }
+
+    let occurrences_this_month = members
+        .iter()
+        .filter_map(|member| member.logged_at())
+        .filter(|at| at.year() == now.year() && at.month() == now.month())
+        .count();
+    let highest_severity = members
+        .iter()
+        .map(|member| member.bug.severity.as_str())
+        .max_by_key(|severity| SEVERITIES.iter().position(|s| s == severity))
+        .unwrap_or("medium")
+        .to_string();
+
+    BugCluster {
+        category: leader.bug.category.clone(),
+        representative: leader.cause().trim().to_string(),
+        bug_ids: members.iter().map(|member| member.bug.id.clone()).collect(),
+        occurrences: members.len() as i32,
+        attempt_count: attempts.len() as i32,
+        program_ids,
+        first_seen: leader.bug.created_at.clone(),
+        last_seen: members[members.len() - 1].bug.created_at.clone(),
+        occurrences_this_month: occurrences_this_month as i32,
+        mean_time_to_fix_minutes: mean_fix_minutes(members.iter().copied()),
+        highest_severity,
+    }
+}
+
+/// Bugs logged without a fix time (0 minutes) are left out of the mean.
+fn mean_fix_minutes<'a>(bugs: impl Iterator<Item = &'a BugOccurrence>) -> Option<f64> {
+    let minutes: Vec<f64> = bugs
+        .filter(|bug| bug.bug.time_to_fix_minutes > 0)
+        .map(|bug| f64::from(bug.bug.time_to_fix_minutes))
+        .collect();
+    (!minutes.is_empty()).then(|| minutes.iter().sum::<f64>() / minutes.len() as f64)
+}
+
+fn time_to_fix_trends(bugs: &[BugOccurrence]) -> Vec<TimeToFixTrend> {
+    let mut by_category: BTreeMap<&str, Vec<&BugOccurrence>> = BTreeMap::new();
+    for bug in bugs.iter().filter(|bug| bug.bug.time_to_fix_minutes > 0) {
+        by_category.entry(bug.bug.category.as_str()).or_default().push(bug);
+    }
+
+    by_category
+        .into_iter()
+        .map(|(category, timed)| {
+            let mut by_month: BTreeMap<String, Vec<&BugOccurrence>> = BTreeMap::new();
+            for bug in &timed {
+                let month = bug.bug.created_at.get(..7).unwrap_or_default().to_string();
+                by_month.entry(month).or_default().push(bug);
+            }
+            let months = by_month
+                .into_iter()
+                .map(|(month, bugs)| MonthlyTimeToFix {
+                    month,
+                    bug_count: bugs.len() as i32,
+                    mean_minutes: mean_fix_minutes(bugs.into_iter()).unwrap_or_default(),
+                })
+                .collect();
+
+            let (earlier, recent) = if timed.len() >= MIN_TREND_BUGS {
+                let (older, newer) = timed.split_at(timed.len() / 2);
+                (
+                    mean_fix_minutes(older.iter().copied()),
+                    mean_fix_minutes(newer.iter().copied()),
+                )
+            } else {
+                (None, None)
+            };
+            let change_percent = match (earlier, recent) {
+                (Some(earlier), Some(recent)) if earlier > 0.0 => {
+                    Some((recent - earlier) / earlier * 100.0)
+                }
+                _ => None,
+            };
+            let direction = match change_percent {
+                None => "insufficient_data",
+                Some(change) if change <= -TREND_TOLERANCE_PERCENT => "improving",
+                Some(change) if change >= TREND_TOLERANCE_PERCENT => "worsening",
+                Some(_) => "steady",
+            };
+
+            TimeToFixTrend {
+                category: category.to_string(),
+                months,
+                earlier_mean_minutes: earlier,
+                recent_mean_minutes: recent,
+                change_percent,
+                direction: direction.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Compares how often the cluster's mistake happened before and after the first prevention
+/// strategy was logged for it.
+fn prevention_outcome(members: &[&BugOccurrence], now: DateTime<Utc>) -> Option<PreventionOutcome> {
+    let position = members
+        .iter()
+        .position(|member| !member.bug.prevention_strategy.trim().is_empty())?;
+    let strategy_bug = members[position];
+    let logged_at = strategy_bug.logged_at()?;
+    let first_seen = members[0].logged_at().unwrap_or(logged_at);
+
+    let occurrences_before = position + 1;
+    let occurrences_after = members.len() - occurrences_before;
+    let days_before = ((logged_at - first_seen).num_hours() as f64 / 24.0).max(RATE_WINDOW_DAYS);
+    let days_observed = (now - logged_at).num_days();
+    let rate_before = occurrences_before as f64 / days_before * RATE_WINDOW_DAYS;
+    let rate_after = occurrences_after as f64 / (days_observed.max(1) as f64) * RATE_WINDOW_DAYS;
+
+    let verdict = if days_observed < MIN_OBSERVATION_DAYS {
+        "too_early"
+    } else if rate_after < rate_before {
+        "reduced"
+    } else {
+        "not_reduced"
+    };
+
+    Some(PreventionOutcome {
+        category: strategy_bug.bug.category.clone(),
+        representative: members[0].cause().trim().to_string(),
+        bug_id: strategy_bug.bug.id.clone(),
+        prevention_strategy: strategy_bug.bug.prevention_strategy.trim().to_string(),
+        logged_at: strategy_bug.bug.created_at.clone(),
+        occurrences_before: occurrences_before as i32,
+        occurrences_after: occurrences_after as i32,
+        rate_before_per_30_days: rate_before,
+        rate_after_per_30_days: rate_after,
+        days_observed,
+        verdict: verdict.to_string(),
+    })
+}
+
+fn ordinal(n: i32) -> String {
+    const WORDS: [&str; 10] = [
+        "First", "Second", "Third", "Fourth", "Fifth", "Sixth", "Seventh", "Eighth", "Ninth",
+        "Tenth",
+    ];
+    if (1..=10).contains(&n) {
+        return WORDS[(n - 1) as usize].to_string();
+    }
+    let suffix = match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
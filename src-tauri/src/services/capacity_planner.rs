@@ -0,0 +1,147 @@
+use crate::db::models::import::{CalendarEntry, GeneratedPlan, ProjectedCalendar, UnplaceableDay};
+use crate::db::models::UserCapacityProfile;
+use crate::error::AppError;
+use crate::services::plan_editor::{merge_days, split_day_parts};
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashSet;
+
+/// Days at or above this complexity count against `max_deep_days_per_week`.
+const DEEP_COMPLEXITY: i64 = 4;
+const MIN_DAILY_MINUTES: i64 = 30;
+/// How far ahead the calendar may pull a lighter day forward when the week's deep quota is used.
+const LOOKAHEAD: usize = 3;
+
+/// Splits days longer than `daily_minutes` into numbered parts, then merges neighbouring days
+/// in the same module while the combined day still fits the budget.
+pub fn fit_to_capacity(plan: &mut GeneratedPlan, daily_minutes: i64) -> Result<(), AppError> {
+    if daily_minutes < MIN_DAILY_MINUTES {
+        return Err(AppError::Validation(format!(
+            "Daily budget must be at least {} minutes",
+            MIN_DAILY_MINUTES
+        )));
+    }
+
+    let mut index = 0;
+    while index < plan.day_plans.len() {
+        let minutes = plan.day_plans[index].estimated_minutes;
+        if minutes <= daily_minutes {
+            index += 1;
+            continue;
+        }
+
+        let parts = ((minutes + daily_minutes - 1) / daily_minutes) as usize;
+        let title = plan.day_plans[index].title.clone();
+        let extra_titles: Vec<String> = (2..=parts)
+            .map(|part| format!("{} (Part {})", title, part))
+            .collect();
+        plan.day_plans[index].title = format!("{} (Part 1)", title);
+        split_day_parts(plan, index, &extra_titles);
+        index += parts;
+    }
+
+    let mut index = 0;
+    while index + 1 < plan.day_plans.len() {
+        let current = &plan.day_plans[index];
+        let next = &plan.day_plans[index + 1];
+        if current.module_index == next.module_index
+            && current.estimated_minutes + next.estimated_minutes <= daily_minutes
+        {
+            merge_days(plan, index + 1, index)?;
+        } else {
+            index += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lays the plan out on the learner's study days from `start`, keeping plan order except when
+/// a deep day would exceed `max_deep_days_per_week`; then a nearby lighter day whose
+/// prerequisites are already scheduled goes first, or the study day is left idle. With no
+/// deep days allowed at all, deep days are left off the calendar and reported as unplaceable.
+pub fn project_calendar(
+    plan: &GeneratedPlan,
+    profile: &UserCapacityProfile,
+    start: NaiveDate,
+) -> ProjectedCalendar {
+    let max_deep = profile.max_deep_days_per_week.max(0).unsigned_abs() as usize;
+
+    let (mut remaining, unplaceable): (Vec<usize>, Vec<usize>) = (0..plan.day_plans.len())
+        .partition(|&i| max_deep > 0 || plan.day_plans[i].complexity_level < DEEP_COMPLEXITY);
+    let unplaceable = unplaceable
+        .into_iter()
+        .map(|day_index| {
+            let day = &plan.day_plans[day_index];
+            UnplaceableDay {
+                day_index,
+                day_number: day.day_number,
+                title: day.title.clone(),
+            }
+        })
+        .collect();
+    let mut entries = Vec::new();
+    let mut idle_study_days = 0;
+    let mut date = start;
+    let mut week = start.iso_week();
+    let mut deep_this_week = 0;
+
+    while !remaining.is_empty() {
+        if date.iso_week() != week {
+            week = date.iso_week();
+            deep_this_week = 0;
+        }
+
+        if profile.is_study_day(date) {
+            let unscheduled: HashSet<i64> = remaining
+                .iter()
+                .map(|&i| plan.day_plans[i].day_number)
+                .collect();
+
+            // The next day in plan order is always allowed through on dependencies, so a
+            // plan with a bad forward reference still finishes.
+            let pick = remaining.iter().take(LOOKAHEAD).enumerate().position(|(position, &i)| {
+                let day = &plan.day_plans[i];
+                let deep_ok = day.complexity_level < DEEP_COMPLEXITY || deep_this_week < max_deep;
+                let deps_met = position == 0
+                    || plan
+                        .dependencies
+                        .iter()
+                        .filter(|dep| dep.day_index == i)
+                        .all(|dep| !unscheduled.contains(&dep.depends_on_day_number));
+                deep_ok && deps_met
+            });
+
+            if let Some(position) = pick {
+                let day_index = remaining.remove(position);
+                let day = &plan.day_plans[day_index];
+                let is_deep = day.complexity_level >= DEEP_COMPLEXITY;
+                if is_deep {
+                    deep_this_week += 1;
+                }
+                entries.push(CalendarEntry {
+                    day_index,
+                    day_number: day.day_number,
+                    title: day.title.clone(),
+                    date: date.format("%Y-%m-%d").to_string(),
+                    estimated_minutes: day.estimated_minutes,
+                    is_deep,
+                });
+            } else {
+                idle_study_days += 1;
+            }
+        }
+
+        date += chrono::Duration::days(1);
+    }
+
+    ProjectedCalendar {
+        start_date: start.format("%Y-%m-%d").to_string(),
+        end_date: entries
+            .last()
+            .map_or_else(|| start.format("%Y-%m-%d").to_string(), |e| e.date.clone()),
+        study_days: entries.len(),
+        idle_study_days,
+        entries,
+        unplaceable,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::db::models::{AttemptChecklistItem, SubmissionCheck};
+use crate::error::AppError;
+use crate::services::settings::setting_or;
+use sqlx::SqliteConnection;
+
+pub const ENFORCEMENT_SETTING: &str = "checklist_enforcement";
+
+pub async fn attempt_checklist(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<AttemptChecklistItem>, AppError> {
+    let items = sqlx::query_as::<_, AttemptChecklistItem>(
+        "SELECT ci.id AS checklist_item_id, ci.label, ci.is_required, ci.order_index,
+            COALESCE(ac.is_completed, 0) AS is_completed, ac.completed_at, ac.artifact_id
+         FROM day_attempts da
+         JOIN checklist_items ci ON ci.day_plan_id = da.day_plan_id
+         LEFT JOIN attempt_checklist ac
+            ON ac.day_attempt_id = da.id AND ac.checklist_item_id = ci.id
+         WHERE da.id = ?
+         ORDER BY ci.order_index",
+    )
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(items)
+}
+
+/// Which required items are still unchecked, and whether the configured enforcement lets
+/// the attempt be submitted anyway.
+pub async fn check_submission(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<SubmissionCheck, AppError> {
+    let enforcement = match setting_or(conn, ENFORCEMENT_SETTING, "warn".to_string())
+        .await?
+        .as_str()
+    {
+        "block" => "block",
+        _ => "warn",
+    };
+
+    let items = attempt_checklist(conn, day_attempt_id).await?;
+    let completed_count = items.iter().filter(|item| item.is_completed).count();
+    let total_count = items.len();
+    let unchecked_required: Vec<AttemptChecklistItem> = items
+        .into_iter()
+        .filter(|item| item.is_required && !item.is_completed)
+        .collect();
+
+    Ok(SubmissionCheck {
+        enforcement: enforcement.to_string(),
+        can_submit: enforcement == "warn" || unchecked_required.is_empty(),
+        unchecked_required,
+        completed_count,
+        total_count,
+    })
+}
+
+/// Errors when the checklist blocks submission.
+pub async fn enforce(conn: &mut SqliteConnection, day_attempt_id: &str) -> Result<(), AppError> {
+    let check = check_submission(conn, day_attempt_id).await?;
+    if check.can_submit {
+        return Ok(());
+    }
+
+    let labels: Vec<&str> = check
+        .unchecked_required
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect();
+    Err(AppError::Validation(format!(
+        "Required checklist items are unchecked: {}",
+        labels.join(", ")
+    )))
+}
@@ -0,0 +1,699 @@
+use crate::db::models::{AnalysisMetrics, AnalyzerFinding, ExerciseEntry};
+use crate::error::AppError;
+use crate::services::exercise_runner::find_program;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Linters on a real project can take a while on a cold cache, but not forever.
+const TOOL_TIMEOUT: Duration = Duration::from_secs(180);
+const MAX_PROJECT_FILES: usize = 500;
+const MAX_SOURCE_BYTES: u64 = 512 * 1024;
+const SKIPPED_DIRS: [&str; 7] = [
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    "venv",
+    "__pycache__",
+    "coverage",
+];
+
+/// Clippy lints that are about speed or allocation rather than style.
+const CLIPPY_PERFORMANCE_LINTS: [&str; 24] = [
+    "box_collection",
+    "boxed_local",
+    "cmp_owned",
+    "expect_fun_call",
+    "extend_with_drain",
+    "format_collect",
+    "inefficient_to_string",
+    "iter_nth",
+    "iter_overeager_cloned",
+    "large_const_arrays",
+    "large_enum_variant",
+    "manual_memcpy",
+    "manual_retain",
+    "manual_str_repeat",
+    "map_entry",
+    "needless_collect",
+    "or_fun_call",
+    "redundant_allocation",
+    "redundant_clone",
+    "single_char_pattern",
+    "slow_vector_initialization",
+    "unnecessary_to_owned",
+    "useless_vec",
+    "vec_init_then_push",
+];
+const ESLINT_PERFORMANCE_RULES: [&str; 1] = ["no-await-in-loop"];
+/// Applied to loose exercise files, which have no ESLint config of their own
+const ESLINT_EXERCISE_RULES: [&str; 8] = [
+    "no-unused-vars: warn",
+    "no-unreachable: error",
+    "no-constant-condition: warn",
+    "no-dupe-keys: error",
+    "eqeqeq: warn",
+    "no-var: warn",
+    "prefer-const: warn",
+    "no-await-in-loop: warn",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    JavaScript,
+    Python,
+}
+
+struct SourceFile {
+    /// Relative to the exercise scratch dir or the project root
+    path: String,
+    language: Language,
+    code: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub findings: Vec<AnalyzerFinding>,
+    pub metrics: AnalysisMetrics,
+}
+
+/// Lints and measures the attempt's exercise entries and, when linked, its project
+/// directory. Linters that aren't installed are skipped and listed in the metrics.
+pub async fn analyze(
+    entries: &[ExerciseEntry],
+    project_path: Option<&Path>,
+) -> Result<Analysis, AppError> {
+    let exercises: Vec<SourceFile> = entries
+        .iter()
+        .filter(|entry| !entry.code.trim().is_empty())
+        .filter_map(|entry| {
+            let (language, extension) = language_for(&entry.language)?;
+            Some(SourceFile {
+                // Underscores, since rustc derives the crate name from the file name
+                path: format!("exercise_{}.{}", entry.order_index + 1, extension),
+                language,
+                code: entry.code.clone(),
+            })
+        })
+        .collect();
+    let project = match project_path {
+        Some(root) => project_sources(root)?,
+        None => Vec::new(),
+    };
+    if exercises.is_empty() && project.is_empty() {
+        return Err(AppError::Validation(
+            "There is no Rust, JavaScript or Python code on this attempt to analyze".to_string(),
+        ));
+    }
+
+    let mut analysis = Analysis::default();
+    for source in exercises.iter().chain(&project) {
+        add_metrics(&mut analysis.metrics, source);
+    }
+
+    if !exercises.is_empty() {
+        let dir = std::env::temp_dir().join(format!("buildops-lint-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let result = lint_exercises(&dir, &exercises, &mut analysis).await;
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!("Failed to clean up {}: {}", dir.display(), e);
+        }
+        result?;
+    }
+    if let Some(root) = project_path.filter(|_| !project.is_empty()) {
+        lint_project(root, &project, &mut analysis).await;
+    }
+
+    analysis.metrics.tools_run.sort();
+    analysis.metrics.tools_run.dedup();
+    analysis.metrics.tools_skipped.dedup();
+    Ok(analysis)
+}
+
+fn language_for(language: &str) -> Option<(Language, &'static str)> {
+    match language.trim().to_lowercase().as_str() {
+        "rust" | "rs" => Some((Language::Rust, "rs")),
+        "javascript" | "js" | "node" | "jsx" => Some((Language::JavaScript, "js")),
+        "typescript" | "ts" | "tsx" => Some((Language::JavaScript, "ts")),
+        "python" | "py" => Some((Language::Python, "py")),
+        _ => None,
+    }
+}
+
+fn language_for_extension(extension: &str) -> Option<Language> {
+    match extension {
+        "rs" => Some(Language::Rust),
+        "js" | "mjs" | "cjs" | "jsx" | "ts" | "tsx" => Some(Language::JavaScript),
+        "py" => Some(Language::Python),
+        _ => None,
+    }
+}
+
+fn project_sources(root: &Path) -> Result<Vec<SourceFile>, AppError> {
+    if !root.is_dir() {
+        return Err(AppError::Validation(format!(
+            "Project directory {} does not exist",
+            root.display()
+        )));
+    }
+
+    let mut sources = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries: Vec<_> = std::fs::read_dir(&dir)?.filter_map(Result::ok).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(file_type) = entry.file_type() else { continue };
+
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    pending.push(path);
+                }
+                continue;
+            }
+
+            let language = path
+                .extension()
+                .and_then(|extension| language_for_extension(&extension.to_string_lossy()));
+            let small_enough = entry.metadata().is_ok_and(|m| m.len() <= MAX_SOURCE_BYTES);
+            let (Some(language), true) = (language, small_enough) else { continue };
+            // Unreadable or non-UTF-8 files are simply left out
+            let Ok(code) = std::fs::read_to_string(&path) else { continue };
+
+            sources.push(SourceFile {
+                path: relative_path(root, &path),
+                language,
+                code,
+            });
+            if sources.len() >= MAX_PROJECT_FILES {
+                return Ok(sources);
+            }
+        }
+    }
+
+    Ok(sources)
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string()
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FileMetrics {
+    code_lines: usize,
+    branches: usize,
+    max_nesting: usize,
+    max_loop_nesting: usize,
+}
+
+fn add_metrics(metrics: &mut AnalysisMetrics, source: &SourceFile) {
+    let file = match source.language {
+        Language::Python => indentation_metrics(&source.code),
+        Language::Rust | Language::JavaScript => brace_metrics(&source.code, source.language),
+    };
+
+    metrics.files_analyzed += 1;
+    metrics.code_lines += file.code_lines;
+    metrics.branches += file.branches;
+    metrics.max_nesting = metrics.max_nesting.max(file.max_nesting);
+    metrics.max_loop_nesting = metrics.max_loop_nesting.max(file.max_loop_nesting);
+}
+
+/// Block depth from braces, skipping comments and string literals. A block opened right
+/// after `for`/`while`/`loop`/`do` counts as a loop.
+fn brace_metrics(code: &str, language: Language) -> FileMetrics {
+    let mut metrics = FileMetrics {
+        code_lines: code
+            .lines()
+            .map(str::trim)
+            .filter(|line| {
+                !line.is_empty()
+                    && !line.starts_with("//")
+                    && !line.starts_with("/*")
+                    && !line.starts_with('*')
+            })
+            .count(),
+        ..FileMetrics::default()
+    };
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut loop_pending = false;
+    let mut word = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            i += 1;
+            continue;
+        }
+        if !word.is_empty() {
+            match word.as_str() {
+                "for" | "while" | "loop" | "do" => {
+                    metrics.branches += 1;
+                    loop_pending = true;
+                }
+                "if" | "match" | "case" | "catch" => metrics.branches += 1,
+                _ => {}
+            }
+            word.clear();
+        }
+
+        match (c, next) {
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            // Single quotes are lifetimes in Rust, so only JS treats them as strings
+            ('"', _) | ('`', _) => i = skip_string(&chars, i),
+            ('\'', _) if language == Language::JavaScript => i = skip_string(&chars, i),
+            ('&', Some('&')) | ('|', Some('|')) => {
+                metrics.branches += 1;
+                i += 1;
+            }
+            ('{', _) => {
+                blocks.push(loop_pending);
+                loop_pending = false;
+                metrics.max_nesting = metrics.max_nesting.max(blocks.len());
+                let loops = blocks.iter().filter(|is_loop| **is_loop).count();
+                metrics.max_loop_nesting = metrics.max_loop_nesting.max(loops);
+            }
+            ('}', _) => {
+                blocks.pop();
+            }
+            (';', _) => loop_pending = false,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    metrics
+}
+
+/// Index of the closing quote of the string starting at `start`.
+fn skip_string(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() && chars[i] != quote {
+        if chars[i] == '\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Block depth from indentation under lines ending in `:`.
+fn indentation_metrics(code: &str) -> FileMetrics {
+    let mut metrics = FileMetrics::default();
+    // (indent of the opening line, whether it opened a loop)
+    let mut blocks: Vec<(usize, bool)> = Vec::new();
+
+    for line in code.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        metrics.code_lines += 1;
+
+        let indent: usize = line
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        while blocks.last().is_some_and(|(opened_at, _)| *opened_at >= indent) {
+            blocks.pop();
+        }
+        metrics.max_nesting = metrics.max_nesting.max(blocks.len());
+        let loops = blocks.iter().filter(|(_, is_loop)| *is_loop).count();
+        metrics.max_loop_nesting = metrics.max_loop_nesting.max(loops);
+
+        let words: Vec<&str> = trimmed
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|w| !w.is_empty())
+            .collect();
+        metrics.branches += words
+            .iter()
+            .filter(|w| matches!(**w, "if" | "elif" | "for" | "while" | "except" | "and" | "or" | "case"))
+            .count();
+
+        if trimmed.ends_with(':') {
+            let first = words.iter().find(|w| **w != "async").copied();
+            blocks.push((indent, matches!(first, Some("for" | "while"))));
+        }
+    }
+
+    metrics
+}
+
+struct ToolOutput {
+    status: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+async fn run_tool(program: &Path, args: &[String], dir: &Path) -> Result<ToolOutput, String> {
+    let child = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    match tokio::time::timeout(TOOL_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) => Ok(ToolOutput {
+            status: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("timed out after {}s", TOOL_TIMEOUT.as_secs())),
+    }
+}
+
+/// First line of a tool's stderr, to say why a run produced nothing usable.
+fn failure_reason(output: &ToolOutput) -> String {
+    output
+        .stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("failed without output")
+        .to_string()
+}
+
+fn record_skip(analysis: &mut Analysis, tool: &str, reason: &str) {
+    analysis.metrics.tools_skipped.push(format!("{}: {}", tool, reason));
+}
+
+async fn lint_exercises(
+    dir: &Path,
+    exercises: &[SourceFile],
+    analysis: &mut Analysis,
+) -> Result<(), AppError> {
+    for source in exercises {
+        std::fs::write(dir.join(&source.path), &source.code)?;
+    }
+    let files = |language: Language| -> Vec<String> {
+        exercises
+            .iter()
+            .filter(|source| source.language == language)
+            .map(|source| source.path.clone())
+            .collect()
+    };
+
+    // Each Rust exercise is its own crate, so clippy-driver checks them one at a time
+    let rust_files = files(Language::Rust);
+    if !rust_files.is_empty() {
+        match find_program("clippy-driver") {
+            Ok(program) => {
+                for file in &rust_files {
+                    let args: Vec<String> = [
+                        "--edition=2021",
+                        "--error-format=json",
+                        "--emit=metadata",
+                        "--crate-type=bin",
+                        "--out-dir",
+                        "out",
+                        file,
+                    ]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                    match run_tool(&program, &args, dir).await {
+                        Ok(output) => {
+                            analysis.findings.extend(parse_rustc_diagnostics(&output.stderr, dir));
+                            analysis.metrics.tools_run.push("clippy".to_string());
+                        }
+                        Err(reason) => record_skip(analysis, "clippy", &reason),
+                    }
+                }
+            }
+            Err(_) => record_skip(analysis, "clippy", "clippy-driver is not installed"),
+        }
+    }
+
+    let js_files = files(Language::JavaScript);
+    if !js_files.is_empty() {
+        match find_program("eslint") {
+            Ok(program) => {
+                let mut args = vec![
+                    "--format".to_string(),
+                    "json".to_string(),
+                    "--no-config-lookup".to_string(),
+                ];
+                for rule in ESLINT_EXERCISE_RULES {
+                    args.push("--rule".to_string());
+                    args.push(rule.to_string());
+                }
+                args.extend(js_files.iter().filter(|f| f.ends_with(".js")).cloned());
+                run_eslint(&program, &args, dir, analysis).await;
+            }
+            Err(_) => record_skip(analysis, "eslint", "not installed"),
+        }
+    }
+
+    if !files(Language::Python).is_empty() {
+        run_ruff(dir, true, analysis).await;
+    }
+
+    Ok(())
+}
+
+async fn lint_project(root: &Path, sources: &[SourceFile], analysis: &mut Analysis) {
+    let has = |language: Language| sources.iter().any(|source| source.language == language);
+
+    if root.join("Cargo.toml").is_file() {
+        match find_program("cargo") {
+            Ok(program) => {
+                let args: Vec<String> = ["clippy", "--quiet", "--message-format=json"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                match run_tool(&program, &args, root).await {
+                    Ok(output) => {
+                        let findings = parse_cargo_messages(&output.stdout, root);
+                        if findings.is_empty() && output.status != Some(0) {
+                            record_skip(analysis, "cargo clippy", &failure_reason(&output));
+                        } else {
+                            analysis.findings.extend(findings);
+                            analysis.metrics.tools_run.push("clippy".to_string());
+                        }
+                    }
+                    Err(reason) => record_skip(analysis, "cargo clippy", &reason),
+                }
+            }
+            Err(_) => record_skip(analysis, "cargo clippy", "cargo is not installed"),
+        }
+    }
+
+    // ESLint needs the project's own config, which lives next to package.json
+    if has(Language::JavaScript) && root.join("package.json").is_file() {
+        let local = root.join("node_modules").join(".bin").join("eslint");
+        let program = if local.is_file() {
+            Ok(local)
+        } else {
+            find_program("eslint")
+        };
+        match program {
+            Ok(program) => {
+                let args = vec!["--format".to_string(), "json".to_string(), ".".to_string()];
+                run_eslint(&program, &args, root, analysis).await;
+            }
+            Err(_) => record_skip(analysis, "eslint", "not installed"),
+        }
+    }
+
+    if has(Language::Python) {
+        run_ruff(root, false, analysis).await;
+    }
+}
+
+async fn run_eslint(program: &Path, args: &[String], dir: &Path, analysis: &mut Analysis) {
+    match run_tool(program, args, dir).await {
+        // 0 is clean, 1 means findings; anything else is a crash or a config problem
+        Ok(output) if matches!(output.status, Some(0 | 1)) => {
+            analysis.findings.extend(parse_eslint(&output.stdout, dir));
+            analysis.metrics.tools_run.push("eslint".to_string());
+        }
+        Ok(output) => record_skip(analysis, "eslint", &failure_reason(&output)),
+        Err(reason) => record_skip(analysis, "eslint", &reason),
+    }
+}
+
+/// `isolated` ignores any config, for loose exercise files.
+async fn run_ruff(dir: &Path, isolated: bool, analysis: &mut Analysis) {
+    let Ok(program) = find_program("ruff") else {
+        record_skip(analysis, "ruff", "not installed");
+        return;
+    };
+
+    let mut args: Vec<String> = [
+        "check",
+        "--output-format",
+        "json",
+        "--exit-zero",
+        "--no-cache",
+        "--extend-select",
+        "PERF",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    if isolated {
+        args.push("--isolated".to_string());
+    }
+    args.push(".".to_string());
+
+    match run_tool(&program, &args, dir).await {
+        Ok(output) if output.status == Some(0) => {
+            analysis.findings.extend(parse_ruff(&output.stdout, dir));
+            analysis.metrics.tools_run.push("ruff".to_string());
+        }
+        Ok(output) => record_skip(analysis, "ruff", &failure_reason(&output)),
+        Err(reason) => record_skip(analysis, "ruff", &reason),
+    }
+}
+
+/// One JSON diagnostic per line, as printed by rustc/clippy-driver with `--error-format=json`.
+fn parse_rustc_diagnostics(output: &str, dir: &Path) -> Vec<AnalyzerFinding> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|diagnostic| rustc_finding(&diagnostic, dir))
+        .collect()
+}
+
+/// `cargo --message-format=json` wraps each compiler diagnostic in a `compiler-message`.
+fn parse_cargo_messages(output: &str, dir: &Path) -> Vec<AnalyzerFinding> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-message")
+        .filter_map(|message| rustc_finding(&message["message"], dir))
+        .collect()
+}
+
+fn rustc_finding(diagnostic: &Value, dir: &Path) -> Option<AnalyzerFinding> {
+    let severity = match diagnostic["level"].as_str()? {
+        "error" => "error",
+        "warning" => "warning",
+        _ => return None,
+    };
+    // Summaries like "2 warnings emitted" have no span
+    let span = diagnostic["spans"]
+        .as_array()?
+        .iter()
+        .find(|span| span["is_primary"].as_bool() == Some(true))?;
+    let rule = diagnostic["code"]["code"].as_str().map(str::to_string);
+    let is_performance = rule.as_deref().is_some_and(|rule| {
+        rule.strip_prefix("clippy::")
+            .is_some_and(|lint| CLIPPY_PERFORMANCE_LINTS.contains(&lint))
+    });
+
+    Some(AnalyzerFinding {
+        tool: "clippy".to_string(),
+        category: category(is_performance),
+        severity: severity.to_string(),
+        rule,
+        message: diagnostic["message"].as_str().unwrap_or_default().to_string(),
+        file: span["file_name"]
+            .as_str()
+            .map(|file| relative_path(dir, Path::new(file))),
+        line: span["line_start"].as_u64().and_then(|line| u32::try_from(line).ok()),
+    })
+}
+
+fn parse_eslint(output: &str, dir: &Path) -> Vec<AnalyzerFinding> {
+    let Ok(Value::Array(files)) = serde_json::from_str::<Value>(output) else {
+        return Vec::new();
+    };
+
+    files
+        .iter()
+        .flat_map(|file| {
+            let path = file["filePath"]
+                .as_str()
+                .map(|path| relative_path(dir, &PathBuf::from(path)));
+            file["messages"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |message| {
+                    let rule = message["ruleId"].as_str().map(str::to_string);
+                    AnalyzerFinding {
+                        tool: "eslint".to_string(),
+                        category: category(rule.as_deref().is_some_and(|rule| {
+                            ESLINT_PERFORMANCE_RULES.contains(&rule)
+                        })),
+                        // A message without a rule is a parse error
+                        severity: match (message["severity"].as_u64(), &rule) {
+                            (Some(2), _) | (_, None) => "error",
+                            _ => "warning",
+                        }
+                        .to_string(),
+                        rule,
+                        message: message["message"].as_str().unwrap_or_default().to_string(),
+                        file: path.clone(),
+                        line: message["line"].as_u64().and_then(|line| u32::try_from(line).ok()),
+                    }
+                })
+        })
+        .collect()
+}
+
+fn parse_ruff(output: &str, dir: &Path) -> Vec<AnalyzerFinding> {
+    let Ok(Value::Array(violations)) = serde_json::from_str::<Value>(output) else {
+        return Vec::new();
+    };
+
+    violations
+        .iter()
+        .map(|violation| {
+            let rule = violation["code"].as_str().map(str::to_string);
+            AnalyzerFinding {
+                tool: "ruff".to_string(),
+                category: category(rule.as_deref().is_some_and(|rule| rule.starts_with("PERF"))),
+                // Syntax errors come without a rule code
+                severity: if rule.is_some() { "warning" } else { "error" }.to_string(),
+                rule,
+                message: violation["message"].as_str().unwrap_or_default().to_string(),
+                file: violation["filename"]
+                    .as_str()
+                    .map(|file| relative_path(dir, Path::new(file))),
+                line: violation["location"]["row"]
+                    .as_u64()
+                    .and_then(|line| u32::try_from(line).ok()),
+            }
+        })
+        .collect()
+}
+
+fn category(is_performance: bool) -> String {
+    if is_performance { "performance" } else { "quality" }.to_string()
+}
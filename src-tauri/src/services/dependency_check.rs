@@ -0,0 +1,60 @@
+use crate::db::models::{DayDependency, DependencyStatus};
+use crate::error::AppError;
+use sqlx::SqliteConnection;
+
+/// How each dependency of the day stands against the best submitted attempt of the day it
+/// depends on. Only prerequisites can be unmet; recommended and related edges never block.
+pub async fn dependency_statuses(
+    conn: &mut SqliteConnection,
+    day_plan_id: &str,
+) -> Result<Vec<DependencyStatus>, AppError> {
+    let dependencies = sqlx::query_as::<_, DayDependency>(
+        "SELECT * FROM day_dependencies WHERE day_plan_id = ?",
+    )
+    .bind(day_plan_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut statuses = Vec::new();
+
+    for dep in dependencies {
+        let day_info: (String, i32) =
+            sqlx::query_as("SELECT title, day_number FROM day_plans WHERE id = ?")
+                .bind(&dep.depends_on_day_plan_id)
+                .fetch_one(&mut *conn)
+                .await?;
+
+        let best_attempt: Option<(i32, String)> = sqlx::query_as(
+            "SELECT total_score, status FROM day_attempts
+             WHERE day_plan_id = ? AND is_draft = 0
+             ORDER BY total_score DESC LIMIT 1",
+        )
+        .bind(&dep.depends_on_day_plan_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let (best_score, best_status) = match best_attempt {
+            Some((score, status)) => (Some(score), Some(status)),
+            None => (None, None),
+        };
+
+        let is_met = match dep.dependency_type.as_str() {
+            "prerequisite" => best_score.is_some_and(|s| s >= dep.minimum_score),
+            _ => true,
+        };
+
+        statuses.push(DependencyStatus {
+            dependency_id: dep.id,
+            depends_on_day_plan_id: dep.depends_on_day_plan_id,
+            depends_on_title: day_info.0,
+            depends_on_day_number: day_info.1,
+            dependency_type: dep.dependency_type,
+            minimum_score: dep.minimum_score,
+            is_met,
+            best_score,
+            best_attempt_status: best_status,
+        });
+    }
+
+    Ok(statuses)
+}
@@ -0,0 +1,302 @@
+use crate::error::AppError;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+/// Environment passed through to toolchains; everything else is cleared. The rustup and
+/// pyenv variables let their shims find the real binaries.
+const PASSTHROUGH_ENV: [&str; 8] = [
+    "PATH",
+    "HOME",
+    "LANG",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_HOME",
+    "PYENV_ROOT",
+    "PYENV_VERSION",
+];
+/// Everything is allowed except network access.
+const MACOS_PROFILE: &str = "(version 1)(allow default)(deny network*)";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub cpu_seconds: u64,
+    /// Virtual memory cap; `None` for runtimes that reserve huge address ranges up front
+    /// (V8) and are capped through their own flags instead
+    pub memory_kb: Option<u64>,
+    pub wall_clock: Duration,
+}
+
+const RUN_LIMITS: Limits = Limits {
+    cpu_seconds: 10,
+    memory_kb: Some(512 * 1024),
+    wall_clock: Duration::from_secs(15),
+};
+/// rustc and its LLVM threads reserve far more address space than a run needs
+const COMPILE_LIMITS: Limits = Limits {
+    cpu_seconds: 60,
+    memory_kb: Some(4 * 1024 * 1024),
+    wall_clock: Duration::from_secs(120),
+};
+
+#[derive(Debug, Clone)]
+pub struct RunOutput {
+    pub stage: &'static str,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Sandbox {
+    /// Linux: an unprivileged user + network namespace with no interfaces but loopback
+    Unshare,
+    /// macOS: Seatbelt profile denying network
+    SandboxExec,
+}
+
+struct Toolchain {
+    source_file: &'static str,
+    compile: Option<(&'static str, Vec<String>)>,
+    run: (String, Vec<String>),
+    run_limits: Limits,
+}
+
+/// Builds (if needed) and runs the code in a fresh temp directory, without network and
+/// under CPU, memory and wall-clock limits. Refuses when no sandbox is available rather
+/// than running unconfined.
+pub async fn run(language: &str, code: &str) -> Result<RunOutput, AppError> {
+    let toolchain = toolchain_for(language)?;
+    let sandbox = detect_sandbox().await?;
+
+    let dir = std::env::temp_dir().join(format!("buildops-run-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let result = run_in(&dir, sandbox, &toolchain, code).await;
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        tracing::warn!("Failed to clean up {}: {}", dir.display(), e);
+    }
+
+    result
+}
+
+async fn run_in(
+    dir: &Path,
+    sandbox: Sandbox,
+    toolchain: &Toolchain,
+    code: &str,
+) -> Result<RunOutput, AppError> {
+    std::fs::write(dir.join(toolchain.source_file), code)?;
+
+    if let Some((program, args)) = &toolchain.compile {
+        let program = find_program(program)?;
+        let output = execute(dir, sandbox, COMPILE_LIMITS, &program, args, "compile").await?;
+        if output.timed_out || output.exit_code != Some(0) {
+            return Ok(output);
+        }
+    }
+
+    let (program, args) = &toolchain.run;
+    let program = if program.starts_with("./") {
+        dir.join(program)
+    } else {
+        find_program(program)?
+    };
+    execute(dir, sandbox, toolchain.run_limits, &program, args, "run").await
+}
+
+fn toolchain_for(language: &str) -> Result<Toolchain, AppError> {
+    let args = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    match language.trim().to_lowercase().as_str() {
+        "rust" | "rs" => Ok(Toolchain {
+            source_file: "main.rs",
+            compile: Some(("rustc", args(&["--edition", "2021", "main.rs", "-o", "main"]))),
+            run: ("./main".to_string(), Vec::new()),
+            run_limits: RUN_LIMITS,
+        }),
+        "javascript" | "js" | "node" => Ok(Toolchain {
+            source_file: "main.js",
+            compile: None,
+            run: ("node".to_string(), args(&["--max-old-space-size=512", "main.js"])),
+            run_limits: Limits {
+                memory_kb: None,
+                ..RUN_LIMITS
+            },
+        }),
+        "python" | "py" => Ok(Toolchain {
+            source_file: "main.py",
+            compile: None,
+            run: ("python3".to_string(), args(&["main.py"])),
+            run_limits: RUN_LIMITS,
+        }),
+        other => Err(AppError::Validation(format!(
+            "There is no runner for '{}' exercises (supported: rust, javascript, python)",
+            other
+        ))),
+    }
+}
+
+pub fn find_program(name: &str) -> Result<PathBuf, AppError> {
+    std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(name))
+                .find(|candidate| candidate.is_file())
+        })
+        .ok_or_else(|| AppError::Validation(format!("{} is not installed or not on PATH", name)))
+}
+
+async fn detect_sandbox() -> Result<Sandbox, AppError> {
+    if cfg!(target_os = "linux") {
+        let probe = Command::new("unshare")
+            .args(["-rn", "true"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        if probe.is_ok_and(|status| status.success()) {
+            return Ok(Sandbox::Unshare);
+        }
+    } else if cfg!(target_os = "macos") && Path::new("/usr/bin/sandbox-exec").is_file() {
+        return Ok(Sandbox::SandboxExec);
+    }
+
+    Err(AppError::Validation(
+        "Exercises only run inside a network sandbox (unprivileged unshare on Linux, \
+         sandbox-exec on macOS), and none is available here"
+            .to_string(),
+    ))
+}
+
+async fn execute(
+    dir: &Path,
+    sandbox: Sandbox,
+    limits: Limits,
+    program: &Path,
+    args: &[String],
+    stage: &'static str,
+) -> Result<RunOutput, AppError> {
+    // ulimit applies to the shell, which then execs the program in its place
+    let mut script = format!("ulimit -t {}", limits.cpu_seconds);
+    if let Some(memory_kb) = limits.memory_kb {
+        script.push_str(&format!("; ulimit -v {}", memory_kb));
+    }
+    script.push_str("; exec \"$0\" \"$@\"");
+
+    let mut command = match sandbox {
+        Sandbox::Unshare => {
+            let mut command = Command::new("unshare");
+            command.arg("-rn");
+            command
+        }
+        Sandbox::SandboxExec => {
+            let mut command = Command::new("/usr/bin/sandbox-exec");
+            command.args(["-p", MACOS_PROFILE]);
+            command
+        }
+    };
+    command
+        .args(["sh", "-c", &script])
+        .arg(program)
+        .args(args)
+        .current_dir(dir)
+        .env_clear()
+        .envs(PASSTHROUGH_ENV.iter().filter_map(|key| {
+            std::env::var_os(key).map(|value| (*key, value))
+        }))
+        .env("TMPDIR", dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let started = Instant::now();
+    let mut child = command.spawn()?;
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let finished = tokio::time::timeout(limits.wall_clock, async {
+        let (stdout_read, stderr_read) = tokio::join!(
+            read_capped(stdout_pipe, &mut stdout),
+            read_capped(stderr_pipe, &mut stderr)
+        );
+        stdout_read?;
+        stderr_read?;
+        child.wait().await
+    })
+    .await;
+    // On timeout the output read so far is kept, since it usually shows where the program hung
+    let (exit_code, timed_out) = match finished {
+        Ok(status) => (status?.code(), false),
+        Err(_) => {
+            child.kill().await?;
+            (None, true)
+        }
+    };
+
+    Ok(RunOutput {
+        stage,
+        exit_code,
+        timed_out,
+        stdout: truncate_output(&stdout),
+        stderr: truncate_output(&stderr),
+        duration_ms: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+    })
+}
+
+/// Reads until the pipe closes or one byte past `MAX_OUTPUT_BYTES`, which is enough to know
+/// the output was cut off. The pipe is closed early then, so a chatty program gets EPIPE
+/// instead of filling memory.
+async fn read_capped<R: AsyncRead + Unpin>(
+    pipe: Option<R>,
+    buffer: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    let Some(mut pipe) = pipe else {
+        return Ok(());
+    };
+
+    let mut chunk = [0u8; 8192];
+    while buffer.len() <= MAX_OUTPUT_BYTES {
+        let read = pipe.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        let room = MAX_OUTPUT_BYTES + 1 - buffer.len();
+        buffer.extend_from_slice(&chunk[..read.min(room)]);
+    }
+
+    Ok(())
+}
+
+fn truncate_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= MAX_OUTPUT_BYTES {
+        return text.into_owned();
+    }
+
+    let mut end = MAX_OUTPUT_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n[output truncated]", &text[..end])
+}
+
+/// Compares line by line, ignoring trailing whitespace and trailing blank lines.
+pub fn matches_expected(expected: &str, stdout: &str) -> bool {
+    let lines = |text: &str| {
+        let mut lines: Vec<String> = text.lines().map(|l| l.trim_end().to_string()).collect();
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+        lines
+    };
+
+    lines(expected) == lines(stdout)
+}
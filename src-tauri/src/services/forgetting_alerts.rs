@@ -0,0 +1,194 @@
+use crate::db::models::ForgettingAlert;
+use crate::error::AppError;
+use crate::services::scheduler::{retrievability, SchedulerConfig};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+
+/// Below these recall estimates a quick review is no longer enough.
+const REBUILD_BELOW: f64 = 0.6;
+const REPLAY_BELOW: f64 = 0.3;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ReviewItem {
+    id: String,
+    day_plan_id: String,
+    day_title: String,
+    day_number: i32,
+    concept_tag_id: String,
+    concept_name: String,
+    last_review_date: String,
+    next_review_date: String,
+    interval_days: i32,
+    stability: Option<f64>,
+    difficulty: Option<f64>,
+}
+
+/// Items of the program whose predicted recall is below the desired retention, ranked by
+/// risk times importance.
+pub async fn forgetting_alerts(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<ForgettingAlert>, AppError> {
+    let items = sqlx::query_as::<_, ReviewItem>(
+        "SELECT
+            sr.id, sr.day_plan_id, dp.title as day_title, dp.day_number,
+            sr.concept_tag_id, ct.name as concept_name, sr.last_review_date,
+            sr.next_review_date, sr.interval_days, sr.stability, sr.difficulty
+         FROM spaced_repetition sr
+         JOIN day_plans dp ON sr.day_plan_id = dp.id
+         JOIN concept_tags ct ON sr.concept_tag_id = ct.id
+         WHERE dp.program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let edges: Vec<(String, String)> = sqlx::query_as(
+        "SELECT dd.depends_on_day_plan_id, dd.day_plan_id
+         FROM day_dependencies dd
+         JOIN day_plans dp ON dp.id = dd.day_plan_id
+         WHERE dp.program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+    for (depends_on, day) in edges {
+        dependents.entry(depends_on).or_default().push(day);
+    }
+
+    let passed: HashSet<String> = sqlx::query_scalar(
+        "SELECT DISTINCT da.day_plan_id FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE dp.program_id = ? AND da.status IN ('passed', 'mastery')",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+
+    let today = now.date_naive();
+    let mut downstream_cache: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut alerts = Vec::new();
+
+    for item in items {
+        let elapsed_days = NaiveDate::parse_from_str(
+            item.last_review_date.get(..10).unwrap_or_default(),
+            "%Y-%m-%d",
+        )
+        .map_or(0, |last| (today - last).num_days().max(0));
+        // Same memory state the review queue ranks by, so both agree on an item's recall
+        let memory = config.memory_state(item.stability, item.difficulty, item.interval_days);
+        let recall = retrievability(elapsed_days as f64, memory.stability);
+        if recall >= config.desired_retention {
+            continue;
+        }
+
+        let downstream = downstream_cache
+            .entry(item.day_plan_id.clone())
+            .or_insert_with(|| downstream_days(&dependents, &item.day_plan_id));
+        let dependent_day_count = downstream.len() as i32;
+        let upcoming_dependent_count =
+            downstream.iter().filter(|day| !passed.contains(*day)).count() as i32;
+
+        let risk = 1.0 - recall;
+        let importance = 1.0 + f64::from(dependent_day_count);
+        let (suggested_action, message) = suggestion(
+            &item.concept_name,
+            item.day_number,
+            recall,
+            upcoming_dependent_count,
+        );
+
+        alerts.push(ForgettingAlert {
+            id: item.id,
+            day_plan_id: item.day_plan_id,
+            day_title: item.day_title,
+            day_number: item.day_number,
+            concept_tag_id: item.concept_tag_id,
+            concept_name: item.concept_name,
+            last_review_date: item.last_review_date,
+            next_review_date: item.next_review_date,
+            elapsed_days: elapsed_days as i32,
+            predicted_recall: recall,
+            risk,
+            importance,
+            dependent_day_count,
+            upcoming_dependent_count,
+            priority: risk * importance,
+            suggested_action: suggested_action.to_string(),
+            message,
+        });
+    }
+
+    alerts.sort_by(|a, b| {
+        b.priority
+            .total_cmp(&a.priority)
+            .then_with(|| a.predicted_recall.total_cmp(&b.predicted_recall))
+    });
+
+    Ok(alerts)
+}
+
+/// Every day that depends on `day_plan_id`, directly or through other days.
+fn downstream_days(dependents: &HashMap<String, Vec<String>>, day_plan_id: &str) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![day_plan_id.to_string()];
+
+    while let Some(day) = stack.pop() {
+        for dependent in dependents.get(&day).into_iter().flatten() {
+            if dependent != day_plan_id && seen.insert(dependent.clone()) {
+                stack.push(dependent.clone());
+            }
+        }
+    }
+
+    seen
+}
+
+fn suggestion(
+    concept: &str,
+    day_number: i32,
+    recall: f64,
+    upcoming_dependents: i32,
+) -> (&'static str, String) {
+    let percent = (recall * 100.0).round();
+    let stakes = match upcoming_dependents {
+        0 => String::new(),
+        1 => " before starting the day that builds on it".to_string(),
+        n => format!(" before starting the {} days that build on it", n),
+    };
+
+    if recall < REPLAY_BELOW {
+        (
+            "replay_day",
+            format!(
+                "Recall of {} is down to about {}%. Replay Day {}{}.",
+                concept, percent, day_number, stakes
+            ),
+        )
+    } else if recall < REBUILD_BELOW {
+        (
+            "memory_rebuild",
+            format!(
+                "Recall of {} is about {}%. Rebuild Day {} from memory{}.",
+                concept, percent, day_number, stakes
+            ),
+        )
+    } else {
+        (
+            "review",
+            format!(
+                "Recall of {} is slipping to about {}%. Review it{}.",
+                concept, percent, stakes
+            ),
+        )
+    }
+}
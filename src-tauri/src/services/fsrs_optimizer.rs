@@ -0,0 +1,204 @@
+use crate::db::models::ReviewLog;
+use crate::services::scheduler::{next_memory_state, retrievability, AGAIN, MemoryState};
+
+/// Recall predictions the log needs before fitted parameters are worth trusting over the
+/// defaults.
+pub const MIN_REVIEWS: usize = 50;
+/// Parameter ranges, as FSRS-4.5 clamps them during training.
+const BOUNDS: [(f64, f64); 17] = [
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (1.0, 10.0),
+    (0.1, 5.0),
+    (0.1, 5.0),
+    (0.0, 0.5),
+    (0.0, 3.0),
+    (0.1, 0.8),
+    (0.01, 2.5),
+    (0.5, 5.0),
+    (0.01, 0.2),
+    (0.01, 0.9),
+    (0.01, 2.0),
+    (0.0, 1.0),
+    (1.0, 6.0),
+];
+/// Pattern search starts with steps of this share of each parameter's range and stops once
+/// every step is below `MIN_STEP` of it.
+const INITIAL_STEP: f64 = 0.05;
+const MIN_STEP: f64 = 1e-4;
+const MAX_ROUNDS: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReviewEvent {
+    pub elapsed_days: f64,
+    pub rating: i32,
+}
+
+/// Splits the log into per-item review sequences. `logs` must be ordered by item, then by
+/// time. Items whose log doesn't start at their first review (reviewed before the log
+/// existed) are left out, since their starting state is unknown.
+pub fn histories(logs: &[ReviewLog]) -> Vec<Vec<ReviewEvent>> {
+    let mut histories: Vec<Vec<ReviewEvent>> = Vec::new();
+    let mut current_item: Option<(&str, &str)> = None;
+
+    for log in logs {
+        let item = (log.day_plan_id.as_str(), log.concept_tag_id.as_str());
+        if current_item != Some(item) {
+            current_item = Some(item);
+            histories.push(Vec::new());
+        }
+        histories.last_mut().unwrap().push(ReviewEvent {
+            elapsed_days: f64::from(log.elapsed_days.max(0)),
+            rating: log.rating,
+        });
+    }
+
+    histories.retain(|events| events.first().is_some_and(|first| first.elapsed_days == 0.0));
+    histories
+}
+
+/// Mean log loss of the recall predicted before each review against whether it was
+/// recalled, with the number of predictions. Same-day repeats aren't predictions and are
+/// only replayed.
+pub fn log_loss(parameters: &[f64; 17], histories: &[Vec<ReviewEvent>]) -> (f64, usize) {
+    let mut total = 0.0;
+    let mut count = 0;
+
+    for events in histories {
+        let mut state: Option<MemoryState> = None;
+        for event in events {
+            if let Some(previous) = state {
+                if event.elapsed_days > 0.0 {
+                    let predicted = retrievability(event.elapsed_days, previous.stability)
+                        .clamp(1e-6, 1.0 - 1e-6);
+                    total -= if event.rating > AGAIN {
+                        predicted.ln()
+                    } else {
+                        (1.0 - predicted).ln()
+                    };
+                    count += 1;
+                }
+            }
+            state = Some(next_memory_state(parameters, state, event.elapsed_days, event.rating));
+        }
+    }
+
+    if count == 0 {
+        (0.0, 0)
+    } else {
+        (total / count as f64, count)
+    }
+}
+
+/// Fits the parameters to `histories` by pattern search from `start`: each parameter is
+/// nudged up and down in turn, improvements are kept, and steps are halved once a full round
+/// finds none.
+pub fn fit(start: &[f64; 17], histories: &[Vec<ReviewEvent>]) -> [f64; 17] {
+    let mut best = *start;
+    for (value, (low, high)) in best.iter_mut().zip(BOUNDS) {
+        *value = value.clamp(low, high);
+    }
+    let mut best_loss = log_loss(&best, histories).0;
+    let mut steps = BOUNDS.map(|(low, high)| (high - low) * INITIAL_STEP);
+
+    for _ in 0..MAX_ROUNDS {
+        let mut improved = false;
+        for i in 0..best.len() {
+            for direction in [1.0, -1.0] {
+                let mut candidate = best;
+                candidate[i] = (candidate[i] + direction * steps[i]).clamp(BOUNDS[i].0, BOUNDS[i].1);
+                if candidate[i] == best[i] || !initial_stabilities_ordered(&candidate) {
+                    continue;
+                }
+                let loss = log_loss(&candidate, histories).0;
+                if loss < best_loss {
+                    best = candidate;
+                    best_loss = loss;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+
+        if !improved {
+            let converged = steps
+                .iter()
+                .zip(BOUNDS)
+                .all(|(step, (low, high))| *step < (high - low) * MIN_STEP);
+            if converged {
+                break;
+            }
+            for step in &mut steps {
+                *step /= 2.0;
+            }
+        }
+    }
+
+    best
+}
+
+/// A better first rating must never start an item with a lower stability.
+fn initial_stabilities_ordered(parameters: &[f64; 17]) -> bool {
+    parameters[..4].windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::scheduler::{DEFAULT_PARAMETERS, GOOD};
+
+    /// Reviews simulated under `truth`, with recall decided by a fixed pseudo-random
+    /// sequence so the log is the same on every run.
+    fn synthetic_histories(truth: &[f64; 17]) -> Vec<Vec<ReviewEvent>> {
+        let mut seed: u64 = 42;
+        let mut uniform = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        (0..80)
+            .map(|_| {
+                let mut events = vec![ReviewEvent { elapsed_days: 0.0, rating: GOOD }];
+                let mut state = next_memory_state(truth, None, 0.0, GOOD);
+                for elapsed_days in [1.0, 3.0, 7.0, 14.0, 30.0] {
+                    let recalled = uniform() < retrievability(elapsed_days, state.stability);
+                    let rating = if recalled { GOOD } else { AGAIN };
+                    events.push(ReviewEvent { elapsed_days, rating });
+                    state = next_memory_state(truth, Some(state), elapsed_days, rating);
+                }
+                events
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_lowers_log_loss() {
+        // A learner who forgets much faster than the defaults assume
+        let mut truth = DEFAULT_PARAMETERS;
+        for stability in &mut truth[..4] {
+            *stability *= 0.2;
+        }
+        let histories = synthetic_histories(&truth);
+
+        let (before, count) = log_loss(&DEFAULT_PARAMETERS, &histories);
+        let fitted = fit(&DEFAULT_PARAMETERS, &histories);
+        let (after, _) = log_loss(&fitted, &histories);
+
+        assert!(count >= MIN_REVIEWS);
+        assert!(after < before, "{} is not below {}", after, before);
+        assert!(initial_stabilities_ordered(&fitted));
+        assert!(fitted.iter().zip(BOUNDS).all(|(value, (low, high))| (low..=high).contains(value)));
+    }
+
+    #[test]
+    fn same_day_repeats_are_not_predictions() {
+        let histories = vec![vec![
+            ReviewEvent { elapsed_days: 0.0, rating: GOOD },
+            ReviewEvent { elapsed_days: 0.0, rating: GOOD },
+            ReviewEvent { elapsed_days: 2.0, rating: AGAIN },
+        ]];
+        assert_eq!(log_loss(&DEFAULT_PARAMETERS, &histories).1, 1);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::db::models::{GitCommit, GitFileChange, GitTreeEntry};
+use crate::error::AppError;
+use crate::services::exercise_runner::find_program;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+const GIT_TIMEOUT: Duration = Duration::from_secs(30);
+/// A pause longer than this between commits starts a new work session.
+const MAX_SESSION_GAP_MINUTES: i64 = 120;
+/// Work before a session's first commit, which the commit history can't see.
+const SESSION_LEAD_IN_MINUTES: i64 = 30;
+const MAX_TREE_ENTRIES: usize = 2000;
+const MAX_SNAPSHOT_FILES: usize = 50;
+const MAX_SNAPSHOT_BYTES: usize = 256 * 1024;
+
+/// Field and record separators for `git log` output, which can't appear in commit messages.
+const FIELD: char = '\u{1f}';
+const RECORD: char = '\u{1e}';
+
+#[derive(Debug, Clone)]
+pub struct GitCapture {
+    pub repository_path: String,
+    pub branch: String,
+    pub head_commit: Option<String>,
+    /// Oldest first
+    pub commits: Vec<GitCommit>,
+    pub tree: Vec<GitTreeEntry>,
+    pub files_changed: usize,
+    pub insertions: i64,
+    pub deletions: i64,
+    pub estimated_minutes: i64,
+    /// Contents of the files changed in the window, as of the last commit
+    pub code_snapshot: String,
+}
+
+/// Reads the commits made between `since` and `until` in the repository containing `path`.
+pub async fn capture(
+    path: &Path,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<GitCapture, AppError> {
+    let git = find_program("git")?;
+    let root = git_output(&git, path, &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| {
+            AppError::Validation(format!("{} is not inside a git repository", path.display()))
+        })?;
+    let root = PathBuf::from(root.trim());
+
+    let branch = git_output(&git, &root, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .await
+        .map(|branch| branch.trim().to_string())
+        .unwrap_or_default();
+
+    let log = git_output(
+        &git,
+        &root,
+        &[
+            "log",
+            "--no-merges",
+            "--numstat",
+            &format!("--since={}", since.to_rfc3339()),
+            &format!("--until={}", until.to_rfc3339()),
+            &format!("--format={}%H{}%an{}%cI{}%B{}", RECORD, FIELD, FIELD, FIELD, FIELD),
+        ],
+    )
+    .await
+    // A repository without any commits yet has no HEAD to log
+    .unwrap_or_default();
+
+    // git filters on its own date parsing; the window is re-checked on the parsed times
+    let mut commits: Vec<GitCommit> = parse_log(&log)
+        .into_iter()
+        .filter(|commit| {
+            DateTime::parse_from_rfc3339(&commit.committed_at)
+                .is_ok_and(|at| at >= since && at <= until)
+        })
+        .collect();
+    commits.reverse();
+
+    let head_commit = commits.last().map(|commit| commit.hash.clone());
+    let tree = match &head_commit {
+        Some(hash) => {
+            let listing = git_output(&git, &root, &["ls-tree", "-r", "-l", hash]).await?;
+            parse_tree(&listing)
+        }
+        None => Vec::new(),
+    };
+
+    let mut changed: Vec<&str> = Vec::new();
+    let mut seen = HashSet::new();
+    for file in commits.iter().flat_map(|commit| &commit.files) {
+        if seen.insert(file.path.as_str()) {
+            changed.push(file.path.as_str());
+        }
+    }
+    let insertions = commits
+        .iter()
+        .flat_map(|commit| &commit.files)
+        .filter_map(|file| file.insertions)
+        .map(i64::from)
+        .sum();
+    let deletions = commits
+        .iter()
+        .flat_map(|commit| &commit.files)
+        .filter_map(|file| file.deletions)
+        .map(i64::from)
+        .sum();
+
+    let code_snapshot = match &head_commit {
+        Some(hash) => snapshot_files(&git, &root, hash, &changed, &tree).await,
+        None => String::new(),
+    };
+
+    Ok(GitCapture {
+        repository_path: root.to_string_lossy().to_string(),
+        branch,
+        head_commit,
+        files_changed: changed.len(),
+        insertions,
+        deletions,
+        estimated_minutes: estimate_minutes(&commits),
+        tree,
+        commits,
+        code_snapshot,
+    })
+}
+
+async fn git_output(git: &Path, dir: &Path, args: &[&str]) -> Result<String, AppError> {
+    let child = Command::new(git)
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let output = tokio::time::timeout(GIT_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| AppError::Validation(format!("git {} timed out", args[0])))??;
+    if !output.status.success() {
+        return Err(AppError::Validation(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Records start with RECORD and hold hash, author, date and message separated by FIELD;
+/// the `--numstat` lines follow the last separator.
+fn parse_log(log: &str) -> Vec<GitCommit> {
+    log.split(RECORD)
+        .filter_map(|record| {
+            let mut fields = record.splitn(5, FIELD);
+            let hash = fields.next()?.trim().to_string();
+            let author = fields.next()?.to_string();
+            let committed_at = fields.next()?.trim().to_string();
+            let message = fields.next()?.trim().to_string();
+            let files = fields.next().unwrap_or_default().lines().filter_map(parse_numstat).collect();
+            (!hash.is_empty()).then_some(GitCommit {
+                hash,
+                author,
+                committed_at,
+                message,
+                files,
+            })
+        })
+        .collect()
+}
+
+/// `insertions<TAB>deletions<TAB>path`, with `-` counts for binary files.
+fn parse_numstat(line: &str) -> Option<GitFileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let insertions = parts.next()?.trim();
+    let deletions = parts.next()?.trim();
+    let path = parts.next()?.trim();
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(GitFileChange {
+        path: path.to_string(),
+        insertions: insertions.parse().ok(),
+        deletions: deletions.parse().ok(),
+    })
+}
+
+/// `ls-tree -r -l` lines: `mode type hash size<TAB>path`, size `-` for submodules.
+fn parse_tree(listing: &str) -> Vec<GitTreeEntry> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let (meta, path) = line.split_once('\t')?;
+            let meta: Vec<&str> = meta.split_whitespace().collect();
+            Some(GitTreeEntry {
+                path: path.to_string(),
+                object_hash: meta.get(2)?.to_string(),
+                size_bytes: meta.get(3).and_then(|size| size.parse().ok()),
+            })
+        })
+        .take(MAX_TREE_ENTRIES)
+        .collect()
+}
+
+/// Session-gap estimate: time between commits counts while the gaps stay short, and each
+/// session gets a fixed lead-in for the work before its first commit.
+pub fn estimate_minutes(commits: &[GitCommit]) -> i64 {
+    let mut times: Vec<DateTime<Utc>> = commits
+        .iter()
+        .filter_map(|commit| DateTime::parse_from_rfc3339(&commit.committed_at).ok())
+        .map(|at| at.with_timezone(&Utc))
+        .collect();
+    times.sort();
+
+    let Some(first) = times.first() else { return 0 };
+    let mut minutes = SESSION_LEAD_IN_MINUTES;
+    let mut previous = *first;
+    for at in times.iter().skip(1) {
+        let gap = (*at - previous).num_minutes();
+        minutes += if gap <= MAX_SESSION_GAP_MINUTES {
+            gap
+        } else {
+            SESSION_LEAD_IN_MINUTES
+        };
+        previous = *at;
+    }
+
+    minutes
+}
+
+/// Concatenates the changed files that still exist at `hash`, skipping binaries, until the
+/// size cap is hit.
+async fn snapshot_files(
+    git: &Path,
+    root: &Path,
+    hash: &str,
+    changed: &[&str],
+    tree: &[GitTreeEntry],
+) -> String {
+    let mut snapshot = String::new();
+    for path in changed.iter().take(MAX_SNAPSHOT_FILES) {
+        let Some(entry) = tree.iter().find(|entry| entry.path == *path) else { continue };
+        if entry
+            .size_bytes
+            .is_some_and(|size| size as usize + snapshot.len() > MAX_SNAPSHOT_BYTES)
+        {
+            continue;
+        }
+        let Ok(content) = git_output(git, root, &["show", &format!("{}:{}", hash, path)]).await
+        else {
+            continue;
+        };
+        if content.contains('\0') {
+            continue;
+        }
+
+        if !snapshot.is_empty() {
+            snapshot.push('\n');
+        }
+        snapshot.push_str(&format!("// ===== {} =====\n", path));
+        snapshot.push_str(&content);
+    }
+
+    snapshot
+}
@@ -1,7 +1,10 @@
-use crate::db::models::import::GeneratedPlan;
+use crate::db::models::import::{
+    ChecklistItemDraft, ConceptTagDraft, DayPlanDraft, DependencyDraft, GeneratedPlan,
+    QuizQuestionDraft,
+};
 use crate::db::models::Program;
 use crate::error::AppError;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Pool, Sqlite, SqliteConnection};
 use uuid::Uuid;
 
 pub async fn apply_import(
@@ -54,147 +57,44 @@ pub async fn apply_import(
 
     let mut day_plan_ids = Vec::new();
     for day in &plan.day_plans {
-        let day_id = Uuid::new_v4().to_string();
         let module_id = &module_ids[day.module_index];
-
-        let focus_blocks = serde_json::json!([
-            {"session_type": "learn", "minutes": day.estimated_minutes / 3},
-            {"session_type": "build", "minutes": day.estimated_minutes / 2},
-            {"session_type": "review", "minutes": day.estimated_minutes / 6}
-        ])
-        .to_string();
-
-        sqlx::query(
-            "INSERT INTO day_plans (id, program_id, module_id, title, day_number, version, status, \
-             syntax_targets, implementation_brief, files_to_create, success_criteria, stretch_challenge, \
-             notes, estimated_minutes, memory_rebuild_minutes, min_minutes, recommended_minutes, \
-             deep_minutes, complexity_level, focus_blocks) \
-             VALUES (?, ?, ?, ?, ?, 1, 'published', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&day_id)
-        .bind(&program_id)
-        .bind(module_id)
-        .bind(&day.title)
-        .bind(day.day_number)
-        .bind(&day.syntax_targets)
-        .bind(&day.implementation_brief)
-        .bind(&day.files_to_create)
-        .bind(&day.success_criteria)
-        .bind(&day.stretch_challenge)
-        .bind(&day.notes)
-        .bind(day.estimated_minutes)
-        .bind(day.memory_rebuild_minutes)
-        .bind(day.min_minutes)
-        .bind(day.recommended_minutes)
-        .bind(day.deep_minutes)
-        .bind(day.complexity_level)
-        .bind(&focus_blocks)
-        .execute(&mut *tx)
-        .await?;
+        let day_id = insert_day_plan(&mut tx, &program_id, module_id, day).await?;
         day_plan_ids.push(day_id);
     }
 
     for item in &plan.checklist_items {
-        let item_id = Uuid::new_v4().to_string();
-        let day_id = &day_plan_ids[item.day_index];
-
-        sqlx::query(
-            "INSERT INTO checklist_items (id, day_plan_id, label, is_required, order_index) VALUES (?, ?, ?, ?, ?)",
-        )
-        .bind(&item_id)
-        .bind(day_id)
-        .bind(&item.label)
-        .bind(if item.is_required { 1 } else { 0 })
-        .bind(item.order_index)
-        .execute(&mut *tx)
-        .await?;
+        insert_checklist_item(&mut tx, &day_plan_ids[item.day_index], item).await?;
     }
 
     for question in &plan.quiz_questions {
-        let question_id = Uuid::new_v4().to_string();
-        let day_id = &day_plan_ids[question.day_index];
-
-        let options_json = serde_json::to_string(&question.options).unwrap_or_default();
-
-        sqlx::query(
-            "INSERT INTO quiz_questions (id, day_plan_id, question_text, question_type, \
-             correct_answer, options_json, points, time_limit_seconds, order_index) \
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0)",
-        )
-        .bind(&question_id)
-        .bind(day_id)
-        .bind(&question.question_text)
-        .bind(&question.question_type)
-        .bind(&question.correct_answer)
-        .bind(&options_json)
-        .bind(question.points)
-        .bind(question.time_limit_seconds)
-        .execute(&mut *tx)
-        .await?;
+        insert_quiz_question(&mut tx, &day_plan_ids[question.day_index], question).await?;
     }
 
     let mut tag_id_map = std::collections::HashMap::new();
     for tag in &plan.concept_tags {
-        let existing: Option<(String,)> = sqlx::query_as(
-            "SELECT id FROM concept_tags WHERE name = ? AND domain = ?",
-        )
-        .bind(&tag.name)
-        .bind(&tag.domain)
-        .fetch_optional(&mut *tx)
-        .await?;
-
-        let tag_id = if let Some((id,)) = existing {
-            id
-        } else {
-            let id = Uuid::new_v4().to_string();
-            sqlx::query(
-                "INSERT INTO concept_tags (id, name, domain, color) VALUES (?, ?, ?, '#6366F1')",
-            )
-            .bind(&id)
-            .bind(&tag.name)
-            .bind(&tag.domain)
-            .execute(&mut *tx)
-            .await?;
-            id
-        };
-
+        let tag_id = upsert_concept_tag(&mut tx, tag).await?;
         tag_id_map.insert(tag.name.clone(), tag_id);
     }
 
     for (day_index, tag_name) in &plan.tag_assignments {
         if let Some(tag_id) = tag_id_map.get(tag_name) {
-            let day_id = &day_plan_ids[*day_index];
-            sqlx::query(
-                "INSERT INTO day_plan_tags (day_plan_id, tag_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
-            )
-            .bind(day_id)
-            .bind(tag_id)
-            .execute(&mut *tx)
-            .await?;
+            assign_tag(&mut tx, &day_plan_ids[*day_index], tag_id).await?;
         }
     }
 
     for dep in &plan.dependencies {
-        let day_id = &day_plan_ids[dep.day_index];
-        
-        let depends_on_day = plan.day_plans
+        let depends_on_index = plan
+            .day_plans
             .iter()
-            .find(|d| d.day_number == dep.depends_on_day_number);
+            .position(|d| d.day_number == dep.depends_on_day_number);
 
-        if let Some(depends_on) = depends_on_day {
-            let depends_on_id = &day_plan_ids[plan.day_plans.iter().position(|d| d.day_number == depends_on.day_number).unwrap()];
-
-            let dep_id = Uuid::new_v4().to_string();
-            sqlx::query(
-                "INSERT INTO day_dependencies (id, day_plan_id, depends_on_day_plan_id, dependency_type, minimum_score) \
-                 VALUES (?, ?, ?, ?, ?)",
+        if let Some(depends_on_index) = depends_on_index {
+            insert_dependency(
+                &mut tx,
+                &day_plan_ids[dep.day_index],
+                &day_plan_ids[depends_on_index],
+                dep,
             )
-            .bind(&dep_id)
-            .bind(day_id)
-            .bind(depends_on_id)
-            .bind(&dep.dependency_type)
-            .bind(dep.minimum_score)
-            .execute(&mut *tx)
             .await?;
         }
     }
@@ -208,3 +108,161 @@ pub async fn apply_import(
 
     Ok(program)
 }
+
+pub(crate) async fn insert_day_plan(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    module_id: &str,
+    day: &DayPlanDraft,
+) -> Result<String, AppError> {
+    let day_id = Uuid::new_v4().to_string();
+
+    let focus_blocks = serde_json::json!([
+        {"session_type": "learn", "minutes": day.estimated_minutes / 3},
+        {"session_type": "build", "minutes": day.estimated_minutes / 2},
+        {"session_type": "review", "minutes": day.estimated_minutes / 6}
+    ])
+    .to_string();
+
+    sqlx::query(
+        "INSERT INTO day_plans (id, program_id, module_id, title, day_number, version, status, \
+         syntax_targets, implementation_brief, files_to_create, success_criteria, stretch_challenge, \
+         notes, estimated_minutes, memory_rebuild_minutes, min_minutes, recommended_minutes, \
+         deep_minutes, complexity_level, focus_blocks) \
+         VALUES (?, ?, ?, ?, ?, 1, 'published', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&day_id)
+    .bind(program_id)
+    .bind(module_id)
+    .bind(&day.title)
+    .bind(day.day_number)
+    .bind(&day.syntax_targets)
+    .bind(&day.implementation_brief)
+    .bind(&day.files_to_create)
+    .bind(&day.success_criteria)
+    .bind(&day.stretch_challenge)
+    .bind(&day.notes)
+    .bind(day.estimated_minutes)
+    .bind(day.memory_rebuild_minutes)
+    .bind(day.min_minutes)
+    .bind(day.recommended_minutes)
+    .bind(day.deep_minutes)
+    .bind(day.complexity_level)
+    .bind(&focus_blocks)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(day_id)
+}
+
+pub(crate) async fn insert_checklist_item(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    item: &ChecklistItemDraft,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO checklist_items (id, day_plan_id, label, is_required, order_index) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(day_id)
+    .bind(&item.label)
+    .bind(i32::from(item.is_required))
+    .bind(item.order_index)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) async fn insert_quiz_question(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    question: &QuizQuestionDraft,
+) -> Result<String, AppError> {
+    let id = Uuid::new_v4().to_string();
+    let options_json = serde_json::to_string(&question.options).unwrap_or_default();
+
+    sqlx::query(
+        "INSERT INTO quiz_questions (id, day_plan_id, question_text, question_type, \
+         correct_answer, options, points, time_limit_seconds, order_index) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0)",
+    )
+    .bind(&id)
+    .bind(day_id)
+    .bind(&question.question_text)
+    .bind(&question.question_type)
+    .bind(&question.correct_answer)
+    .bind(&options_json)
+    .bind(question.points)
+    .bind(question.time_limit_seconds)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+pub(crate) async fn upsert_concept_tag(
+    conn: &mut SqliteConnection,
+    tag: &ConceptTagDraft,
+) -> Result<String, AppError> {
+    let existing: Option<(String,)> = sqlx::query_as(
+        "SELECT id FROM concept_tags WHERE name = ? AND domain = ?",
+    )
+    .bind(&tag.name)
+    .bind(&tag.domain)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some((id,)) = existing {
+        return Ok(id);
+    }
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO concept_tags (id, name, domain, color) VALUES (?, ?, ?, '#6366F1')",
+    )
+    .bind(&id)
+    .bind(&tag.name)
+    .bind(&tag.domain)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+pub(crate) async fn assign_tag(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    tag_id: &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO day_plan_tags (day_plan_id, concept_tag_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
+    )
+    .bind(day_id)
+    .bind(tag_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(crate) async fn insert_dependency(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    depends_on_id: &str,
+    dep: &DependencyDraft,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO day_dependencies (id, day_plan_id, depends_on_day_plan_id, dependency_type, minimum_score) \
+         VALUES (?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(day_id)
+    .bind(depends_on_id)
+    .bind(&dep.dependency_type)
+    .bind(dep.minimum_score)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
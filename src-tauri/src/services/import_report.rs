@@ -0,0 +1,176 @@
+use crate::db::models::import::{
+    ComplexityBucket, DayIssue, DayPlanDraft, DuplicateTitle, ExtractedSection, GeneratedPlan,
+    ImportQualityReport, SectionCoverage,
+};
+use crate::services::plan_generator::calculate_complexity;
+use crate::services::similarity::{normalize, title_similarity, token_coverage};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Deeper headings are usually sub-points ("Example", "Summary") that never get a day of their own.
+const MAX_SECTION_LEVEL: u8 = 2;
+const TITLE_MATCH_THRESHOLD: f64 = 0.5;
+const TOKEN_MATCH_THRESHOLD: f64 = 0.6;
+const ESTIMATE_TOLERANCE: f64 = 0.2;
+
+pub fn build_report(
+    job_id: &str,
+    plan: &GeneratedPlan,
+    sections: &[ExtractedSection],
+) -> ImportQualityReport {
+    let day_texts: Vec<String> = plan
+        .day_plans
+        .iter()
+        .map(|day| format!("{} {} {}", day.title, day.syntax_targets, day.implementation_brief))
+        .collect();
+
+    let mut covered_sections = Vec::new();
+    let mut orphaned_sections = Vec::new();
+    for section in sections
+        .iter()
+        .filter(|s| s.level <= MAX_SECTION_LEVEL && !s.heading.trim().is_empty())
+    {
+        let mut covered_by_days = Vec::new();
+        let mut best_match = 0.0f64;
+        for (day, text) in plan.day_plans.iter().zip(&day_texts) {
+            let title_score = title_similarity(&section.heading, &day.title);
+            let token_score = token_coverage(&section.heading, text);
+            best_match = best_match.max(title_score).max(token_score);
+            if title_score >= TITLE_MATCH_THRESHOLD || token_score >= TOKEN_MATCH_THRESHOLD {
+                covered_by_days.push(day.day_number);
+            }
+        }
+
+        let coverage = SectionCoverage {
+            heading: section.heading.clone(),
+            page_number: section.page_number,
+            covered_by_days,
+            best_match,
+        };
+        if coverage.covered_by_days.is_empty() {
+            orphaned_sections.push(coverage);
+        } else {
+            covered_sections.push(coverage);
+        }
+    }
+
+    let with_quiz: HashSet<usize> = plan.quiz_questions.iter().map(|q| q.day_index).collect();
+    let with_checklist: HashSet<usize> = plan.checklist_items.iter().map(|i| i.day_index).collect();
+    let day_issue = |(day_index, day): (usize, &DayPlanDraft)| DayIssue {
+        day_index,
+        day_number: day.day_number,
+        title: day.title.clone(),
+    };
+    let days_without_quiz: Vec<DayIssue> = plan
+        .day_plans
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !with_quiz.contains(i))
+        .map(day_issue)
+        .collect();
+    let days_without_checklist: Vec<DayIssue> = plan
+        .day_plans
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !with_checklist.contains(i))
+        .map(day_issue)
+        .collect();
+
+    let generated_days = plan.day_plans.len();
+    let estimated_total_days = plan.program.estimated_total_days;
+    let total_estimated_minutes: i64 = plan.day_plans.iter().map(|d| d.estimated_minutes).sum();
+    let estimate_clash = if estimated_total_days > 0 {
+        let drift = (generated_days as f64 - estimated_total_days as f64).abs()
+            / estimated_total_days as f64;
+        (drift > ESTIMATE_TOLERANCE).then(|| {
+            format!(
+                "Plan has {} days but the document was estimated at {} days",
+                generated_days, estimated_total_days
+            )
+        })
+    } else {
+        None
+    };
+
+    let mut titles: HashMap<String, (String, Vec<i64>)> = HashMap::new();
+    for day in &plan.day_plans {
+        titles
+            .entry(normalize(&day.title))
+            .or_insert_with(|| (day.title.clone(), Vec::new()))
+            .1
+            .push(day.day_number);
+    }
+    let mut duplicate_titles: Vec<DuplicateTitle> = titles
+        .into_values()
+        .filter(|(_, day_numbers)| day_numbers.len() > 1)
+        .map(|(title, day_numbers)| DuplicateTitle { title, day_numbers })
+        .collect();
+    duplicate_titles.sort_by_key(|d| d.day_numbers[0]);
+
+    let mut histogram: BTreeMap<i64, usize> = (1..=5).map(|level| (level, 0)).collect();
+    for day in &plan.day_plans {
+        let level = calculate_complexity(
+            &day.syntax_targets,
+            &day.implementation_brief,
+            day.estimated_minutes,
+        );
+        *histogram.entry(level).or_insert(0) += 1;
+    }
+    let complexity_distribution: Vec<ComplexityBucket> = histogram
+        .into_iter()
+        .map(|(level, count)| ComplexityBucket { level, count })
+        .collect();
+
+    let score = quality_score(
+        covered_sections.len(),
+        orphaned_sections.len(),
+        generated_days,
+        days_without_quiz.len(),
+        days_without_checklist.len(),
+        estimate_clash.is_some(),
+        duplicate_titles.len(),
+    );
+
+    ImportQualityReport {
+        job_id: job_id.to_string(),
+        score,
+        total_sections: covered_sections.len() + orphaned_sections.len(),
+        covered_sections,
+        orphaned_sections,
+        days_without_quiz,
+        days_without_checklist,
+        estimated_total_days,
+        generated_days,
+        total_estimated_minutes,
+        estimate_clash,
+        duplicate_titles,
+        complexity_distribution,
+        validation_warnings: plan.validation_warnings.clone(),
+    }
+}
+
+fn quality_score(
+    covered: usize,
+    orphaned: usize,
+    days: usize,
+    without_quiz: usize,
+    without_checklist: usize,
+    estimate_clash: bool,
+    duplicates: usize,
+) -> i64 {
+    if days == 0 {
+        return 0;
+    }
+
+    let mut score = 100.0;
+    if covered + orphaned > 0 {
+        score -= 40.0 * orphaned as f64 / (covered + orphaned) as f64;
+    }
+    score -= 15.0 * without_quiz as f64 / days as f64;
+    score -= 15.0 * without_checklist as f64 / days as f64;
+    if estimate_clash {
+        score -= 10.0;
+    }
+    score -= (5 * duplicates).min(20) as f64;
+
+    (score.round() as i64).clamp(0, 100)
+}
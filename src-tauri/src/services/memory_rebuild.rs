@@ -0,0 +1,170 @@
+use crate::db::models::SyntaxTargetCoverage;
+use crate::error::AppError;
+use crate::services::similarity::{code_tokens, sequence_similarity};
+use sqlx::SqliteConnection;
+use std::collections::HashSet;
+
+/// Token similarity a rebuild needs to be suggested as a pass.
+pub const SIMILARITY_PASS: f64 = 0.7;
+/// Share of syntax targets a rebuild needs to use to be suggested as a pass.
+pub const COVERAGE_PASS: f64 = 0.8;
+const MAX_MISSING_IDENTIFIERS: usize = 30;
+/// Words that describe a syntax target rather than appear in the code.
+const PROSE_WORDS: [&str; 22] = [
+    "operator", "operators", "expression", "expressions", "generic", "generics", "object",
+    "objects", "pattern", "patterns", "syntax", "keyword", "keywords", "statement",
+    "statements", "method", "methods", "function", "functions", "usage", "the", "and",
+];
+
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub token_similarity: f64,
+    pub target_coverage: Option<f64>,
+    pub targets: Vec<SyntaxTargetCoverage>,
+    pub missing_identifiers: Vec<String>,
+    pub suggested_pass: bool,
+    pub evidence: String,
+}
+
+/// The day's best submitted attempt with a code snapshot other than the one rebuilding it,
+/// as `(attempt id, code)`.
+pub async fn reference_attempt(
+    conn: &mut SqliteConnection,
+    day_plan_id: &str,
+    day_attempt_id: &str,
+) -> Result<Option<(String, String)>, AppError> {
+    let reference = sqlx::query_as(
+        "SELECT id, code_snapshot FROM day_attempts
+         WHERE day_plan_id = ? AND id != ? AND TRIM(code_snapshot) != ''
+            AND status IN ('passed', 'mastery', 'blocked')
+         ORDER BY total_score DESC, attempt_number DESC
+         LIMIT 1",
+    )
+    .bind(day_plan_id)
+    .bind(day_attempt_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(reference)
+}
+
+pub fn evaluate(reference: &str, rebuilt: &str, syntax_targets: &str, overtime: bool) -> Evaluation {
+    let reference_tokens = code_tokens(reference);
+    let rebuilt_tokens = code_tokens(rebuilt);
+    let token_similarity = sequence_similarity(&reference_tokens, &rebuilt_tokens);
+
+    let rebuilt_words: HashSet<String> = rebuilt_tokens
+        .iter()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .map(|token| token.to_lowercase())
+        .collect();
+    let compact_rebuild = rebuilt_tokens.concat();
+
+    let targets: Vec<SyntaxTargetCoverage> = parse_targets(syntax_targets)
+        .into_iter()
+        .map(|target| SyntaxTargetCoverage {
+            covered: target_covered(&target, &rebuilt_words, &compact_rebuild),
+            target,
+        })
+        .collect();
+    let target_coverage = (!targets.is_empty()).then(|| {
+        targets.iter().filter(|t| t.covered).count() as f64 / targets.len() as f64
+    });
+
+    let mut seen = HashSet::new();
+    let missing_identifiers: Vec<String> = reference_tokens
+        .iter()
+        .filter(|token| is_identifier(token))
+        .filter(|token| !rebuilt_words.contains(&token.to_lowercase()))
+        .filter(|token| seen.insert(token.as_str()))
+        .take(MAX_MISSING_IDENTIFIERS)
+        .cloned()
+        .collect();
+
+    let similar = token_similarity >= SIMILARITY_PASS;
+    let covered = target_coverage.map_or(true, |coverage| coverage >= COVERAGE_PASS);
+
+    let mut evidence = vec![format!(
+        "Token similarity {:.0}% (needs {:.0}%).",
+        token_similarity * 100.0,
+        SIMILARITY_PASS * 100.0
+    )];
+    match target_coverage {
+        Some(coverage) => evidence.push(format!(
+            "{} of {} syntax targets used, {:.0}% (needs {:.0}%).",
+            targets.iter().filter(|t| t.covered).count(),
+            targets.len(),
+            coverage * 100.0,
+            COVERAGE_PASS * 100.0
+        )),
+        None => evidence.push("The day lists no syntax targets.".to_string()),
+    }
+    if !missing_identifiers.is_empty() {
+        evidence.push(format!(
+            "{} reference identifier(s) never appear in the rebuild.",
+            missing_identifiers.len()
+        ));
+    }
+    if overtime {
+        evidence.push("Submitted after the time limit.".to_string());
+    }
+
+    Evaluation {
+        token_similarity,
+        target_coverage,
+        targets,
+        missing_identifiers,
+        suggested_pass: similar && covered && !overtime,
+        evidence: evidence.join(" "),
+    }
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.chars().count() >= 2
+        && token
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+}
+
+/// Syntax targets are free text: one target per comma, semicolon or line, with list
+/// bullets stripped.
+pub fn parse_targets(syntax_targets: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    syntax_targets
+        .split([',', ';', '\n'])
+        .map(|target| {
+            target
+                .trim()
+                .trim_start_matches(['-', '*', '•'])
+                .trim()
+                .to_string()
+        })
+        .filter(|target| !target.is_empty() && seen.insert(target.to_lowercase()))
+        .collect()
+}
+
+/// Targets mix code and prose ("Option<T> generics", "? operator"), so descriptive words and
+/// single-letter type parameters are ignored and half of the remaining words showing up in
+/// the rebuild is enough. Targets with no words left must have their symbols appear verbatim.
+fn target_covered(target: &str, words_in_rebuild: &HashSet<String>, compact_rebuild: &str) -> bool {
+    let tokens = code_tokens(target);
+    let words: Vec<String> = tokens
+        .iter()
+        .filter(|token| token.chars().count() >= 2 && token.chars().any(char::is_alphanumeric))
+        .map(|token| token.to_lowercase())
+        .filter(|word| !PROSE_WORDS.contains(&word.as_str()))
+        .collect();
+
+    if words.is_empty() {
+        let symbols: Vec<&String> = tokens
+            .iter()
+            .filter(|token| !token.chars().any(char::is_alphanumeric))
+            .collect();
+        return !symbols.is_empty()
+            && symbols.iter().all(|symbol| compact_rebuild.contains(symbol.as_str()));
+    }
+
+    let found = words.iter().filter(|word| words_in_rebuild.contains(*word)).count();
+    2 * found >= words.len()
+}
@@ -1,5 +1,34 @@
-pub mod pdf_extractor;
-pub mod document_chunker;
-pub mod ai_analyzer;
+// Phase 12 - PDF import: these need reqwest, tiktoken-rs, pdf-extract and pulldown-cmark
+// pub mod pdf_extractor;
+// pub mod document_chunker;
+// pub mod ai_analyzer;
 pub mod plan_generator;
 pub mod import_applier;
+pub mod plan_merger;
+pub mod plan_editor;
+pub mod import_report;
+pub mod capacity_planner;
+pub mod scoring;
+pub mod quiz_grader;
+pub mod checklist_gate;
+pub mod settings;
+pub mod attempt_state;
+pub mod dependency_check;
+pub mod snapshots;
+pub mod attempt_compare;
+pub mod similarity;
+pub mod similar_bugs;
+pub mod exercise_runner;
+pub mod code_analyzer;
+pub mod score_suggestions;
+pub mod git_evidence;
+pub mod memory_rebuild;
+pub mod artifact_store;
+pub mod bug_analytics;
+pub mod scheduler;
+pub mod fsrs_optimizer;
+pub mod review_queue;
+pub mod quiz_cards;
+pub mod forgetting_alerts;
+pub mod badges;
+pub mod streaks;
@@ -0,0 +1,521 @@
+use crate::db::models::import::{
+    ConceptTagDraft, DependencyDraft, GeneratedPlan, InferredDependency, PlanEditOperation,
+};
+use crate::error::AppError;
+use crate::services::capacity_planner;
+use crate::services::plan_generator::{infer_dependencies, normalize_tag_name, validate_dependencies};
+use std::collections::{HashMap, HashSet};
+
+/// Applies `operation` to a copy of `plan`. The edit is rejected if it leaves dangling
+/// indices or introduces dependency problems the plan didn't already have.
+pub fn apply_edit(
+    plan: &GeneratedPlan,
+    operation: &PlanEditOperation,
+) -> Result<GeneratedPlan, AppError> {
+    validate_plan(plan)?;
+    let warnings_before = dependency_warnings(plan).len();
+
+    let mut edited = plan.clone();
+    match operation {
+        PlanEditOperation::RenameModule { module_index, title } => {
+            rename_module(&mut edited, *module_index, title)?;
+        }
+        PlanEditOperation::MoveDay { day_index, module_index } => {
+            move_day(&mut edited, *day_index, *module_index)?;
+        }
+        PlanEditOperation::MergeDays { day_index, into_day_index } => {
+            merge_days(&mut edited, *day_index, *into_day_index)?;
+        }
+        PlanEditOperation::SplitDay { day_index, second_title } => {
+            split_day(&mut edited, *day_index, second_title)?;
+        }
+        PlanEditOperation::DeleteQuizQuestion { question_index } => {
+            if *question_index >= edited.quiz_questions.len() {
+                return Err(AppError::Validation(format!(
+                    "Quiz question index {} is out of range",
+                    question_index
+                )));
+            }
+            edited.quiz_questions.remove(*question_index);
+        }
+        PlanEditOperation::Retag { day_index, tags } => {
+            retag(&mut edited, *day_index, tags)?;
+        }
+        PlanEditOperation::FitToCapacity { daily_minutes } => {
+            capacity_planner::fit_to_capacity(&mut edited, *daily_minutes)?;
+        }
+        PlanEditOperation::InferDependencies => {
+            refresh_inferred_dependencies(&mut edited)?;
+        }
+        PlanEditOperation::AcceptInferredDependency { index } => {
+            let dep = pending_inferred(&mut edited, *index)?;
+            dep.status = "accepted".to_string();
+            let accepted = DependencyDraft {
+                day_index: dep.day_index,
+                depends_on_day_number: dep.depends_on_day_number,
+                dependency_type: "prerequisite".to_string(),
+                minimum_score: dep.minimum_score,
+            };
+            edited.dependencies.push(accepted);
+        }
+        PlanEditOperation::RejectInferredDependency { index } => {
+            pending_inferred(&mut edited, *index)?.status = "rejected".to_string();
+        }
+        PlanEditOperation::ReplacePlan { plan } => {
+            edited = (**plan).clone();
+        }
+    }
+
+    validate_plan(&edited)?;
+    let warnings_after = dependency_warnings(&edited);
+    if warnings_after.len() > warnings_before {
+        return Err(AppError::Validation(format!(
+            "Edit would break dependencies: {}",
+            warnings_after.join("; ")
+        )));
+    }
+
+    Ok(edited)
+}
+
+/// Structural checks on a plan: every index points at something that exists and
+/// day numbers are unique.
+pub fn validate_plan(plan: &GeneratedPlan) -> Result<(), AppError> {
+    let day_count = plan.day_plans.len();
+
+    let mut day_numbers = HashSet::new();
+    for day in &plan.day_plans {
+        if day.module_index >= plan.modules.len() {
+            return Err(AppError::Validation(format!(
+                "Day {} references missing module {}",
+                day.day_number, day.module_index
+            )));
+        }
+        if !day_numbers.insert(day.day_number) {
+            return Err(AppError::Validation(format!(
+                "Day number {} is used more than once",
+                day.day_number
+            )));
+        }
+    }
+
+    if let Some(item) = plan.checklist_items.iter().find(|i| i.day_index >= day_count) {
+        return Err(AppError::Validation(format!(
+            "Checklist item '{}' references missing day index {}",
+            item.label, item.day_index
+        )));
+    }
+    if let Some(question) = plan.quiz_questions.iter().find(|q| q.day_index >= day_count) {
+        return Err(AppError::Validation(format!(
+            "Quiz question references missing day index {}",
+            question.day_index
+        )));
+    }
+    if let Some(dep) = plan.dependencies.iter().find(|d| d.day_index >= day_count) {
+        return Err(AppError::Validation(format!(
+            "Dependency references missing day index {}",
+            dep.day_index
+        )));
+    }
+
+    let tag_names: HashSet<&str> = plan.concept_tags.iter().map(|t| t.name.as_str()).collect();
+    for (day_index, tag_name) in &plan.tag_assignments {
+        if *day_index >= day_count {
+            return Err(AppError::Validation(format!(
+                "Tag '{}' is assigned to missing day index {}",
+                tag_name, day_index
+            )));
+        }
+        if !tag_names.contains(tag_name.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Tag '{}' is assigned but not defined",
+                tag_name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn dependency_warnings(plan: &GeneratedPlan) -> Vec<String> {
+    let day_map: HashMap<i64, usize> = plan
+        .day_plans
+        .iter()
+        .enumerate()
+        .map(|(index, day)| (day.day_number, index))
+        .collect();
+
+    let mut warnings = Vec::new();
+    validate_dependencies(&plan.dependencies, &day_map, &mut warnings);
+    warnings
+}
+
+fn pending_inferred(plan: &mut GeneratedPlan, index: usize) -> Result<&mut InferredDependency, AppError> {
+    let dep = plan.inferred_dependencies.get_mut(index).ok_or_else(|| {
+        AppError::Validation(format!("Inferred dependency index {} is out of range", index))
+    })?;
+    if dep.status != "pending" {
+        return Err(AppError::Validation(format!(
+            "Inferred dependency was already {}",
+            dep.status
+        )));
+    }
+    Ok(dep)
+}
+
+/// Re-runs inference, keeping earlier accept/reject decisions so a rejected edge doesn't
+/// come back as pending.
+fn refresh_inferred_dependencies(plan: &mut GeneratedPlan) -> Result<(), AppError> {
+    let decided: Vec<InferredDependency> = plan
+        .inferred_dependencies
+        .drain(..)
+        .filter(|dep| dep.status != "pending")
+        .collect();
+    let decided_edges: HashSet<(usize, i64)> = decided
+        .iter()
+        .map(|dep| (dep.day_index, dep.depends_on_day_number))
+        .collect();
+
+    let fresh = infer_dependencies(plan)?;
+    plan.inferred_dependencies = decided;
+    plan.inferred_dependencies.extend(
+        fresh
+            .into_iter()
+            .filter(|dep| !decided_edges.contains(&(dep.day_index, dep.depends_on_day_number))),
+    );
+    Ok(())
+}
+
+fn check_day(plan: &GeneratedPlan, day_index: usize) -> Result<(), AppError> {
+    if day_index >= plan.day_plans.len() {
+        return Err(AppError::Validation(format!(
+            "Day index {} is out of range",
+            day_index
+        )));
+    }
+    Ok(())
+}
+
+fn check_module(plan: &GeneratedPlan, module_index: usize) -> Result<(), AppError> {
+    if module_index >= plan.modules.len() {
+        return Err(AppError::Validation(format!(
+            "Module index {} is out of range",
+            module_index
+        )));
+    }
+    Ok(())
+}
+
+fn rename_module(plan: &mut GeneratedPlan, module_index: usize, title: &str) -> Result<(), AppError> {
+    check_module(plan, module_index)?;
+
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::Validation("Module title cannot be empty".to_string()));
+    }
+
+    plan.modules[module_index].title = title.to_string();
+    Ok(())
+}
+
+fn move_day(plan: &mut GeneratedPlan, day_index: usize, module_index: usize) -> Result<(), AppError> {
+    check_day(plan, day_index)?;
+    check_module(plan, module_index)?;
+
+    if plan.day_plans[day_index].module_index == module_index {
+        return Err(AppError::Validation(
+            "Day is already in that module".to_string(),
+        ));
+    }
+
+    // Days are stored grouped by module, so the moved day goes to the end of its new module.
+    let mut order: Vec<usize> = (0..plan.day_plans.len()).filter(|&i| i != day_index).collect();
+    let position = order
+        .iter()
+        .rposition(|&i| plan.day_plans[i].module_index <= module_index)
+        .map_or(0, |p| p + 1);
+    order.insert(position, day_index);
+
+    plan.day_plans[day_index].module_index = module_index;
+    reorder_days(plan, &order);
+    Ok(())
+}
+
+pub(crate) fn merge_days(plan: &mut GeneratedPlan, day_index: usize, into_day_index: usize) -> Result<(), AppError> {
+    check_day(plan, day_index)?;
+    check_day(plan, into_day_index)?;
+
+    if day_index == into_day_index {
+        return Err(AppError::Validation("Cannot merge a day into itself".to_string()));
+    }
+
+    let merged = plan.day_plans[day_index].clone();
+    let target = &mut plan.day_plans[into_day_index];
+    let target_number = target.day_number;
+
+    append_section(&mut target.syntax_targets, &merged.syntax_targets);
+    append_section(&mut target.implementation_brief, &merged.implementation_brief);
+    append_section(&mut target.files_to_create, &merged.files_to_create);
+    append_section(&mut target.success_criteria, &merged.success_criteria);
+    append_section(&mut target.stretch_challenge, &merged.stretch_challenge);
+    append_section(&mut target.notes, &merged.notes);
+    target.estimated_minutes += merged.estimated_minutes;
+    target.min_minutes += merged.min_minutes;
+    target.recommended_minutes += merged.recommended_minutes;
+    target.deep_minutes += merged.deep_minutes;
+    target.memory_rebuild_minutes = target.memory_rebuild_minutes.max(merged.memory_rebuild_minutes);
+    target.complexity_level = target.complexity_level.max(merged.complexity_level);
+
+    let next_order = plan
+        .checklist_items
+        .iter()
+        .filter(|i| i.day_index == into_day_index)
+        .map(|i| i.order_index + 1)
+        .max()
+        .unwrap_or(0);
+    let merged_items = plan.checklist_items.iter_mut().filter(|i| i.day_index == day_index);
+    for (offset, item) in (0..).zip(merged_items) {
+        item.day_index = into_day_index;
+        item.order_index = next_order + offset;
+    }
+    for question in plan.quiz_questions.iter_mut().filter(|q| q.day_index == day_index) {
+        question.day_index = into_day_index;
+    }
+    for (assigned_day, _) in plan.tag_assignments.iter_mut().filter(|(d, _)| *d == day_index) {
+        *assigned_day = into_day_index;
+    }
+    for dep in &mut plan.dependencies {
+        if dep.day_index == day_index {
+            dep.day_index = into_day_index;
+        }
+        if dep.depends_on_day_number == merged.day_number {
+            dep.depends_on_day_number = target_number;
+        }
+    }
+    for dep in &mut plan.inferred_dependencies {
+        if dep.day_index == day_index {
+            dep.day_index = into_day_index;
+        }
+        if dep.depends_on_day_number == merged.day_number {
+            dep.depends_on_day_number = target_number;
+        }
+    }
+
+    let order: Vec<usize> = (0..plan.day_plans.len()).filter(|&i| i != day_index).collect();
+    reorder_days(plan, &order);
+    Ok(())
+}
+
+fn split_day(plan: &mut GeneratedPlan, day_index: usize, second_title: &str) -> Result<(), AppError> {
+    check_day(plan, day_index)?;
+
+    let second_title = second_title.trim();
+    if second_title.is_empty() {
+        return Err(AppError::Validation("Split day needs a title".to_string()));
+    }
+
+    split_day_parts(plan, day_index, &[second_title.to_string()]);
+    Ok(())
+}
+
+/// Splits a day into `1 + extra_titles.len()` consecutive parts. Minutes are shared evenly,
+/// the checklist and quiz are dealt out in order, and each part depends on the one before.
+pub(crate) fn split_day_parts(plan: &mut GeneratedPlan, day_index: usize, extra_titles: &[String]) {
+    let parts = extra_titles.len() + 1;
+    let parts_i64 = parts as i64;
+    let first_new_index = plan.day_plans.len();
+
+    let original = &mut plan.day_plans[day_index];
+    original.estimated_minutes = (original.estimated_minutes / parts_i64).max(15);
+    original.min_minutes = (original.min_minutes / parts_i64).max(15);
+    original.recommended_minutes = (original.recommended_minutes / parts_i64).max(15);
+    original.deep_minutes = (original.deep_minutes / parts_i64).max(15);
+    let template = original.clone();
+
+    // New parts are parked on unused numbers until reorder_days renumbers everything.
+    let first_free = plan.day_plans.iter().map(|d| d.day_number).max().unwrap_or(0) + 1;
+    for (day_number, title) in (first_free..).zip(extra_titles) {
+        let mut part = template.clone();
+        part.title = title.clone();
+        part.day_number = day_number;
+        plan.day_plans.push(part);
+    }
+    let part_index = |part: usize| if part == 0 { day_index } else { first_new_index + part - 1 };
+
+    let mut checklist: Vec<usize> = (0..plan.checklist_items.len())
+        .filter(|&i| plan.checklist_items[i].day_index == day_index)
+        .collect();
+    checklist.sort_by_key(|&i| plan.checklist_items[i].order_index);
+    let chunk = checklist.len().div_ceil(parts).max(1);
+    for (position, &i) in checklist.iter().enumerate() {
+        let item = &mut plan.checklist_items[i];
+        item.day_index = part_index(position / chunk);
+        item.order_index = (position % chunk) as i64;
+    }
+
+    let questions: Vec<usize> = (0..plan.quiz_questions.len())
+        .filter(|&i| plan.quiz_questions[i].day_index == day_index)
+        .collect();
+    let chunk = questions.len().div_ceil(parts).max(1);
+    for (position, &i) in questions.iter().enumerate() {
+        plan.quiz_questions[i].day_index = part_index(position / chunk);
+    }
+
+    let tags: Vec<String> = plan
+        .tag_assignments
+        .iter()
+        .filter(|(d, _)| *d == day_index)
+        .map(|(_, name)| name.clone())
+        .collect();
+    for part in 1..parts {
+        plan.tag_assignments
+            .extend(tags.iter().map(|name| (part_index(part), name.clone())));
+    }
+
+    let mut order: Vec<usize> = (0..first_new_index).collect();
+    for part in 1..parts {
+        order.insert(day_index + part, part_index(part));
+    }
+    reorder_days(plan, &order);
+
+    // Days that built on the original now need every part, which the last part implies.
+    let first_number = plan.day_plans[day_index].day_number;
+    let last_number = plan.day_plans[day_index + parts - 1].day_number;
+    for dep in &mut plan.dependencies {
+        if dep.depends_on_day_number == first_number {
+            dep.depends_on_day_number = last_number;
+        }
+    }
+    for dep in &mut plan.inferred_dependencies {
+        if dep.depends_on_day_number == first_number {
+            dep.depends_on_day_number = last_number;
+        }
+    }
+    for part in 1..parts {
+        plan.dependencies.push(DependencyDraft {
+            day_index: day_index + part,
+            depends_on_day_number: plan.day_plans[day_index + part - 1].day_number,
+            dependency_type: "prerequisite".to_string(),
+            minimum_score: 70,
+        });
+    }
+}
+
+fn retag(plan: &mut GeneratedPlan, day_index: usize, tags: &[ConceptTagDraft]) -> Result<(), AppError> {
+    check_day(plan, day_index)?;
+
+    let mut names = Vec::new();
+    for tag in tags {
+        let name = normalize_tag_name(&tag.name);
+        if name.is_empty() {
+            return Err(AppError::Validation("Tag name cannot be empty".to_string()));
+        }
+        if names.contains(&name) {
+            continue;
+        }
+        if !plan.concept_tags.iter().any(|t| t.name == name) {
+            plan.concept_tags.push(ConceptTagDraft {
+                name: name.clone(),
+                domain: tag.domain.trim().to_string(),
+            });
+        }
+        names.push(name);
+    }
+
+    plan.tag_assignments.retain(|(d, _)| *d != day_index);
+    plan.tag_assignments.extend(names.into_iter().map(|name| (day_index, name)));
+
+    let assigned: HashSet<&str> = plan.tag_assignments.iter().map(|(_, n)| n.as_str()).collect();
+    plan.concept_tags.retain(|t| assigned.contains(t.name.as_str()));
+    Ok(())
+}
+
+fn append_section(field: &mut String, extra: &str) {
+    let extra = extra.trim();
+    if extra.is_empty() {
+        return;
+    }
+    if !field.trim().is_empty() {
+        field.push_str("\n\n");
+    }
+    field.push_str(extra);
+}
+
+/// Rebuilds `day_plans` in the given order (a list of old indices; omitted days are dropped),
+/// renumbers days contiguously and remaps every index and day-number reference to match.
+fn reorder_days(plan: &mut GeneratedPlan, order: &[usize]) {
+    let start = plan.day_plans.iter().map(|d| d.day_number).min().unwrap_or(1);
+
+    let old_to_new: HashMap<usize, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(new, &old)| (old, new))
+        .collect();
+    let number_map: HashMap<i64, i64> = order
+        .iter()
+        .enumerate()
+        .map(|(new, &old)| (plan.day_plans[old].day_number, start + new as i64))
+        .collect();
+
+    plan.day_plans = order
+        .iter()
+        .enumerate()
+        .map(|(new, &old)| {
+            let mut day = plan.day_plans[old].clone();
+            day.day_number = start + new as i64;
+            day
+        })
+        .collect();
+
+    plan.checklist_items.retain(|i| old_to_new.contains_key(&i.day_index));
+    for item in &mut plan.checklist_items {
+        item.day_index = old_to_new[&item.day_index];
+    }
+
+    plan.quiz_questions.retain(|q| old_to_new.contains_key(&q.day_index));
+    for question in &mut plan.quiz_questions {
+        question.day_index = old_to_new[&question.day_index];
+    }
+
+    let mut seen_tags = HashSet::new();
+    plan.tag_assignments = plan
+        .tag_assignments
+        .drain(..)
+        .filter_map(|(d, name)| old_to_new.get(&d).map(|&new| (new, name)))
+        .filter(|assignment| seen_tags.insert(assignment.clone()))
+        .collect();
+
+    let mut seen_deps = HashSet::new();
+    plan.dependencies = plan
+        .dependencies
+        .drain(..)
+        .filter_map(|mut dep| {
+            dep.day_index = *old_to_new.get(&dep.day_index)?;
+            // Dangling references are left alone so existing warnings stay visible.
+            if let Some(&number) = number_map.get(&dep.depends_on_day_number) {
+                dep.depends_on_day_number = number;
+            }
+            Some(dep)
+        })
+        .filter(|dep| {
+            start + dep.day_index as i64 != dep.depends_on_day_number
+                && seen_deps.insert((dep.day_index, dep.depends_on_day_number))
+        })
+        .collect();
+
+    // Inferred edges only ever point at real days, so ones whose ends are gone are dropped.
+    let mut seen_inferred = HashSet::new();
+    plan.inferred_dependencies = plan
+        .inferred_dependencies
+        .drain(..)
+        .filter_map(|mut dep| {
+            dep.day_index = *old_to_new.get(&dep.day_index)?;
+            dep.depends_on_day_number = *number_map.get(&dep.depends_on_day_number)?;
+            Some(dep)
+        })
+        .filter(|dep| {
+            start + dep.day_index as i64 != dep.depends_on_day_number
+                && seen_inferred.insert((dep.day_index, dep.depends_on_day_number))
+        })
+        .collect();
+}
@@ -1,11 +1,28 @@
+use crate::db::models::UserCapacityProfile;
 use crate::db::models::import::{
-    AiAnalysisResponse, ChecklistItemDraft, ConceptTagDraft, DayPlanDraft, DependencyDraft,
-    GeneratedPlan, ModuleDraft, ProgramDraft, QuizQuestionDraft,
+    AiAnalysisResponse, CapacityPlan, ChecklistItemDraft, ConceptTagDraft, DayPlanDraft, DependencyDraft,
+    GeneratedPlan, InferredDependency, ModuleDraft, ProgramDraft, QuizQuestionDraft,
 };
 use crate::error::AppError;
+use crate::services::capacity_planner;
+use chrono::NaiveDate;
 use std::collections::{HashMap, HashSet};
 
-pub fn generate_plan(ai_response: AiAnalysisResponse) -> Result<GeneratedPlan, AppError> {
+/// Builds the plan from the AI response, fits it to the learner's daily minutes and projects
+/// it onto their study days from `start`, spacing deep days by `max_deep_days_per_week`.
+pub fn generate_plan(
+    ai_response: AiAnalysisResponse,
+    profile: &UserCapacityProfile,
+    start: NaiveDate,
+) -> Result<CapacityPlan, AppError> {
+    let mut plan = build_plan(ai_response)?;
+    capacity_planner::fit_to_capacity(&mut plan, i64::from(profile.default_daily_minutes))?;
+    let calendar = capacity_planner::project_calendar(&plan, profile, start);
+
+    Ok(CapacityPlan { plan, calendar })
+}
+
+fn build_plan(ai_response: AiAnalysisResponse) -> Result<GeneratedPlan, AppError> {
     let mut validation_warnings = Vec::new();
 
     let program = ProgramDraft {
@@ -125,7 +142,7 @@ pub fn generate_plan(ai_response: AiAnalysisResponse) -> Result<GeneratedPlan, A
                     day_index: global_day_index,
                     depends_on_day_number: dep.depends_on_day_number,
                     dependency_type: validate_dependency_type(&dep.dependency_type),
-                    minimum_score: dep.minimum_score.max(0).min(100),
+                    minimum_score: dep.minimum_score.clamp(0, 100),
                 });
             }
 
@@ -140,7 +157,7 @@ pub fn generate_plan(ai_response: AiAnalysisResponse) -> Result<GeneratedPlan, A
         .map(|(name, domain)| ConceptTagDraft { name, domain })
         .collect();
 
-    Ok(GeneratedPlan {
+    let mut plan = GeneratedPlan {
         program,
         modules,
         day_plans,
@@ -150,7 +167,15 @@ pub fn generate_plan(ai_response: AiAnalysisResponse) -> Result<GeneratedPlan, A
         tag_assignments,
         dependencies,
         validation_warnings,
-    })
+        inferred_dependencies: Vec::new(),
+    };
+
+    match infer_dependencies(&plan) {
+        Ok(inferred) => plan.inferred_dependencies = inferred,
+        Err(e) => plan.validation_warnings.push(e.to_string()),
+    }
+
+    Ok(plan)
 }
 
 fn clamp_minutes(minutes: i64, warnings: &mut Vec<String>) -> i64 {
@@ -177,7 +202,7 @@ fn validate_color(color: &str) -> String {
     }
 }
 
-fn calculate_complexity(syntax: &str, implementation: &str, minutes: i64) -> i64 {
+pub(crate) fn calculate_complexity(syntax: &str, implementation: &str, minutes: i64) -> i64 {
     let mut score = 1i64;
 
     if minutes > 90 {
@@ -217,7 +242,8 @@ fn calculate_complexity(syntax: &str, implementation: &str, minutes: i64) -> i64
 
 fn validate_question_type(qtype: &str) -> String {
     match qtype {
-        "short_answer" | "multiple_choice" | "code_prompt" | "reflection" => qtype.to_string(),
+        "short_answer" | "multiple_choice" | "multi_select" | "true_false" | "numeric"
+        | "code_prompt" | "reflection" => qtype.to_string(),
         _ => "short_answer".to_string(),
     }
 }
@@ -229,11 +255,11 @@ fn validate_dependency_type(dtype: &str) -> String {
     }
 }
 
-fn normalize_tag_name(name: &str) -> String {
+pub(crate) fn normalize_tag_name(name: &str) -> String {
     name.trim().to_lowercase()
 }
 
-fn validate_dependencies(
+pub(crate) fn validate_dependencies(
     dependencies: &[DependencyDraft],
     day_map: &HashMap<i64, usize>,
     warnings: &mut Vec<String>,
@@ -256,3 +282,134 @@ fn validate_dependencies(
         }
     }
 }
+
+/// Infers prerequisite edges from concept tag flow: a day that uses a tag depends on the day
+/// that introduced it. Edges the plan already has or that are implied transitively are dropped,
+/// and a cycle through the plan's own dependencies is an error.
+pub(crate) fn infer_dependencies(plan: &GeneratedPlan) -> Result<Vec<InferredDependency>, AppError> {
+    let mut order: Vec<usize> = (0..plan.day_plans.len()).collect();
+    order.sort_by_key(|&i| plan.day_plans[i].day_number);
+    let position: HashMap<usize, usize> = order.iter().enumerate().map(|(p, &i)| (i, p)).collect();
+    let number_to_index: HashMap<i64, usize> = plan
+        .day_plans
+        .iter()
+        .enumerate()
+        .map(|(i, day)| (day.day_number, i))
+        .collect();
+
+    let mut introduced_by: HashMap<&str, usize> = HashMap::new();
+    for (day_index, tag_name) in &plan.tag_assignments {
+        let entry = introduced_by.entry(tag_name.as_str()).or_insert(*day_index);
+        if position.get(day_index) < position.get(entry) {
+            *entry = *day_index;
+        }
+    }
+
+    // (day_index, depends_on_index) -> tags that justify the edge
+    let mut candidates: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+    for (day_index, tag_name) in &plan.tag_assignments {
+        let introducer = introduced_by[tag_name.as_str()];
+        if introducer != *day_index {
+            let tags = candidates.entry((*day_index, introducer)).or_default();
+            if !tags.contains(tag_name) {
+                tags.push(tag_name.clone());
+            }
+        }
+    }
+
+    let explicit: HashSet<(usize, usize)> = plan
+        .dependencies
+        .iter()
+        .filter_map(|dep| {
+            number_to_index
+                .get(&dep.depends_on_day_number)
+                .map(|&target| (dep.day_index, target))
+        })
+        .collect();
+    candidates.retain(|edge, _| !explicit.contains(edge));
+
+    let mut edges: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(from, to) in explicit.iter().chain(candidates.keys()) {
+        edges.entry(from).or_default().push(to);
+    }
+
+    if let Some(day_index) = find_cycle(&edges, plan.day_plans.len()) {
+        return Err(AppError::Validation(format!(
+            "Dependency cycle through day {}",
+            plan.day_plans[day_index].day_number
+        )));
+    }
+
+    let mut inferred: Vec<InferredDependency> = candidates
+        .into_iter()
+        .filter(|&((from, to), _)| !reachable_without_edge(&edges, from, to))
+        .map(|((from, to), tag_names)| InferredDependency {
+            day_index: from,
+            depends_on_day_number: plan.day_plans[to].day_number,
+            minimum_score: minimum_score_for_complexity(plan.day_plans[to].complexity_level),
+            tag_names,
+            status: "pending".to_string(),
+        })
+        .collect();
+    inferred.sort_by_key(|dep| (dep.day_index, dep.depends_on_day_number));
+
+    Ok(inferred)
+}
+
+fn minimum_score_for_complexity(complexity: i64) -> i64 {
+    match complexity {
+        ..=2 => 70,
+        3 => 75,
+        4 => 80,
+        _ => 85,
+    }
+}
+
+fn find_cycle(edges: &HashMap<usize, Vec<usize>>, node_count: usize) -> Option<usize> {
+    // 0 = unvisited, 1 = on the current path, 2 = done
+    let mut state = vec![0u8; node_count];
+    for start in 0..node_count {
+        if state[start] != 0 {
+            continue;
+        }
+        let mut stack = vec![(start, 0usize)];
+        state[start] = 1;
+        while let Some((node, next_child)) = stack.pop() {
+            let children = edges.get(&node).map_or(&[][..], Vec::as_slice);
+            if let Some(&child) = children.get(next_child) {
+                stack.push((node, next_child + 1));
+                match state.get(child) {
+                    Some(1) => return Some(child),
+                    Some(0) => {
+                        state[child] = 1;
+                        stack.push((child, 0));
+                    }
+                    _ => {}
+                }
+            } else {
+                state[node] = 2;
+            }
+        }
+    }
+    None
+}
+
+fn reachable_without_edge(edges: &HashMap<usize, Vec<usize>>, from: usize, to: usize) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack: Vec<usize> = edges
+        .get(&from)
+        .into_iter()
+        .flatten()
+        .copied()
+        .filter(|&next| next != to)
+        .collect();
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        if seen.insert(node) {
+            stack.extend(edges.get(&node).into_iter().flatten().copied());
+        }
+    }
+    false
+}
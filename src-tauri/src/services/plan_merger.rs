@@ -0,0 +1,802 @@
+use crate::db::models::import::{
+    AddedDay, ChecklistItemDraft, DayMatch, DayPlanDraft, GeneratedPlan, ModuleMatch,
+    QuizQuestionDraft, ReimportMergePlan, RemovedDay,
+};
+use crate::db::models::{DayPlan, Module, Program};
+use crate::error::AppError;
+use crate::services::import_applier::{
+    assign_tag, insert_checklist_item, insert_day_plan, insert_dependency, insert_quiz_question,
+    upsert_concept_tag,
+};
+use crate::services::plan_editor::validate_plan;
+use crate::services::similarity::title_similarity;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const MODULE_MATCH_THRESHOLD: f64 = 0.6;
+const DAY_TITLE_THRESHOLD: f64 = 0.5;
+const TITLE_WEIGHT: f64 = 0.7;
+
+struct ExistingDay {
+    day: DayPlan,
+    checklist: Vec<String>,
+    quiz: Vec<String>,
+    attempt_count: i64,
+}
+
+pub async fn build_merge_plan(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    program_id: &str,
+    plan: &GeneratedPlan,
+) -> Result<ReimportMergePlan, AppError> {
+    let modules = sqlx::query_as::<_, Module>(
+        "SELECT * FROM modules WHERE program_id = ? ORDER BY order_index",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?;
+
+    let days = sqlx::query_as::<_, DayPlan>(
+        "SELECT * FROM day_plans WHERE program_id = ? AND status != 'archived' ORDER BY day_number",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?;
+
+    if modules.is_empty() && days.is_empty() {
+        return Err(AppError::NotFound {
+            entity: "Program".to_string(),
+            id: program_id.to_string(),
+        });
+    }
+
+    let checklist_rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT ci.day_plan_id, ci.label FROM checklist_items ci
+         JOIN day_plans dp ON ci.day_plan_id = dp.id
+         WHERE dp.program_id = ?
+         ORDER BY ci.order_index",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?;
+
+    let quiz_rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT qq.day_plan_id, qq.question_text FROM quiz_questions qq
+         JOIN day_plans dp ON qq.day_plan_id = dp.id
+         WHERE dp.program_id = ?
+         ORDER BY qq.order_index",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?;
+
+    let attempt_rows: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT da.day_plan_id, COUNT(*) FROM day_attempts da
+         JOIN day_plans dp ON da.day_plan_id = dp.id
+         WHERE dp.program_id = ?
+         GROUP BY da.day_plan_id",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut checklists: HashMap<String, Vec<String>> = HashMap::new();
+    for (day_id, label) in checklist_rows {
+        checklists.entry(day_id).or_default().push(label);
+    }
+    let mut quizzes: HashMap<String, Vec<String>> = HashMap::new();
+    for (day_id, text) in quiz_rows {
+        quizzes.entry(day_id).or_default().push(text);
+    }
+    let attempts: HashMap<String, i64> = attempt_rows.into_iter().collect();
+
+    let existing_days: Vec<ExistingDay> = days
+        .into_iter()
+        .map(|day| ExistingDay {
+            checklist: checklists.remove(&day.id).unwrap_or_default(),
+            quiz: quizzes.remove(&day.id).unwrap_or_default(),
+            attempt_count: attempts.get(&day.id).copied().unwrap_or(0),
+            day,
+        })
+        .collect();
+
+    Ok(match_plan(job_id, program_id, plan, &modules, &existing_days))
+}
+
+fn match_plan(
+    job_id: &str,
+    program_id: &str,
+    plan: &GeneratedPlan,
+    modules: &[Module],
+    existing_days: &[ExistingDay],
+) -> ReimportMergePlan {
+    let module_matches = match_modules(plan, modules);
+
+    // Score every (draft, existing) pair: mostly title similarity, with provenance
+    // (same module, similar position) breaking ties between near-identical titles.
+    let span = plan.day_plans.len().max(existing_days.len()).max(1) as f64;
+    let mut candidates = Vec::new();
+    for (day_index, draft) in plan.day_plans.iter().enumerate() {
+        let matched_module_id = module_matches
+            .get(draft.module_index)
+            .and_then(|m| m.existing_module_id.as_deref());
+
+        for (existing_index, existing) in existing_days.iter().enumerate() {
+            let title_score = title_similarity(&draft.title, &existing.day.title);
+            if title_score < DAY_TITLE_THRESHOLD {
+                continue;
+            }
+
+            let module_score = if matched_module_id == Some(existing.day.module_id.as_str()) {
+                1.0
+            } else {
+                0.0
+            };
+            let distance = (draft.day_number - i64::from(existing.day.day_number)).abs() as f64;
+            let position_score = 1.0 - (distance / span).min(1.0);
+            let provenance = 0.5 * module_score + 0.5 * position_score;
+
+            let score = TITLE_WEIGHT * title_score + (1.0 - TITLE_WEIGHT) * provenance;
+            candidates.push((score, day_index, existing_index));
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut matched_drafts = HashSet::new();
+    let mut matched_existing = HashSet::new();
+    let mut pairs = Vec::new();
+    for (score, day_index, existing_index) in candidates {
+        if matched_drafts.contains(&day_index) || matched_existing.contains(&existing_index) {
+            continue;
+        }
+        matched_drafts.insert(day_index);
+        matched_existing.insert(existing_index);
+        pairs.push((score, day_index, existing_index));
+    }
+
+    // A match only counts as "moved" when its order relative to the other matched days
+    // changes, so inserting one new day doesn't flag everything after it.
+    let mut by_old: Vec<usize> = (0..pairs.len()).collect();
+    by_old.sort_by_key(|&i| existing_days[pairs[i].2].day.day_number);
+    let mut by_new: Vec<usize> = (0..pairs.len()).collect();
+    by_new.sort_by_key(|&i| plan.day_plans[pairs[i].1].day_number);
+    let mut old_rank = vec![0; pairs.len()];
+    let mut new_rank = vec![0; pairs.len()];
+    for (rank, &i) in by_old.iter().enumerate() {
+        old_rank[i] = rank;
+    }
+    for (rank, &i) in by_new.iter().enumerate() {
+        new_rank[i] = rank;
+    }
+
+    let mut changed = Vec::new();
+    let mut moved = Vec::new();
+    let mut unchanged = Vec::new();
+    for (pair_index, &(score, day_index, existing_index)) in pairs.iter().enumerate() {
+        let draft = &plan.day_plans[day_index];
+        let existing = &existing_days[existing_index];
+
+        let new_module_id = module_matches
+            .get(draft.module_index)
+            .and_then(|m| m.existing_module_id.as_deref());
+        let module_changed = new_module_id != Some(existing.day.module_id.as_str());
+
+        let day_match = DayMatch {
+            day_index,
+            existing_day_plan_id: existing.day.id.clone(),
+            existing_title: existing.day.title.clone(),
+            new_title: draft.title.clone(),
+            old_day_number: i64::from(existing.day.day_number),
+            new_day_number: draft.day_number,
+            old_module_id: existing.day.module_id.clone(),
+            new_module_index: draft.module_index,
+            similarity: score,
+            changed_fields: changed_fields(plan, day_index, existing),
+            attempt_count: existing.attempt_count,
+        };
+
+        if module_changed || old_rank[pair_index] != new_rank[pair_index] {
+            moved.push(day_match);
+        } else if day_match.changed_fields.is_empty() {
+            unchanged.push(day_match);
+        } else {
+            changed.push(day_match);
+        }
+    }
+
+    let added = plan
+        .day_plans
+        .iter()
+        .enumerate()
+        .filter(|(day_index, _)| !matched_drafts.contains(day_index))
+        .map(|(day_index, draft)| AddedDay {
+            day_index,
+            title: draft.title.clone(),
+            day_number: draft.day_number,
+            module_index: draft.module_index,
+        })
+        .collect();
+
+    let removed = existing_days
+        .iter()
+        .enumerate()
+        .filter(|(existing_index, _)| !matched_existing.contains(existing_index))
+        .map(|(_, existing)| RemovedDay {
+            day_plan_id: existing.day.id.clone(),
+            title: existing.day.title.clone(),
+            day_number: i64::from(existing.day.day_number),
+            attempt_count: existing.attempt_count,
+        })
+        .collect();
+
+    ReimportMergePlan {
+        job_id: job_id.to_string(),
+        program_id: program_id.to_string(),
+        modules: module_matches,
+        added,
+        changed,
+        moved,
+        unchanged,
+        removed,
+    }
+}
+
+fn match_modules(plan: &GeneratedPlan, modules: &[Module]) -> Vec<ModuleMatch> {
+    let mut candidates = Vec::new();
+    for (module_index, draft) in plan.modules.iter().enumerate() {
+        for (existing_index, existing) in modules.iter().enumerate() {
+            let score = title_similarity(&draft.title, &existing.title);
+            if score >= MODULE_MATCH_THRESHOLD {
+                candidates.push((score, module_index, existing_index));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut assigned: HashMap<usize, (usize, f64)> = HashMap::new();
+    let mut taken = HashSet::new();
+    for (score, module_index, existing_index) in candidates {
+        if assigned.contains_key(&module_index) || taken.contains(&existing_index) {
+            continue;
+        }
+        taken.insert(existing_index);
+        assigned.insert(module_index, (existing_index, score));
+    }
+
+    plan.modules
+        .iter()
+        .enumerate()
+        .map(|(module_index, draft)| {
+            let existing = assigned.get(&module_index);
+            ModuleMatch {
+                module_index,
+                title: draft.title.clone(),
+                existing_module_id: existing.map(|&(i, _)| modules[i].id.clone()),
+                existing_title: existing.map(|&(i, _)| modules[i].title.clone()),
+                similarity: existing.map_or(0.0, |&(_, score)| score),
+            }
+        })
+        .collect()
+}
+
+fn changed_fields(plan: &GeneratedPlan, day_index: usize, existing: &ExistingDay) -> Vec<String> {
+    let draft = &plan.day_plans[day_index];
+    let day = &existing.day;
+    let mut fields = Vec::new();
+
+    let text_fields = [
+        ("title", &draft.title, &day.title),
+        ("syntax_targets", &draft.syntax_targets, &day.syntax_targets),
+        ("implementation_brief", &draft.implementation_brief, &day.implementation_brief),
+        ("files_to_create", &draft.files_to_create, &day.files_to_create),
+        ("success_criteria", &draft.success_criteria, &day.success_criteria),
+        ("stretch_challenge", &draft.stretch_challenge, &day.stretch_challenge),
+        ("notes", &draft.notes, &day.notes),
+    ];
+    for (name, new, old) in text_fields {
+        if new.trim() != old.trim() {
+            fields.push(name.to_string());
+        }
+    }
+
+    let number_fields = [
+        ("estimated_minutes", draft.estimated_minutes, day.estimated_minutes),
+        ("memory_rebuild_minutes", draft.memory_rebuild_minutes, day.memory_rebuild_minutes),
+        ("complexity_level", draft.complexity_level, day.complexity_level),
+    ];
+    for (name, new, old) in number_fields {
+        if new != i64::from(old) {
+            fields.push(name.to_string());
+        }
+    }
+
+    let checklist: Vec<&str> = plan
+        .checklist_items
+        .iter()
+        .filter(|item| item.day_index == day_index)
+        .map(|item| item.label.trim())
+        .collect();
+    if checklist != existing.checklist.iter().map(|l| l.trim()).collect::<Vec<_>>() {
+        fields.push("checklist".to_string());
+    }
+
+    let quiz: Vec<&str> = plan
+        .quiz_questions
+        .iter()
+        .filter(|question| question.day_index == day_index)
+        .map(|question| question.question_text.trim())
+        .collect();
+    if quiz != existing.quiz.iter().map(|q| q.trim()).collect::<Vec<_>>() {
+        fields.push("quiz".to_string());
+    }
+
+    fields
+}
+
+pub async fn apply_merge_plan(
+    pool: &Pool<Sqlite>,
+    plan: &GeneratedPlan,
+    merge: &ReimportMergePlan,
+) -> Result<Program, AppError> {
+    let program_id = merge.program_id.as_str();
+
+    validate_plan(plan)?;
+    if merge.modules.len() != plan.modules.len() {
+        return Err(AppError::Validation(
+            "Merge plan modules do not match the import plan".to_string(),
+        ));
+    }
+
+    let owned_days: HashSet<String> = sqlx::query_scalar(
+        "SELECT id FROM day_plans WHERE program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+    let owned_modules: HashSet<String> = sqlx::query_scalar(
+        "SELECT id FROM modules WHERE program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let matches: Vec<&DayMatch> = merge
+        .changed
+        .iter()
+        .chain(&merge.moved)
+        .chain(&merge.unchanged)
+        .collect();
+
+    for day_match in &matches {
+        if !owned_days.contains(&day_match.existing_day_plan_id) {
+            return Err(AppError::Validation(format!(
+                "Day plan {} does not belong to program {}",
+                day_match.existing_day_plan_id, program_id
+            )));
+        }
+        if day_match.day_index >= plan.day_plans.len() {
+            return Err(AppError::Validation(format!(
+                "Merge plan references missing day index {}",
+                day_match.day_index
+            )));
+        }
+    }
+    for module_match in &merge.modules {
+        if let Some(id) = &module_match.existing_module_id {
+            if !owned_modules.contains(id) {
+                return Err(AppError::Validation(format!(
+                    "Module {} does not belong to program {}",
+                    id, program_id
+                )));
+            }
+        }
+    }
+    for added in &merge.added {
+        if added.day_index >= plan.day_plans.len() {
+            return Err(AppError::Validation(format!(
+                "Merge plan references missing day index {}",
+                added.day_index
+            )));
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Park existing rows on negative positions so renumbering can't trip the
+    // UNIQUE(program_id, order_index) and UNIQUE(program_id, day_number, version) constraints.
+    sqlx::query("UPDATE modules SET order_index = -1 - order_index WHERE program_id = ?")
+        .bind(program_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE day_plans SET day_number = -1 - day_number WHERE program_id = ?")
+        .bind(program_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut module_ids = Vec::new();
+    for (module_match, draft) in merge.modules.iter().zip(&plan.modules) {
+        let order_index = module_match.module_index as i64;
+        let module_id = if let Some(id) = &module_match.existing_module_id {
+            sqlx::query(
+                "UPDATE modules SET title = ?, description = ?, color = ?, order_index = ?, \
+                 updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = ?",
+            )
+            .bind(&draft.title)
+            .bind(&draft.description)
+            .bind(&draft.color)
+            .bind(order_index)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+            id.clone()
+        } else {
+            let id = Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO modules (id, program_id, title, description, order_index, color) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(program_id)
+            .bind(&draft.title)
+            .bind(&draft.description)
+            .bind(order_index)
+            .bind(&draft.color)
+            .execute(&mut *tx)
+            .await?;
+            id
+        };
+        module_ids.push(module_id);
+    }
+
+    let leftover_modules: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM modules WHERE program_id = ? AND order_index < 0 ORDER BY order_index DESC",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    for (offset, id) in leftover_modules.iter().enumerate() {
+        sqlx::query("UPDATE modules SET order_index = ? WHERE id = ?")
+            .bind((module_ids.len() + offset) as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let mut day_ids: Vec<Option<String>> = vec![None; plan.day_plans.len()];
+
+    for day_match in &matches {
+        let draft = &plan.day_plans[day_match.day_index];
+        let module_id = &module_ids[draft.module_index];
+        update_matched_day(&mut tx, day_match, draft, module_id).await?;
+
+        if day_match.changed_fields.iter().any(|f| f == "checklist") {
+            let items: Vec<&ChecklistItemDraft> = plan
+                .checklist_items
+                .iter()
+                .filter(|i| i.day_index == day_match.day_index)
+                .collect();
+            sync_checklist(&mut tx, &day_match.existing_day_plan_id, &items).await?;
+        }
+        if day_match.changed_fields.iter().any(|f| f == "quiz") {
+            let questions: Vec<&QuizQuestionDraft> = plan
+                .quiz_questions
+                .iter()
+                .filter(|q| q.day_index == day_match.day_index)
+                .collect();
+            sync_quiz(&mut tx, &day_match.existing_day_plan_id, &questions).await?;
+        }
+
+        day_ids[day_match.day_index] = Some(day_match.existing_day_plan_id.clone());
+    }
+
+    for added in &merge.added {
+        let draft = &plan.day_plans[added.day_index];
+        let day_id = insert_day_plan(&mut tx, program_id, &module_ids[draft.module_index], draft).await?;
+
+        for item in plan.checklist_items.iter().filter(|i| i.day_index == added.day_index) {
+            insert_checklist_item(&mut tx, &day_id, item).await?;
+        }
+        for question in plan.quiz_questions.iter().filter(|q| q.day_index == added.day_index) {
+            insert_quiz_question(&mut tx, &day_id, question).await?;
+        }
+
+        day_ids[added.day_index] = Some(day_id);
+    }
+
+    // Removed days are archived rather than deleted so their attempts and review history survive.
+    for removed in &merge.removed {
+        if owned_days.contains(&removed.day_plan_id) {
+            sqlx::query(
+                "UPDATE day_plans SET status = 'archived', \
+                 updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = ?",
+            )
+            .bind(&removed.day_plan_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    let next_day_number: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(day_number), 0) + 1 FROM day_plans WHERE program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let parked_days: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM day_plans WHERE program_id = ? AND day_number < 0 ORDER BY day_number DESC",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    for (offset, id) in parked_days.iter().enumerate() {
+        sqlx::query("UPDATE day_plans SET day_number = ? WHERE id = ?")
+            .bind(next_day_number + offset as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // Tags are only ever added, so tags the learner attached by hand are kept.
+    let mut tag_ids = HashMap::new();
+    for tag in &plan.concept_tags {
+        tag_ids.insert(tag.name.clone(), upsert_concept_tag(&mut tx, tag).await?);
+    }
+    for (day_index, tag_name) in &plan.tag_assignments {
+        if let (Some(Some(day_id)), Some(tag_id)) = (day_ids.get(*day_index), tag_ids.get(tag_name)) {
+            assign_tag(&mut tx, day_id, tag_id).await?;
+        }
+    }
+
+    for dep in &plan.dependencies {
+        let depends_on_index = plan
+            .day_plans
+            .iter()
+            .position(|d| d.day_number == dep.depends_on_day_number);
+        let Some(depends_on_index) = depends_on_index else {
+            continue;
+        };
+        if let (Some(Some(day_id)), Some(Some(depends_on_id))) =
+            (day_ids.get(dep.day_index), day_ids.get(depends_on_index))
+        {
+            if day_id != depends_on_id {
+                insert_dependency(&mut tx, day_id, depends_on_id, dep).await?;
+            }
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        "UPDATE programs SET target_days = ?, updated_at = ? WHERE id = ?",
+    )
+    .bind(plan.program.estimated_total_days)
+    .bind(&now)
+    .bind(program_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE import_jobs SET status = 'completed', program_id = ?, completed_at = ?, updated_at = ? WHERE id = ?",
+    )
+    .bind(program_id)
+    .bind(&now)
+    .bind(&now)
+    .bind(&merge.job_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let program: Program = sqlx::query_as("SELECT * FROM programs WHERE id = ?")
+        .bind(program_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(program)
+}
+
+/// Pairs each draft `(text, order_index)` with an existing `(id, text, order_index)` row:
+/// first by identical text, then a reworded item by its position. Returns the matched row
+/// id per draft and the ids of rows nothing matched.
+fn match_rows(
+    existing: &[(String, String, i64)],
+    drafts: &[(&str, i64)],
+) -> (Vec<Option<String>>, Vec<String>) {
+    let mut claimed = vec![false; existing.len()];
+    let mut matched: Vec<Option<usize>> = vec![None; drafts.len()];
+
+    for (draft_index, (text, _)) in drafts.iter().enumerate() {
+        let found = existing
+            .iter()
+            .enumerate()
+            .position(|(i, (_, existing_text, _))| !claimed[i] && existing_text == text);
+        if let Some(i) = found {
+            claimed[i] = true;
+            matched[draft_index] = Some(i);
+        }
+    }
+    for (draft_index, (_, order_index)) in drafts.iter().enumerate() {
+        if matched[draft_index].is_some() {
+            continue;
+        }
+        let found = existing
+            .iter()
+            .enumerate()
+            .position(|(i, (_, _, existing_order))| !claimed[i] && existing_order == order_index);
+        if let Some(i) = found {
+            claimed[i] = true;
+            matched[draft_index] = Some(i);
+        }
+    }
+
+    let matched = matched
+        .into_iter()
+        .map(|i| i.map(|i| existing[i].0.clone()))
+        .collect();
+    let unmatched = existing
+        .iter()
+        .zip(claimed)
+        .filter(|(_, claimed)| !claimed)
+        .map(|((id, _, _), _)| id.clone())
+        .collect();
+    (matched, unmatched)
+}
+
+/// Updates the day's checklist in place so completion records of kept items survive;
+/// only items that are really gone are deleted.
+async fn sync_checklist(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    items: &[&ChecklistItemDraft],
+) -> Result<(), AppError> {
+    let existing: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT id, label, order_index FROM checklist_items
+         WHERE day_plan_id = ? ORDER BY order_index",
+    )
+    .bind(day_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let drafts: Vec<(&str, i64)> = items
+        .iter()
+        .map(|item| (item.label.as_str(), item.order_index))
+        .collect();
+    let (matched, unmatched) = match_rows(&existing, &drafts);
+
+    for id in unmatched {
+        sqlx::query("DELETE FROM checklist_items WHERE id = ?")
+            .bind(&id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    for (item, id) in items.iter().zip(matched) {
+        match id {
+            Some(id) => {
+                sqlx::query(
+                    "UPDATE checklist_items SET label = ?, is_required = ?, order_index = ?
+                     WHERE id = ?",
+                )
+                .bind(&item.label)
+                .bind(i32::from(item.is_required))
+                .bind(item.order_index)
+                .bind(&id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            None => insert_checklist_item(conn, day_id, item).await?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Updates the day's quiz in place so answers and review cards of kept questions survive;
+/// only questions that are really gone are deleted. Questions are ordered as in the plan.
+async fn sync_quiz(
+    conn: &mut SqliteConnection,
+    day_id: &str,
+    questions: &[&QuizQuestionDraft],
+) -> Result<(), AppError> {
+    let existing: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT id, question_text, order_index FROM quiz_questions
+         WHERE day_plan_id = ? ORDER BY order_index, created_at",
+    )
+    .bind(day_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let drafts: Vec<(&str, i64)> = questions
+        .iter()
+        .enumerate()
+        .map(|(position, question)| (question.question_text.as_str(), position as i64))
+        .collect();
+    let (matched, unmatched) = match_rows(&existing, &drafts);
+
+    for id in unmatched {
+        sqlx::query("DELETE FROM quiz_questions WHERE id = ?")
+            .bind(&id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    for ((question, id), (_, order_index)) in questions.iter().zip(matched).zip(&drafts) {
+        let id = match id {
+            Some(id) => id,
+            None => insert_quiz_question(conn, day_id, question).await?,
+        };
+        sqlx::query(
+            "UPDATE quiz_questions SET question_text = ?, question_type = ?, correct_answer = ?,
+                options = ?, points = ?, time_limit_seconds = ?, order_index = ?
+             WHERE id = ?",
+        )
+        .bind(&question.question_text)
+        .bind(&question.question_type)
+        .bind(&question.correct_answer)
+        .bind(serde_json::to_string(&question.options)?)
+        .bind(question.points)
+        .bind(question.time_limit_seconds)
+        .bind(order_index)
+        .bind(&id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn update_matched_day(
+    conn: &mut SqliteConnection,
+    day_match: &DayMatch,
+    draft: &DayPlanDraft,
+    module_id: &str,
+) -> Result<(), AppError> {
+    // Same rule as update_day_plan: published content edits bump the version.
+    let content_changed = day_match
+        .changed_fields
+        .iter()
+        .any(|f| f != "checklist" && f != "quiz");
+
+    sqlx::query(
+        "UPDATE day_plans SET
+            module_id = ?,
+            title = ?,
+            day_number = ?,
+            version = CASE WHEN ? AND status = 'published' THEN version + 1 ELSE version END,
+            syntax_targets = ?,
+            implementation_brief = ?,
+            files_to_create = ?,
+            success_criteria = ?,
+            stretch_challenge = ?,
+            notes = ?,
+            estimated_minutes = ?,
+            memory_rebuild_minutes = ?,
+            min_minutes = ?,
+            recommended_minutes = ?,
+            deep_minutes = ?,
+            complexity_level = ?,
+            updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+         WHERE id = ?",
+    )
+    .bind(module_id)
+    .bind(&draft.title)
+    .bind(draft.day_number)
+    .bind(content_changed)
+    .bind(&draft.syntax_targets)
+    .bind(&draft.implementation_brief)
+    .bind(&draft.files_to_create)
+    .bind(&draft.success_criteria)
+    .bind(&draft.stretch_challenge)
+    .bind(&draft.notes)
+    .bind(draft.estimated_minutes)
+    .bind(draft.memory_rebuild_minutes)
+    .bind(draft.min_minutes)
+    .bind(draft.recommended_minutes)
+    .bind(draft.deep_minutes)
+    .bind(draft.complexity_level)
+    .bind(&day_match.existing_day_plan_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
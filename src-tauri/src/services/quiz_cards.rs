@@ -0,0 +1,367 @@
+use crate::db::models::{
+    AnswerQuizCardInput, LeechCard, LeechSuggestion, QuizCard, QuizCardPrompt, QuizCardReview,
+    QuizCardReviewResult, QuizCardSyncResult, QuizPrompt, QuizQuestion,
+};
+use crate::error::AppError;
+use crate::services::quiz_grader::{grade_answer, AUTO_GRADED_TYPES};
+use crate::services::review_queue;
+use crate::services::scheduler::{self, PriorReview, SchedulerConfig, AGAIN};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+/// Lapses after which a card is flagged as a leech.
+pub const LEECH_THRESHOLD: i32 = 4;
+/// Other cards of the same day with at least this many lapses point at the day's material
+/// rather than the leech's wording.
+const STRUGGLING_SIBLING_LAPSES: i32 = 2;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct CardRow {
+    #[sqlx(flatten)]
+    card: QuizCard,
+    question_text: String,
+    question_type: String,
+    options: String,
+    points: i32,
+    time_limit_seconds: i32,
+    order_index: i32,
+    day_title: String,
+    day_number: i32,
+}
+
+async fn fetch_card(conn: &mut SqliteConnection, id: &str) -> Result<QuizCard, AppError> {
+    sqlx::query_as::<_, QuizCard>("SELECT * FROM quiz_cards WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "QuizCard".to_string(),
+            id: id.to_string(),
+        })
+}
+
+/// Creates a card, due today, for every auto-graded question of the program that doesn't
+/// have one yet.
+pub async fn sync_cards(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<QuizCardSyncResult, AppError> {
+    let questions = sqlx::query_as::<_, QuizQuestion>(
+        "SELECT qq.* FROM quiz_questions qq
+         JOIN day_plans dp ON dp.id = qq.day_plan_id
+         WHERE dp.program_id = ?
+           AND NOT EXISTS (SELECT 1 FROM quiz_cards qc WHERE qc.quiz_question_id = qq.id)
+         ORDER BY dp.day_number, qq.order_index",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let timestamp = now.to_rfc3339();
+    let today = now.format("%Y-%m-%d").to_string();
+    let mut result = QuizCardSyncResult {
+        created: 0,
+        skipped_ungraded: 0,
+    };
+    for question in questions {
+        if !AUTO_GRADED_TYPES.contains(&question.question_type.as_str()) {
+            result.skipped_ungraded += 1;
+            continue;
+        }
+        sqlx::query(
+            "INSERT INTO quiz_cards (
+                id, quiz_question_id, day_plan_id, next_review_date, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&question.id)
+        .bind(&question.day_plan_id)
+        .bind(&today)
+        .bind(&timestamp)
+        .bind(&timestamp)
+        .execute(&mut *conn)
+        .await?;
+        result.created += 1;
+    }
+
+    Ok(result)
+}
+
+/// Active cards of the program due today, oldest due first, up to what's left of the daily
+/// review cap.
+pub async fn due_cards(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<QuizCardPrompt>, AppError> {
+    let today = now.format("%Y-%m-%d").to_string();
+
+    let reviewed_today = review_queue::reviewed_on(conn, program_id, &today).await?;
+    let remaining = (config.daily_review_cap - reviewed_today).max(0);
+
+    let rows = sqlx::query_as::<_, CardRow>(
+        "SELECT qc.*, qq.question_text, qq.question_type, qq.options, qq.points,
+            qq.time_limit_seconds, qq.order_index, dp.title AS day_title, dp.day_number
+         FROM quiz_cards qc
+         JOIN quiz_questions qq ON qq.id = qc.quiz_question_id
+         JOIN day_plans dp ON dp.id = qc.day_plan_id
+         WHERE dp.program_id = ? AND qc.status = 'active' AND qc.next_review_date <= ?
+         ORDER BY qc.next_review_date, dp.day_number, qq.order_index
+         LIMIT ?",
+    )
+    .bind(program_id)
+    .bind(&today)
+    .bind(remaining)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| QuizCardPrompt {
+            question: QuizPrompt {
+                id: row.card.quiz_question_id.clone(),
+                question_text: row.question_text,
+                question_type: row.question_type,
+                options: row.options,
+                points: row.points,
+                time_limit_seconds: row.time_limit_seconds,
+                order_index: row.order_index,
+            },
+            card: row.card,
+            day_title: row.day_title,
+            day_number: row.day_number,
+        })
+        .collect())
+}
+
+/// Grades the answer with the quiz engine's rules, schedules the card from the grade and
+/// flags it as a leech once it has lapsed `LEECH_THRESHOLD` times.
+pub async fn answer_card(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    input: &AnswerQuizCardInput,
+    now: DateTime<Utc>,
+) -> Result<QuizCardReviewResult, AppError> {
+    let card = fetch_card(conn, &input.quiz_card_id).await?;
+    if card.status != "active" {
+        return Err(AppError::InvalidStateTransition(
+            "Suspended quiz cards can't be reviewed".to_string(),
+        ));
+    }
+    let question = sqlx::query_as::<_, QuizQuestion>("SELECT * FROM quiz_questions WHERE id = ?")
+        .bind(&card.quiz_question_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let credit = grade_answer(&question, &input.answer).ok_or_else(|| {
+        AppError::Validation(format!(
+            "{} questions can't be graded automatically",
+            question.question_type
+        ))
+    })?;
+    let score = (credit * 100.0).round() as i32;
+
+    let prior = card.last_review_date.as_ref().map(|_| PriorReview {
+        easiness_factor: card.easiness_factor,
+        interval_days: card.interval_days,
+        repetition_count: card.repetition_count,
+        stability: card.stability,
+        difficulty: card.difficulty,
+    });
+    let elapsed_days = scheduler::elapsed_days(card.last_review_date.as_deref(), now);
+    let mut scheduled = config.review(prior.as_ref(), score, elapsed_days);
+    scheduled.interval_days = scheduler::spread_interval(
+        conn,
+        &format!("card:{}", card.id),
+        scheduled.interval_days,
+        config.maximum_interval_days,
+        now,
+    )
+    .await?;
+
+    let lapsed = prior.is_some() && scheduled.rating == AGAIN;
+    let lapse_count = card.lapse_count + i32::from(lapsed);
+    let is_leech = card.is_leech || lapse_count >= LEECH_THRESHOLD;
+
+    let timestamp = now.to_rfc3339();
+    let next_review = (now + chrono::Duration::days(i64::from(scheduled.interval_days)))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let card = sqlx::query_as::<_, QuizCard>(
+        "UPDATE quiz_cards SET
+            easiness_factor = ?, interval_days = ?, repetition_count = ?, stability = ?,
+            difficulty = ?, last_review_date = ?, next_review_date = ?, last_score = ?,
+            review_count = review_count + 1, lapse_count = ?, is_leech = ?, updated_at = ?
+         WHERE id = ?
+         RETURNING *",
+    )
+    .bind(scheduled.easiness_factor)
+    .bind(scheduled.interval_days)
+    .bind(scheduled.repetition_count)
+    .bind(scheduled.memory.stability)
+    .bind(scheduled.memory.difficulty)
+    .bind(now.format("%Y-%m-%d").to_string())
+    .bind(&next_review)
+    .bind(score)
+    .bind(lapse_count)
+    .bind(is_leech)
+    .bind(&timestamp)
+    .bind(&card.id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let review = sqlx::query_as::<_, QuizCardReview>(
+        "INSERT INTO quiz_card_reviews (
+            id, quiz_card_id, scheduler, answer, is_correct, score, rating,
+            time_taken_seconds, elapsed_days, scheduled_days, stability, difficulty, reviewed_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING *",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&card.id)
+    .bind(config.kind.as_str())
+    .bind(&input.answer)
+    .bind(credit >= 1.0)
+    .bind(score)
+    .bind(scheduled.rating)
+    .bind(input.time_taken_seconds.max(0))
+    .bind(elapsed_days)
+    .bind(scheduled.interval_days)
+    .bind(scheduled.memory.stability)
+    .bind(scheduled.memory.difficulty)
+    .bind(&timestamp)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let leech = if card.is_leech {
+        Some(leech_suggestion(conn, &card).await?)
+    } else {
+        None
+    };
+
+    Ok(QuizCardReviewResult {
+        card,
+        review,
+        correct_answer: question.correct_answer,
+        leech,
+    })
+}
+
+/// Suggests replaying the source day when its other cards are failing too, since the
+/// material then hasn't stuck, and rewriting the question otherwise.
+pub async fn leech_suggestion(
+    conn: &mut SqliteConnection,
+    card: &QuizCard,
+) -> Result<LeechSuggestion, AppError> {
+    let (day_title, day_number): (String, i32) =
+        sqlx::query_as("SELECT title, day_number FROM day_plans WHERE id = ?")
+            .bind(&card.day_plan_id)
+            .fetch_one(&mut *conn)
+            .await?;
+    let struggling_siblings: i32 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM quiz_cards
+         WHERE day_plan_id = ? AND id != ? AND (is_leech OR lapse_count >= ?)",
+    )
+    .bind(&card.day_plan_id)
+    .bind(&card.id)
+    .bind(STRUGGLING_SIBLING_LAPSES)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(if struggling_siblings > 0 {
+        LeechSuggestion {
+            action: "replay_day".to_string(),
+            message: format!(
+                "Failed {} times, and {} other question{} from Day {}: {} keep failing too. \
+                 Replaying the day is likely to help more than drilling this card.",
+                card.lapse_count,
+                struggling_siblings,
+                if struggling_siblings == 1 { "" } else { "s" },
+                day_number,
+                day_title
+            ),
+        }
+    } else {
+        LeechSuggestion {
+            action: "rewrite_question".to_string(),
+            message: format!(
+                "Failed {} times while the rest of Day {}: {} holds up. The question may be \
+                 ambiguous or test too much at once; consider rewriting or splitting it.",
+                card.lapse_count, day_number, day_title
+            ),
+        }
+    })
+}
+
+/// Every leech card of the program with its suggestion, most lapses first.
+pub async fn leeches(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+) -> Result<Vec<LeechCard>, AppError> {
+    let rows = sqlx::query_as::<_, CardRow>(
+        "SELECT qc.*, qq.question_text, qq.question_type, qq.options, qq.points,
+            qq.time_limit_seconds, qq.order_index, dp.title AS day_title, dp.day_number
+         FROM quiz_cards qc
+         JOIN quiz_questions qq ON qq.id = qc.quiz_question_id
+         JOIN day_plans dp ON dp.id = qc.day_plan_id
+         WHERE dp.program_id = ? AND qc.is_leech
+         ORDER BY qc.lapse_count DESC, dp.day_number",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut leeches = Vec::with_capacity(rows.len());
+    for row in rows {
+        let suggestion = leech_suggestion(conn, &row.card).await?;
+        leeches.push(LeechCard {
+            card: row.card,
+            question_text: row.question_text,
+            day_title: row.day_title,
+            day_number: row.day_number,
+            suggestion,
+        });
+    }
+
+    Ok(leeches)
+}
+
+/// Suspends or reactivates a card. Reactivating clears the leech flag and lapse count, on
+/// the assumption the question was rewritten or the day replayed.
+pub async fn set_suspended(
+    conn: &mut SqliteConnection,
+    id: &str,
+    suspended: bool,
+    now: DateTime<Utc>,
+) -> Result<QuizCard, AppError> {
+    let card = fetch_card(conn, id).await?;
+    let today = now.format("%Y-%m-%d").to_string();
+
+    let card = if suspended {
+        sqlx::query_as::<_, QuizCard>(
+            "UPDATE quiz_cards SET status = 'suspended', updated_at = ? WHERE id = ? RETURNING *",
+        )
+        .bind(now.to_rfc3339())
+        .bind(&card.id)
+        .fetch_one(&mut *conn)
+        .await?
+    } else {
+        sqlx::query_as::<_, QuizCard>(
+            "UPDATE quiz_cards SET
+                status = 'active', is_leech = 0, lapse_count = 0,
+                next_review_date = MIN(next_review_date, ?), updated_at = ?
+             WHERE id = ?
+             RETURNING *",
+        )
+        .bind(&today)
+        .bind(now.to_rfc3339())
+        .bind(&card.id)
+        .fetch_one(&mut *conn)
+        .await?
+    };
+
+    Ok(card)
+}
@@ -0,0 +1,144 @@
+use crate::db::models::{QuizQuestion, RubricDimension};
+use crate::error::AppError;
+use crate::services::similarity::normalize;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqliteConnection;
+use std::collections::HashSet;
+
+pub const QUIZ_DIMENSION_KEY: &str = "quiz";
+/// Question types `grade_answer` can mark without a human.
+pub const AUTO_GRADED_TYPES: [&str; 5] =
+    ["multiple_choice", "true_false", "numeric", "short_answer", "multi_select"];
+/// The fixed quiz maximum from before rubrics, used when a rubric has no quiz dimension.
+const LEGACY_QUIZ_MAX: i32 = 10;
+/// Slack on the session deadline for the round trip between the last answer and the submit.
+const DEADLINE_GRACE_SECONDS: i64 = 30;
+
+/// Fraction of the question's points the answer earns, in `0.0..=1.0`, or `None` when the
+/// question type needs a human to mark it.
+pub fn grade_answer(question: &QuizQuestion, answer: &str) -> Option<f64> {
+    let full = |correct: bool| if correct { 1.0 } else { 0.0 };
+
+    match question.question_type.as_str() {
+        "multiple_choice" => Some(full(
+            !answer.trim().is_empty() && normalize(answer) == normalize(&question.correct_answer),
+        )),
+        "true_false" => Some(full(
+            parse_bool(answer).is_some() && parse_bool(answer) == parse_bool(&question.correct_answer),
+        )),
+        "numeric" => {
+            let expected = parse_number(&question.correct_answer)?;
+            Some(full(parse_number(answer).is_some_and(|given| {
+                (given - expected).abs() <= question.answer_tolerance.abs() + f64::EPSILON
+            })))
+        }
+        // Alternatives are separated by `|`, e.g. "let|const"
+        "short_answer" => {
+            let given = normalize(answer);
+            Some(full(
+                !given.is_empty()
+                    && question
+                        .correct_answer
+                        .split('|')
+                        .any(|alternative| normalize(alternative) == given),
+            ))
+        }
+        "multi_select" => Some(multi_select_credit(&question.correct_answer, answer)),
+        _ => None,
+    }
+}
+
+/// Each correct pick earns a share of the points and each wrong pick takes one back, so
+/// selecting every option doesn't pay off.
+fn multi_select_credit(correct_answer: &str, answer: &str) -> f64 {
+    let correct: HashSet<String> = parse_list(correct_answer).into_iter().collect();
+    if correct.is_empty() {
+        return 0.0;
+    }
+    let selected: HashSet<String> = parse_list(answer).into_iter().collect();
+
+    let hits = selected.intersection(&correct).count() as f64;
+    let misses = selected.difference(&correct).count() as f64;
+    ((hits - misses) / correct.len() as f64).clamp(0.0, 1.0)
+}
+
+/// Accepts a JSON array of strings or a comma/newline separated list.
+fn parse_list(text: &str) -> Vec<String> {
+    let items = serde_json::from_str::<Vec<String>>(text.trim()).unwrap_or_else(|_| {
+        text.split([',', '\n'])
+            .map(str::to_string)
+            .collect()
+    });
+
+    items
+        .iter()
+        .map(|item| normalize(item))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn parse_bool(text: &str) -> Option<bool> {
+    match normalize(text).as_str() {
+        "true" | "t" | "yes" | "y" | "1" => Some(true),
+        "false" | "f" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim().replace('_', "").parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// The whole quiz may take as long as its questions' limits combined; `None` when no
+/// question has a limit.
+pub fn session_deadline(started_at: DateTime<Utc>, questions: &[QuizQuestion]) -> Option<DateTime<Utc>> {
+    let total: i64 = questions
+        .iter()
+        .map(|q| i64::from(q.time_limit_seconds.max(0)))
+        .sum();
+
+    (total > 0).then(|| started_at + Duration::seconds(total))
+}
+
+pub fn is_past_deadline(deadline_at: Option<&str>, now: DateTime<Utc>) -> bool {
+    deadline_at
+        .and_then(|deadline| DateTime::parse_from_rfc3339(deadline).ok())
+        .is_some_and(|deadline| {
+            now > deadline.with_timezone(&Utc) + Duration::seconds(DEADLINE_GRACE_SECONDS)
+        })
+}
+
+pub fn score_percent(points_earned: f64, points_possible: i32) -> i32 {
+    if points_possible <= 0 {
+        return 0;
+    }
+    ((100.0 * points_earned / f64::from(points_possible)).round() as i32).clamp(0, 100)
+}
+
+/// Scales a quiz percentage onto the rubric's quiz dimension.
+pub fn quiz_dimension_score(percent: i32, dimensions: &[RubricDimension]) -> i32 {
+    let max_points = dimensions
+        .iter()
+        .find(|d| d.key == QUIZ_DIMENSION_KEY)
+        .map_or(LEGACY_QUIZ_MAX, |d| d.max_points);
+
+    (f64::from(percent) * f64::from(max_points) / 100.0).round() as i32
+}
+
+/// Percentage from the most recent finished quiz on the attempt that had anything to grade.
+pub async fn latest_quiz_percent(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Option<i32>, AppError> {
+    let percent = sqlx::query_scalar(
+        "SELECT score_percent FROM quiz_sessions
+         WHERE day_attempt_id = ? AND status IN ('submitted', 'expired') AND points_possible > 0
+         ORDER BY submitted_at DESC
+         LIMIT 1",
+    )
+    .bind(day_attempt_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(percent)
+}
@@ -0,0 +1,232 @@
+use crate::db::models::{QueuedReview, ReviewForecastDay, ReviewSession};
+use crate::error::AppError;
+use crate::services::scheduler::{retrievability, SchedulerConfig};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::collections::{BTreeMap, VecDeque};
+
+/// How much being overdue counts next to predicted forgetting. The overdue ratio (days
+/// overdue over the interval) is capped, so a long break doesn't drown out recall.
+const OVERDUE_WEIGHT: f64 = 0.5;
+const MAX_OVERDUE_RATIO: f64 = 2.0;
+pub const DEFAULT_FORECAST_DAYS: i32 = 30;
+pub const MAX_FORECAST_DAYS: i32 = 365;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct DueItem {
+    id: String,
+    day_plan_id: String,
+    day_title: String,
+    day_number: i32,
+    concept_tag_id: String,
+    concept_name: String,
+    domain: String,
+    next_review_date: String,
+    interval_days: i32,
+    last_review_date: String,
+    stability: Option<f64>,
+    difficulty: Option<f64>,
+}
+
+fn date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Items that predict the lowest recall and are furthest past their interval come first.
+pub fn priority(predicted_recall: f64, overdue_days: i32, interval_days: i32) -> f64 {
+    let overdue_ratio = f64::from(overdue_days.max(0)) / f64::from(interval_days.max(1));
+    (1.0 - predicted_recall) + OVERDUE_WEIGHT * overdue_ratio.min(MAX_OVERDUE_RATIO)
+}
+
+/// Concept and quiz card reviews of `program_id` on `day` (`YYYY-MM-DD`). Both kinds share
+/// the daily review cap.
+pub async fn reviewed_on(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    day: &str,
+) -> Result<i32, AppError> {
+    let count = sqlx::query_scalar(
+        "SELECT
+            (SELECT COUNT(*) FROM review_log rl
+             JOIN day_plans dp ON rl.day_plan_id = dp.id
+             WHERE dp.program_id = ?1 AND substr(rl.reviewed_at, 1, 10) = ?2)
+          + (SELECT COUNT(*) FROM quiz_card_reviews r
+             JOIN quiz_cards qc ON qc.id = r.quiz_card_id
+             JOIN day_plans dp ON dp.id = qc.day_plan_id
+             WHERE dp.program_id = ?1 AND substr(r.reviewed_at, 1, 10) = ?2)",
+    )
+    .bind(program_id)
+    .bind(day)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(count)
+}
+
+/// Today's reviews for `program_id`: everything due, ranked by priority, cut to what's left
+/// of the daily cap and ordered so consecutive reviews come from different concept domains
+/// where possible.
+pub async fn build_session(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<ReviewSession, AppError> {
+    let today = now.date_naive();
+    let today_str = today.format("%Y-%m-%d").to_string();
+
+    let due = sqlx::query_as::<_, DueItem>(
+        "SELECT
+            sr.id, sr.day_plan_id, dp.title as day_title, dp.day_number,
+            sr.concept_tag_id, ct.name as concept_name, ct.domain,
+            sr.next_review_date, sr.interval_days, sr.last_review_date,
+            sr.stability, sr.difficulty
+         FROM spaced_repetition sr
+         JOIN day_plans dp ON sr.day_plan_id = dp.id
+         JOIN concept_tags ct ON sr.concept_tag_id = ct.id
+         WHERE dp.program_id = ? AND sr.next_review_date <= ?",
+    )
+    .bind(program_id)
+    .bind(&today_str)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let reviewed_today = reviewed_on(conn, program_id, &today_str).await?;
+
+    let mut queued: Vec<QueuedReview> = due
+        .into_iter()
+        .map(|item| {
+            let memory = config.memory_state(item.stability, item.difficulty, item.interval_days);
+            let elapsed = date(&item.last_review_date).map_or(0, |last| (today - last).num_days());
+            let overdue_days = date(&item.next_review_date)
+                .map_or(0, |due| (today - due).num_days().max(0)) as i32;
+            let predicted_recall = retrievability(elapsed as f64, memory.stability);
+
+            QueuedReview {
+                priority: priority(predicted_recall, overdue_days, item.interval_days),
+                id: item.id,
+                day_plan_id: item.day_plan_id,
+                day_title: item.day_title,
+                day_number: item.day_number,
+                concept_tag_id: item.concept_tag_id,
+                concept_name: item.concept_name,
+                domain: item.domain,
+                next_review_date: item.next_review_date,
+                interval_days: item.interval_days,
+                overdue_days,
+                predicted_recall,
+            }
+        })
+        .collect();
+    queued.sort_by(|a, b| {
+        b.priority
+            .total_cmp(&a.priority)
+            .then_with(|| a.next_review_date.cmp(&b.next_review_date))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let due_count = queued.len() as i32;
+    let remaining = (config.daily_review_cap - reviewed_today).max(0);
+    queued.truncate(remaining as usize);
+
+    Ok(ReviewSession {
+        program_id: program_id.to_string(),
+        date: today_str,
+        daily_review_cap: config.daily_review_cap,
+        reviewed_today,
+        due_count,
+        deferred_count: due_count - queued.len() as i32,
+        reviews: interleave(queued),
+    })
+}
+
+/// Greedily takes the highest priority review whose domain differs from the previous one,
+/// falling back to the same domain only when nothing else is left. `reviews` must be
+/// sorted by priority.
+pub fn interleave(reviews: Vec<QueuedReview>) -> Vec<QueuedReview> {
+    let mut by_domain: Vec<VecDeque<QueuedReview>> = Vec::new();
+    for review in reviews {
+        match by_domain.iter_mut().find(|queue| queue[0].domain == review.domain) {
+            Some(queue) => queue.push_back(review),
+            None => by_domain.push(VecDeque::from([review])),
+        }
+    }
+
+    let mut ordered = Vec::new();
+    let mut last_domain: Option<String> = None;
+    loop {
+        let next = by_domain
+            .iter()
+            .enumerate()
+            .filter(|(_, queue)| !queue.is_empty())
+            .filter(|(_, queue)| last_domain.as_deref() != Some(queue[0].domain.as_str()))
+            .max_by(|(_, a), (_, b)| a[0].priority.total_cmp(&b[0].priority))
+            .map(|(index, _)| index)
+            .or_else(|| by_domain.iter().position(|queue| !queue.is_empty()));
+        let Some(index) = next else { break };
+
+        let review = by_domain[index].pop_front().unwrap();
+        last_domain = Some(review.domain.clone());
+        ordered.push(review);
+    }
+
+    ordered
+}
+
+/// Due counts for `program_id` over the next `days` days, active quiz cards included, with
+/// the backlog left if the daily cap is reviewed every day.
+pub async fn forecast(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    program_id: &str,
+    days: i32,
+    now: DateTime<Utc>,
+) -> Result<Vec<ReviewForecastDay>, AppError> {
+    let today = now.date_naive();
+    let last_day = today + chrono::Duration::days(i64::from(days - 1));
+
+    let rows: Vec<(String, i32)> = sqlx::query_as(
+        "SELECT sr.next_review_date, COUNT(*)
+         FROM spaced_repetition sr
+         JOIN day_plans dp ON sr.day_plan_id = dp.id
+         WHERE dp.program_id = ?1 AND sr.next_review_date <= ?2
+         GROUP BY sr.next_review_date
+         UNION ALL
+         SELECT qc.next_review_date, COUNT(*)
+         FROM quiz_cards qc
+         JOIN day_plans dp ON qc.day_plan_id = dp.id
+         WHERE dp.program_id = ?1 AND qc.status = 'active' AND qc.next_review_date <= ?2
+         GROUP BY qc.next_review_date",
+    )
+    .bind(program_id)
+    .bind(last_day.format("%Y-%m-%d").to_string())
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut due_by_day: BTreeMap<NaiveDate, i32> = BTreeMap::new();
+    for (due_date, count) in rows {
+        if let Some(due) = date(&due_date) {
+            *due_by_day.entry(due.max(today)).or_default() += count;
+        }
+    }
+
+    let mut backlog = 0;
+    let forecast = (0..days)
+        .map(|offset| {
+            let day = today + chrono::Duration::days(i64::from(offset));
+            let due_count = due_by_day.get(&day).copied().unwrap_or(0);
+            let waiting = backlog + due_count;
+            let projected_reviews = waiting.min(config.daily_review_cap);
+            backlog = waiting - projected_reviews;
+
+            ReviewForecastDay {
+                date: day.format("%Y-%m-%d").to_string(),
+                due_count,
+                projected_reviews,
+                projected_backlog: backlog,
+            }
+        })
+        .collect();
+
+    Ok(forecast)
+}
@@ -0,0 +1,571 @@
+use crate::db::models::{RecordReviewInput, ReviewSchedulerSettings, SpacedRepetition};
+use crate::error::AppError;
+use crate::services::settings::setting_or;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+use uuid::Uuid;
+
+pub const SCHEDULER_SETTING: &str = "review_scheduler";
+pub const DESIRED_RETENTION_SETTING: &str = "fsrs_desired_retention";
+pub const MAXIMUM_INTERVAL_SETTING: &str = "review_maximum_interval_days";
+pub const DAILY_REVIEW_CAP_SETTING: &str = "daily_review_cap";
+/// Absent until parameters have been fitted to the review log
+pub const PARAMETERS_SETTING: &str = "fsrs_parameters";
+
+pub const DEFAULT_DESIRED_RETENTION: f64 = 0.9;
+pub const DESIRED_RETENTION_RANGE: RangeInclusive<f64> = 0.7..=0.97;
+pub const DEFAULT_MAXIMUM_INTERVAL_DAYS: i32 = 365;
+pub const MAXIMUM_INTERVAL_RANGE: RangeInclusive<i32> = 7..=36500;
+pub const DEFAULT_DAILY_REVIEW_CAP: i32 = 30;
+pub const DAILY_REVIEW_CAP_RANGE: RangeInclusive<i32> = 1..=500;
+
+/// FSRS-4.5 defaults, fitted by its authors on a large body of Anki reviews.
+pub const DEFAULT_PARAMETERS: [f64; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461,
+    2.1072, 0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+/// Shape of the FSRS-4.5 forgetting curve. `FACTOR` makes recall exactly 90% after
+/// `stability` days.
+const DECAY: f64 = -0.5;
+const FACTOR: f64 = 19.0 / 81.0;
+const STABILITY_RANGE: RangeInclusive<f64> = 0.1..=36500.0;
+/// How far a due date may move either way, as (interval from, interval to, share of the
+/// interval in that span), on top of one day. Same ranges as FSRS's own fuzz.
+const FUZZ_RANGES: [(f64, f64, f64); 3] = [(2.5, 7.0, 0.15), (7.0, 20.0, 0.1), (20.0, f64::MAX, 0.05)];
+
+pub const AGAIN: i32 = 1;
+pub const HARD: i32 = 2;
+pub const GOOD: i32 = 3;
+pub const EASY: i32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerKind {
+    Sm2,
+    Fsrs,
+}
+
+impl SchedulerKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sm2" => Some(SchedulerKind::Sm2),
+            "fsrs" => Some(SchedulerKind::Fsrs),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchedulerKind::Sm2 => "sm2",
+            SchedulerKind::Fsrs => "fsrs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryState {
+    pub stability: f64,
+    pub difficulty: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    pub kind: SchedulerKind,
+    pub desired_retention: f64,
+    pub maximum_interval_days: i32,
+    pub daily_review_cap: i32,
+    pub parameters: [f64; 17],
+    pub parameters_fitted: bool,
+}
+
+/// What a scheduler needs to know about an item's previous review.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorReview {
+    pub easiness_factor: f64,
+    pub interval_days: i32,
+    pub repetition_count: i32,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+}
+
+impl From<&SpacedRepetition> for PriorReview {
+    fn from(sr: &SpacedRepetition) -> Self {
+        PriorReview {
+            easiness_factor: sr.easiness_factor,
+            interval_days: sr.interval_days,
+            repetition_count: sr.repetition_count,
+            stability: sr.stability,
+            difficulty: sr.difficulty,
+        }
+    }
+}
+
+/// The new state of a reviewed item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledReview {
+    pub rating: i32,
+    pub easiness_factor: f64,
+    pub interval_days: i32,
+    pub repetition_count: i32,
+    pub memory: MemoryState,
+}
+
+impl SchedulerConfig {
+    /// Reads the scheduler settings, falling back to SM-2 and the defaults for anything
+    /// missing or out of range.
+    pub async fn load(conn: &mut SqliteConnection) -> Result<Self, AppError> {
+        let kind = setting_or(conn, SCHEDULER_SETTING, "sm2".to_string()).await?;
+        let desired_retention =
+            setting_or(conn, DESIRED_RETENTION_SETTING, DEFAULT_DESIRED_RETENTION).await?;
+        let maximum_interval_days =
+            setting_or(conn, MAXIMUM_INTERVAL_SETTING, DEFAULT_MAXIMUM_INTERVAL_DAYS).await?;
+        let daily_review_cap =
+            setting_or(conn, DAILY_REVIEW_CAP_SETTING, DEFAULT_DAILY_REVIEW_CAP).await?;
+        let fitted: Option<Vec<f64>> = setting_or(conn, PARAMETERS_SETTING, None).await?;
+        let fitted = fitted.and_then(|parameters| <[f64; 17]>::try_from(parameters).ok());
+
+        Ok(SchedulerConfig {
+            kind: SchedulerKind::parse(&kind).unwrap_or(SchedulerKind::Sm2),
+            desired_retention: if DESIRED_RETENTION_RANGE.contains(&desired_retention) {
+                desired_retention
+            } else {
+                DEFAULT_DESIRED_RETENTION
+            },
+            maximum_interval_days: if MAXIMUM_INTERVAL_RANGE.contains(&maximum_interval_days) {
+                maximum_interval_days
+            } else {
+                DEFAULT_MAXIMUM_INTERVAL_DAYS
+            },
+            daily_review_cap: if DAILY_REVIEW_CAP_RANGE.contains(&daily_review_cap) {
+                daily_review_cap
+            } else {
+                DEFAULT_DAILY_REVIEW_CAP
+            },
+            parameters: fitted.unwrap_or(DEFAULT_PARAMETERS),
+            parameters_fitted: fitted.is_some(),
+        })
+    }
+
+    pub fn settings(&self) -> ReviewSchedulerSettings {
+        ReviewSchedulerSettings {
+            scheduler: self.kind.as_str().to_string(),
+            desired_retention: self.desired_retention,
+            maximum_interval_days: self.maximum_interval_days,
+            daily_review_cap: self.daily_review_cap,
+            fsrs_parameters: self.parameters.to_vec(),
+            parameters_fitted: self.parameters_fitted,
+        }
+    }
+
+    /// Schedules the next review of an item scored `score` after `elapsed_days`. Both SM-2 and
+    /// FSRS state are advanced; the selected scheduler picks the interval.
+    pub fn review(
+        &self,
+        previous: Option<&PriorReview>,
+        score: i32,
+        elapsed_days: i64,
+    ) -> ScheduledReview {
+        let rating = rating_for_score(score);
+        let memory = next_memory_state(
+            &self.parameters,
+            previous.map(|prior| self.memory_state(prior.stability, prior.difficulty, prior.interval_days)),
+            elapsed_days.max(0) as f64,
+            rating,
+        );
+
+        let (easiness_factor, sm2_interval, sm2_count) = match previous {
+            Some(prior) => {
+                let quality = sm2_quality(score);
+                let count = if quality >= 3 { prior.repetition_count + 1 } else { 0 };
+                (
+                    sm2_easiness_factor(prior.easiness_factor, quality),
+                    sm2_interval(prior.interval_days, prior.repetition_count, quality),
+                    count,
+                )
+            }
+            // First review
+            None => (2.5, 1, 1),
+        };
+
+        let (interval_days, repetition_count) = match self.kind {
+            SchedulerKind::Sm2 => (sm2_interval.clamp(1, self.maximum_interval_days), sm2_count),
+            SchedulerKind::Fsrs => {
+                let count = match previous {
+                    Some(prior) if rating > AGAIN => prior.repetition_count + 1,
+                    Some(_) => 0,
+                    None => 1,
+                };
+                (self.interval(memory.stability), count)
+            }
+        };
+
+        ScheduledReview {
+            rating,
+            easiness_factor,
+            interval_days,
+            repetition_count,
+            memory,
+        }
+    }
+
+    /// Days until recall is predicted to drop to the desired retention.
+    pub fn interval(&self, stability: f64) -> i32 {
+        let days = stability / FACTOR * (self.desired_retention.powf(1.0 / DECAY) - 1.0);
+        (days.round() as i32).clamp(1, self.maximum_interval_days)
+    }
+
+    /// The item's FSRS state. Items last reviewed before FSRS state was kept start from their
+    /// SM-2 interval, which is about the time they were expected to stay recallable.
+    pub fn memory_state(
+        &self,
+        stability: Option<f64>,
+        difficulty: Option<f64>,
+        interval_days: i32,
+    ) -> MemoryState {
+        match (stability, difficulty) {
+            (Some(stability), Some(difficulty)) => MemoryState { stability, difficulty },
+            _ => MemoryState {
+                stability: f64::from(interval_days.max(1)),
+                difficulty: initial_difficulty(&self.parameters, GOOD),
+            },
+        }
+    }
+}
+
+/// Maps a 0-100 review score to an FSRS rating. Anything below the blocked threshold is a
+/// lapse, mastery is easy.
+pub fn rating_for_score(score: i32) -> i32 {
+    match score {
+        i32::MIN..=69 => AGAIN,
+        70..=84 => HARD,
+        85..=94 => GOOD,
+        _ => EASY,
+    }
+}
+
+/// Probability of recalling an item `elapsed_days` after its last review.
+pub fn retrievability(elapsed_days: f64, stability: f64) -> f64 {
+    (1.0 + FACTOR * elapsed_days.max(0.0) / stability).powf(DECAY)
+}
+
+fn initial_difficulty(parameters: &[f64; 17], rating: i32) -> f64 {
+    (parameters[4] - f64::from(rating - GOOD) * parameters[5]).clamp(1.0, 10.0)
+}
+
+/// FSRS-4.5 memory state after a review rated `rating`, `elapsed_days` after the previous one.
+pub fn next_memory_state(
+    parameters: &[f64; 17],
+    previous: Option<MemoryState>,
+    elapsed_days: f64,
+    rating: i32,
+) -> MemoryState {
+    let w = parameters;
+    let rating = rating.clamp(AGAIN, EASY);
+    let Some(previous) = previous else {
+        return MemoryState {
+            stability: w[(rating - 1) as usize].clamp(*STABILITY_RANGE.start(), *STABILITY_RANGE.end()),
+            difficulty: initial_difficulty(w, rating),
+        };
+    };
+
+    let recall = retrievability(elapsed_days, previous.stability);
+    let (s, d) = (previous.stability, previous.difficulty);
+
+    let stability = if rating == AGAIN {
+        let forgotten = w[11]
+            * d.powf(-w[12])
+            * ((s + 1.0).powf(w[13]) - 1.0)
+            * (w[14] * (1.0 - recall)).exp();
+        forgotten.min(s)
+    } else {
+        let hard_penalty = if rating == HARD { w[15] } else { 1.0 };
+        let easy_bonus = if rating == EASY { w[16] } else { 1.0 };
+        s * (1.0
+            + w[8].exp()
+                * (11.0 - d)
+                * s.powf(-w[9])
+                * ((w[10] * (1.0 - recall)).exp() - 1.0)
+                * hard_penalty
+                * easy_bonus)
+    };
+    // Mean reversion towards the difficulty of a first "good" review
+    let difficulty = w[7] * initial_difficulty(w, GOOD)
+        + (1.0 - w[7]) * (d - w[6] * f64::from(rating - GOOD));
+
+    MemoryState {
+        stability: stability.clamp(*STABILITY_RANGE.start(), *STABILITY_RANGE.end()),
+        difficulty: difficulty.clamp(1.0, 10.0),
+    }
+}
+
+/// SM-2 grade (0-5) for a 0-100 score.
+pub fn sm2_quality(score: i32) -> i32 {
+    match score {
+        i32::MIN..=49 => 0,
+        50..=59 => 1,
+        60..=69 => 2,
+        70..=79 => 3,
+        80..=89 => 4,
+        _ => 5,
+    }
+}
+
+pub fn sm2_easiness_factor(current_ef: f64, quality: i32) -> f64 {
+    let miss = 5.0 - f64::from(quality);
+    (current_ef + (0.1 - miss * (0.08 + miss * 0.02))).max(1.3)
+}
+
+pub fn sm2_interval(current_interval: i32, repetition_count: i32, quality: i32) -> i32 {
+    if quality < 3 || repetition_count == 0 {
+        1
+    } else if repetition_count == 1 {
+        6
+    } else {
+        (f64::from(current_interval) * 2.5) as i32
+    }
+}
+
+/// The shortest and longest interval a scheduled interval may be moved to.
+pub fn fuzz_range(interval_days: i32, maximum_interval_days: i32) -> (i32, i32) {
+    let interval = f64::from(interval_days);
+    if interval < FUZZ_RANGES[0].0 {
+        return (interval_days, interval_days);
+    }
+    let delta = 1.0
+        + FUZZ_RANGES
+            .iter()
+            .map(|(from, to, share)| share * (interval.min(*to) - from).max(0.0))
+            .sum::<f64>();
+    let shortest = ((interval - delta).round() as i32).max(2);
+    let longest = ((interval + delta).round() as i32).min(maximum_interval_days);
+    (shortest.min(longest), longest)
+}
+
+/// Moves an item's next review within its fuzz range to the day with the fewest reviews
+/// already due, counting concept reviews and quiz cards alike, so items reviewed together
+/// don't all come due together again. Ties are broken by a hash of `item_key`, which keeps
+/// the choice stable but different per item.
+pub async fn spread_interval(
+    conn: &mut SqliteConnection,
+    item_key: &str,
+    interval_days: i32,
+    maximum_interval_days: i32,
+    now: DateTime<Utc>,
+) -> Result<i32, AppError> {
+    let (shortest, longest) = fuzz_range(interval_days, maximum_interval_days);
+    if shortest == longest {
+        return Ok(interval_days);
+    }
+
+    let due_date = |days: i32| {
+        (now + chrono::Duration::days(i64::from(days)))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    let load: HashMap<String, i64> = sqlx::query_as(
+        "SELECT next_review_date, COUNT(*) FROM (
+            SELECT next_review_date FROM spaced_repetition
+            UNION ALL
+            SELECT next_review_date FROM quiz_cards WHERE status = 'active'
+         )
+         WHERE next_review_date BETWEEN ? AND ?
+         GROUP BY next_review_date",
+    )
+    .bind(due_date(shortest))
+    .bind(due_date(longest))
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+
+    let tie_break = |days: i32| {
+        let mut hasher = DefaultHasher::new();
+        (item_key, days).hash(&mut hasher);
+        hasher.finish()
+    };
+    let spread = (shortest..=longest)
+        .min_by_key(|days| (load.get(&due_date(*days)).copied().unwrap_or(0), tie_break(*days)))
+        .unwrap_or(interval_days);
+
+    Ok(spread)
+}
+
+/// Whole days between the item's last review and `now`; 0 for a new item.
+pub fn elapsed_days(last_review_date: Option<&str>, now: DateTime<Utc>) -> i64 {
+    last_review_date
+        .and_then(|date| date.get(..10))
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .map_or(0, |last| (now.date_naive() - last).num_days().max(0))
+}
+
+/// Schedules the reviewed item, spreads its due date, stores its new state and appends the
+/// review to the log.
+pub async fn record_review(
+    conn: &mut SqliteConnection,
+    config: &SchedulerConfig,
+    input: &RecordReviewInput,
+    now: DateTime<Utc>,
+) -> Result<SpacedRepetition, AppError> {
+    if !(0..=100).contains(&input.score) {
+        return Err(AppError::Validation(format!(
+            "Review score must be between 0 and 100, got {}",
+            input.score
+        )));
+    }
+
+    let existing = sqlx::query_as::<_, SpacedRepetition>(
+        "SELECT * FROM spaced_repetition WHERE day_plan_id = ? AND concept_tag_id = ?",
+    )
+    .bind(&input.day_plan_id)
+    .bind(&input.concept_tag_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let elapsed_days = elapsed_days(existing.as_ref().map(|sr| sr.last_review_date.as_str()), now);
+    let prior = existing.as_ref().map(PriorReview::from);
+    let mut scheduled = config.review(prior.as_ref(), input.score, elapsed_days);
+    scheduled.interval_days = spread_interval(
+        conn,
+        &format!("{}:{}", input.day_plan_id, input.concept_tag_id),
+        scheduled.interval_days,
+        config.maximum_interval_days,
+        now,
+    )
+    .await?;
+
+    let timestamp = now.to_rfc3339();
+    let today = now.format("%Y-%m-%d").to_string();
+    let next_review = (now + chrono::Duration::days(i64::from(scheduled.interval_days)))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let sr = sqlx::query_as::<_, SpacedRepetition>(
+        "INSERT INTO spaced_repetition (
+            id, day_plan_id, concept_tag_id, easiness_factor, interval_days,
+            repetition_count, last_review_date, next_review_date, last_score,
+            stability, difficulty, created_at, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(day_plan_id, concept_tag_id) DO UPDATE SET
+            easiness_factor = excluded.easiness_factor,
+            interval_days = excluded.interval_days,
+            repetition_count = excluded.repetition_count,
+            last_review_date = excluded.last_review_date,
+            next_review_date = excluded.next_review_date,
+            last_score = excluded.last_score,
+            stability = excluded.stability,
+            difficulty = excluded.difficulty,
+            updated_at = excluded.updated_at
+        RETURNING *",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&input.day_plan_id)
+    .bind(&input.concept_tag_id)
+    .bind(scheduled.easiness_factor)
+    .bind(scheduled.interval_days)
+    .bind(scheduled.repetition_count)
+    .bind(&today)
+    .bind(&next_review)
+    .bind(input.score)
+    .bind(scheduled.memory.stability)
+    .bind(scheduled.memory.difficulty)
+    .bind(&timestamp)
+    .bind(&timestamp)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO review_log (
+            id, day_plan_id, concept_tag_id, scheduler, score, rating, elapsed_days,
+            scheduled_days, stability, difficulty, reviewed_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&input.day_plan_id)
+    .bind(&input.concept_tag_id)
+    .bind(config.kind.as_str())
+    .bind(input.score)
+    .bind(scheduled.rating)
+    .bind(elapsed_days)
+    .bind(scheduled.interval_days)
+    .bind(scheduled.memory.stability)
+    .bind(scheduled.memory.difficulty)
+    .bind(&timestamp)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(sr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(desired_retention: f64) -> SchedulerConfig {
+        SchedulerConfig {
+            kind: SchedulerKind::Fsrs,
+            desired_retention,
+            maximum_interval_days: DEFAULT_MAXIMUM_INTERVAL_DAYS,
+            daily_review_cap: DEFAULT_DAILY_REVIEW_CAP,
+            parameters: DEFAULT_PARAMETERS,
+            parameters_fitted: false,
+        }
+    }
+
+    #[test]
+    fn first_review_stability_follows_the_rating() {
+        for rating in AGAIN..=EASY {
+            let state = next_memory_state(&DEFAULT_PARAMETERS, None, 0.0, rating);
+            assert_eq!(state.stability, DEFAULT_PARAMETERS[(rating - 1) as usize]);
+            assert_eq!(state.difficulty, initial_difficulty(&DEFAULT_PARAMETERS, rating));
+        }
+
+        let again = next_memory_state(&DEFAULT_PARAMETERS, None, 0.0, AGAIN);
+        let easy = next_memory_state(&DEFAULT_PARAMETERS, None, 0.0, EASY);
+        assert!(again.stability < easy.stability);
+        assert!(again.difficulty > easy.difficulty);
+    }
+
+    #[test]
+    fn lapse_never_raises_stability() {
+        for stability in [0.5, 3.0, 20.0, 200.0] {
+            for elapsed_days in [0.0, 1.0, stability, stability * 4.0] {
+                let previous = MemoryState { stability, difficulty: 5.0 };
+                let lapsed =
+                    next_memory_state(&DEFAULT_PARAMETERS, Some(previous), elapsed_days, AGAIN);
+                assert!(lapsed.stability <= stability);
+            }
+        }
+    }
+
+    #[test]
+    fn successful_review_raises_stability() {
+        let previous = MemoryState { stability: 10.0, difficulty: 5.0 };
+        for rating in HARD..=EASY {
+            let next = next_memory_state(&DEFAULT_PARAMETERS, Some(previous), 10.0, rating);
+            assert!(next.stability > previous.stability);
+        }
+    }
+
+    #[test]
+    fn interval_reaches_the_desired_retention() {
+        // FACTOR is chosen so recall is 90% after exactly `stability` days
+        assert_eq!(config(0.9).interval(10.0), 10);
+
+        for desired_retention in [0.75, 0.85, 0.95] {
+            let stability = 40.0;
+            let interval = config(desired_retention).interval(stability);
+            let recall = retrievability(f64::from(interval), stability);
+            assert!((recall - desired_retention).abs() < 0.01, "{} at {}", recall, interval);
+        }
+    }
+
+    #[test]
+    fn interval_stays_within_bounds() {
+        let config = config(0.9);
+        assert_eq!(config.interval(0.1), 1);
+        assert_eq!(config.interval(10_000.0), config.maximum_interval_days);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::db::models::{
+    AnalysisMetrics, AnalyzerFinding, AttemptScoreSuggestion, RubricDimension,
+};
+use crate::error::AppError;
+use crate::services::code_analyzer::Analysis;
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+pub const CODE_QUALITY_KEY: &str = "code_quality";
+pub const PERFORMANCE_KEY: &str = "performance";
+
+/// Penalty points that take a dimension from full marks to zero.
+const QUALITY_BUDGET: f64 = 10.0;
+const PERFORMANCE_BUDGET: f64 = 5.0;
+/// Findings are counted per this many lines so a big project isn't punished for its size.
+const LINES_PER_UNIT: f64 = 100.0;
+/// Branch points per code line above which the code reads as tangled.
+const BRANCH_DENSITY_LIMIT: f64 = 0.25;
+const NESTING_LIMIT: usize = 4;
+const LOOP_NESTING_LIMIT: usize = 2;
+/// Findings kept on each suggestion; the counts in the explanation cover the rest.
+const MAX_STORED_FINDINGS: usize = 100;
+
+struct Suggestion {
+    dimension_key: &'static str,
+    fraction: f64,
+    explanation: String,
+    findings: Vec<AnalyzerFinding>,
+}
+
+/// Turns an analysis into suggested scores for whichever of code quality and performance
+/// the rubric scores, replacing earlier suggestions for the attempt.
+pub async fn store(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+    analysis: &Analysis,
+    dimensions: &[RubricDimension],
+) -> Result<Vec<AttemptScoreSuggestion>, AppError> {
+    let suggestions: Vec<(Suggestion, &RubricDimension)> =
+        [quality_suggestion(analysis), performance_suggestion(analysis)]
+            .into_iter()
+            .filter_map(|suggestion| {
+                let dimension = dimensions.iter().find(|d| d.key == suggestion.dimension_key)?;
+                Some((suggestion, dimension))
+            })
+            .collect();
+    if suggestions.is_empty() {
+        return Err(AppError::Validation(
+            "The rubric has no code quality or performance dimension to suggest scores for"
+                .to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let metrics_json = serde_json::to_string(&analysis.metrics)?;
+
+    sqlx::query("DELETE FROM attempt_score_suggestions WHERE day_attempt_id = ?")
+        .bind(day_attempt_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let mut stored = Vec::new();
+    for (mut suggestion, dimension) in suggestions {
+        suggestion.findings.truncate(MAX_STORED_FINDINGS);
+        let score = (suggestion.fraction * f64::from(dimension.max_points)).round() as i32;
+
+        let row = sqlx::query_as::<_, AttemptScoreSuggestion>(
+            "INSERT INTO attempt_score_suggestions (
+                id, day_attempt_id, dimension_key, suggested_score, max_points, explanation,
+                findings_json, metrics_json, status, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'pending', ?)
+            RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(day_attempt_id)
+        .bind(suggestion.dimension_key)
+        .bind(score.clamp(0, dimension.max_points))
+        .bind(dimension.max_points)
+        .bind(&suggestion.explanation)
+        .bind(serde_json::to_string(&suggestion.findings)?)
+        .bind(&metrics_json)
+        .bind(&now)
+        .fetch_one(&mut *conn)
+        .await?;
+        stored.push(row);
+    }
+
+    Ok(stored)
+}
+
+fn quality_suggestion(analysis: &Analysis) -> Suggestion {
+    let metrics = &analysis.metrics;
+    let findings: Vec<AnalyzerFinding> = analysis
+        .findings
+        .iter()
+        .filter(|f| f.category == "quality")
+        .cloned()
+        .collect();
+    let mut reasons = Vec::new();
+    let mut penalty = 0.0;
+
+    let errors = findings.iter().filter(|f| f.severity == "error").count();
+    let warnings = findings.len() - errors;
+    if findings.is_empty() {
+        if !metrics.tools_run.is_empty() {
+            reasons.push(format!("{} reported nothing.", metrics.tools_run.join(", ")));
+        }
+    } else {
+        let lint_penalty =
+            (errors as f64 + 0.5 * warnings as f64) * per_unit(metrics.code_lines);
+        penalty += lint_penalty;
+        reasons.push(format!(
+            "{} error(s) and {} warning(s) from {} over {} lines (-{:.1}).",
+            errors,
+            warnings,
+            tools_of(&findings),
+            metrics.code_lines,
+            lint_penalty
+        ));
+    }
+
+    let density = metrics.branches as f64 / metrics.code_lines.max(1) as f64;
+    if density > BRANCH_DENSITY_LIMIT {
+        let density_penalty = (density - BRANCH_DENSITY_LIMIT) * 20.0;
+        penalty += density_penalty;
+        reasons.push(format!(
+            "{:.2} branch points per line is above {:.2} (-{:.1}).",
+            density, BRANCH_DENSITY_LIMIT, density_penalty
+        ));
+    }
+    if metrics.max_nesting > NESTING_LIMIT {
+        let nesting_penalty = (metrics.max_nesting - NESTING_LIMIT) as f64;
+        penalty += nesting_penalty;
+        reasons.push(format!(
+            "Blocks nest {} deep, past {} (-{:.1}).",
+            metrics.max_nesting, NESTING_LIMIT, nesting_penalty
+        ));
+    }
+
+    Suggestion {
+        dimension_key: CODE_QUALITY_KEY,
+        fraction: fraction(penalty, QUALITY_BUDGET),
+        explanation: explanation(reasons, metrics),
+        findings,
+    }
+}
+
+fn performance_suggestion(analysis: &Analysis) -> Suggestion {
+    let metrics = &analysis.metrics;
+    let findings: Vec<AnalyzerFinding> = analysis
+        .findings
+        .iter()
+        .filter(|f| f.category == "performance")
+        .cloned()
+        .collect();
+    let mut reasons = Vec::new();
+    let mut penalty = 0.0;
+
+    if !findings.is_empty() {
+        let lint_penalty = findings.len() as f64 * per_unit(metrics.code_lines);
+        penalty += lint_penalty;
+        reasons.push(format!(
+            "{} performance finding(s) from {} (-{:.1}).",
+            findings.len(),
+            tools_of(&findings),
+            lint_penalty
+        ));
+    } else if !metrics.tools_run.is_empty() {
+        reasons.push("No performance lints fired.".to_string());
+    }
+    if metrics.max_loop_nesting > LOOP_NESTING_LIMIT {
+        let loop_penalty = 2.0 * (metrics.max_loop_nesting - LOOP_NESTING_LIMIT) as f64;
+        penalty += loop_penalty;
+        reasons.push(format!(
+            "Loops nest {} deep, which tends to mean superlinear work (-{:.1}).",
+            metrics.max_loop_nesting, loop_penalty
+        ));
+    }
+
+    Suggestion {
+        dimension_key: PERFORMANCE_KEY,
+        fraction: fraction(penalty, PERFORMANCE_BUDGET),
+        explanation: explanation(reasons, metrics),
+        findings,
+    }
+}
+
+/// Scales a finding count to "per 100 lines", never inflating it for tiny files.
+fn per_unit(code_lines: usize) -> f64 {
+    LINES_PER_UNIT / (code_lines as f64).max(LINES_PER_UNIT)
+}
+
+fn fraction(penalty: f64, budget: f64) -> f64 {
+    (1.0 - penalty / budget).clamp(0.0, 1.0)
+}
+
+fn tools_of(findings: &[AnalyzerFinding]) -> String {
+    let mut tools: Vec<&str> = findings.iter().map(|f| f.tool.as_str()).collect();
+    tools.sort_unstable();
+    tools.dedup();
+    tools.join(", ")
+}
+
+fn explanation(mut reasons: Vec<String>, metrics: &AnalysisMetrics) -> String {
+    if metrics.tools_run.is_empty() {
+        reasons.push("No linter could run, so only complexity metrics count.".to_string());
+    }
+    if !metrics.tools_skipped.is_empty() {
+        reasons.push(format!("Skipped: {}.", metrics.tools_skipped.join("; ")));
+    }
+    if reasons.is_empty() {
+        reasons.push("Nothing to deduct.".to_string());
+    }
+    reasons.join(" ")
+}
+
+pub async fn list(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<AttemptScoreSuggestion>, AppError> {
+    let suggestions = sqlx::query_as::<_, AttemptScoreSuggestion>(
+        "SELECT * FROM attempt_score_suggestions WHERE day_attempt_id = ? ORDER BY dimension_key",
+    )
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(suggestions)
+}
+
+/// Replaces submitted scores with the pending suggestions, rescaled if the rubric's max
+/// points changed since the analysis ran.
+pub fn accept(suggestions: &[AttemptScoreSuggestion], scores: &mut [(&RubricDimension, i32)]) {
+    for (dimension, score) in scores.iter_mut() {
+        let suggestion = suggestions
+            .iter()
+            .find(|s| s.status == "pending" && s.dimension_key == dimension.key);
+        if let Some(suggestion) = suggestion {
+            *score = scaled_score(suggestion, dimension);
+        }
+    }
+}
+
+fn scaled_score(suggestion: &AttemptScoreSuggestion, dimension: &RubricDimension) -> i32 {
+    let scaled = f64::from(suggestion.suggested_score) * f64::from(dimension.max_points)
+        / f64::from(suggestion.max_points.max(1));
+    (scaled.round() as i32).clamp(0, dimension.max_points)
+}
+
+/// Marks each pending suggestion accepted or overridden by comparing it with the score
+/// that was actually submitted.
+pub async fn resolve(
+    conn: &mut SqliteConnection,
+    suggestions: &[AttemptScoreSuggestion],
+    scores: &[(&RubricDimension, i32)],
+    now: &str,
+) -> Result<(), AppError> {
+    for suggestion in suggestions.iter().filter(|s| s.status == "pending") {
+        let Some((dimension, score)) =
+            scores.iter().find(|(d, _)| d.key == suggestion.dimension_key)
+        else {
+            continue;
+        };
+        let accepted = scaled_score(suggestion, dimension) == *score;
+
+        sqlx::query(
+            "UPDATE attempt_score_suggestions SET status = ?, final_score = ?, resolved_at = ?
+             WHERE id = ?",
+        )
+        .bind(if accepted { "accepted" } else { "overridden" })
+        .bind(score)
+        .bind(now)
+        .bind(&suggestion.id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use crate::db::models::{
+    RubricDimension, RubricDimensionInput, RubricWithDimensions, ScoringRubric, SubmitScoresInput,
+};
+use crate::error::AppError;
+use crate::services::attempt_state;
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_RUBRIC_ID: &str = "default-rubric";
+
+/// The program's own rubric, or the built-in default when it hasn't defined one.
+pub async fn rubric_for_program(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+) -> Result<RubricWithDimensions, AppError> {
+    let rubric = sqlx::query_as::<_, ScoringRubric>(
+        "SELECT * FROM scoring_rubrics
+         WHERE program_id = ? OR id = ?
+         ORDER BY program_id IS NULL
+         LIMIT 1",
+    )
+    .bind(program_id)
+    .bind(DEFAULT_RUBRIC_ID)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound {
+        entity: "ScoringRubric".to_string(),
+        id: program_id.to_string(),
+    })?;
+
+    let dimensions = sqlx::query_as::<_, RubricDimension>(
+        "SELECT * FROM rubric_dimensions WHERE rubric_id = ? ORDER BY order_index",
+    )
+    .bind(&rubric.id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(RubricWithDimensions { rubric, dimensions })
+}
+
+/// Pairs every rubric dimension with its submitted score. Legacy inputs without `scores`
+/// are mapped by key onto the old fixed fields; dimensions those don't cover score 0.
+pub fn resolve_scores<'a>(
+    dimensions: &'a [RubricDimension],
+    input: &SubmitScoresInput,
+) -> Result<Vec<(&'a RubricDimension, i32)>, AppError> {
+    let submitted: HashMap<&str, i32> = if input.scores.is_empty() {
+        HashMap::from([
+            ("implementation", input.score_implementation),
+            ("code_quality", input.score_code_quality),
+            ("accessibility", input.score_accessibility),
+            ("performance", input.score_performance),
+            ("quiz", input.score_quiz),
+        ])
+    } else {
+        let mut scores = HashMap::new();
+        for score in &input.scores {
+            if !dimensions.iter().any(|d| d.key == score.dimension_key) {
+                return Err(AppError::Validation(format!(
+                    "Unknown rubric dimension '{}'",
+                    score.dimension_key
+                )));
+            }
+            scores.insert(score.dimension_key.as_str(), score.score);
+        }
+        for dimension in dimensions {
+            if !scores.contains_key(dimension.key.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "Missing score for '{}'",
+                    dimension.label
+                )));
+            }
+        }
+        scores
+    };
+
+    dimensions
+        .iter()
+        .map(|dimension| {
+            let score = submitted.get(dimension.key.as_str()).copied().unwrap_or(0);
+            if score < 0 || score > dimension.max_points {
+                return Err(AppError::Validation(format!(
+                    "{} must be between 0 and {}",
+                    dimension.label, dimension.max_points
+                )));
+            }
+            Ok((dimension, score))
+        })
+        .collect()
+}
+
+/// Weighted percentage `100 * Σ(weight * score / max) / Σweight`, rounded. All-zero weights
+/// count every dimension equally.
+pub fn weighted_total(scores: &[(i32, i32, f64)]) -> i32 {
+    let total_weight: f64 = scores.iter().map(|&(_, _, weight)| weight).sum();
+    let (sum, weight_sum) = if total_weight > 0.0 {
+        let sum: f64 = scores
+            .iter()
+            .map(|&(score, max, weight)| weight * f64::from(score) / f64::from(max.max(1)))
+            .sum();
+        (sum, total_weight)
+    } else {
+        let sum: f64 = scores
+            .iter()
+            .map(|&(score, max, _)| f64::from(score) / f64::from(max.max(1)))
+            .sum();
+        (sum, scores.len().max(1) as f64)
+    };
+
+    ((100.0 * sum / weight_sum).round() as i32).clamp(0, 100)
+}
+
+pub fn attempt_status(
+    total_score: i32,
+    memory_rebuild_passed: bool,
+    rubric: &ScoringRubric,
+) -> &'static str {
+    if !memory_rebuild_passed || total_score < rubric.pass_threshold {
+        attempt_state::BLOCKED
+    } else if total_score >= rubric.mastery_threshold {
+        attempt_state::MASTERY
+    } else {
+        attempt_state::PASSED
+    }
+}
+
+pub fn validate_rubric(
+    pass_threshold: i32,
+    mastery_threshold: i32,
+    dimensions: &[RubricDimensionInput],
+) -> Result<(), AppError> {
+    if !(0..=100).contains(&pass_threshold) || !(0..=100).contains(&mastery_threshold) {
+        return Err(AppError::Validation(
+            "Thresholds must be between 0 and 100".to_string(),
+        ));
+    }
+    if mastery_threshold < pass_threshold {
+        return Err(AppError::Validation(
+            "Mastery threshold cannot be below the pass threshold".to_string(),
+        ));
+    }
+    if dimensions.is_empty() {
+        return Err(AppError::Validation(
+            "A rubric needs at least one dimension".to_string(),
+        ));
+    }
+
+    let mut keys = HashSet::new();
+    for dimension in dimensions {
+        if dimension.key.trim().is_empty() || dimension.label.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Dimension key and label are required".to_string(),
+            ));
+        }
+        if !keys.insert(dimension.key.trim()) {
+            return Err(AppError::Validation(format!(
+                "Dimension '{}' is listed twice",
+                dimension.key
+            )));
+        }
+        if dimension.max_points <= 0 {
+            return Err(AppError::Validation(format!(
+                "{} needs a positive max points value",
+                dimension.label
+            )));
+        }
+        if !dimension.weight.is_finite() || dimension.weight < 0.0 {
+            return Err(AppError::Validation(format!(
+                "{} needs a non-negative weight",
+                dimension.label
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::error::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::SqliteConnection;
+
+/// Reads a JSON-encoded value from the `settings` table, falling back to `default` when the
+/// key is missing or holds something that doesn't parse as `T`.
+pub async fn setting_or<T: DeserializeOwned>(
+    conn: &mut SqliteConnection,
+    key: &str,
+    default: T,
+) -> Result<T, AppError> {
+    let raw: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    Ok(raw
+        .and_then(|value| match serde_json::from_str(&value) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                tracing::warn!("Ignoring malformed setting {}: {}", key, e);
+                None
+            }
+        })
+        .unwrap_or(default))
+}
+
+/// Stores `value` JSON-encoded under `key`, replacing any previous value.
+pub async fn set_setting<T: Serialize>(
+    conn: &mut SqliteConnection,
+    key: &str,
+    value: &T,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+    )
+    .bind(key)
+    .bind(serde_json::to_string(value)?)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
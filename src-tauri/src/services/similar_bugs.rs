@@ -0,0 +1,138 @@
+use crate::db::models::{BugLog, SimilarBug};
+use crate::error::AppError;
+use crate::services::similarity::{bm25_scores, tokenize};
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_LIMIT: usize = 5;
+pub const MAX_LIMIT: usize = 50;
+/// Each concept tag the past bug's day shares with the new bug's day adds this share of its
+/// text score. Tags only reorder textual matches, they never make a match on their own.
+const TAG_BOOST: f64 = 0.25;
+const MAX_BOOSTED_TAGS: usize = 3;
+/// Words too common in bug descriptions to say anything about the bug.
+const STOPWORDS: [&str; 20] = [
+    "the", "an", "is", "are", "was", "were", "it", "its", "in", "on", "of", "to", "and", "or",
+    "for", "with", "at", "by", "this", "that",
+];
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct PastBug {
+    #[sqlx(flatten)]
+    bug: BugLog,
+    program_id: String,
+    day_plan_id: String,
+    day_title: String,
+    day_number: i32,
+}
+
+fn terms(symptom: &str, root_cause: &str) -> Vec<String> {
+    tokenize(&format!("{} {}", symptom, root_cause))
+        .into_iter()
+        .filter(|token| token.chars().count() >= 2 && !STOPWORDS.contains(&token.as_str()))
+        .collect()
+}
+
+/// Earlier bugs ranked by how closely their symptom and root cause match the new one, most
+/// similar first.
+pub async fn find(
+    conn: &mut SqliteConnection,
+    day_attempt_id: Option<&str>,
+    symptom: &str,
+    root_cause: &str,
+    exclude_bug_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<SimilarBug>, AppError> {
+    let query = terms(symptom, root_cause);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let past = sqlx::query_as::<_, PastBug>(
+        "SELECT bl.*, dp.program_id, dp.id AS day_plan_id, dp.title AS day_title, dp.day_number
+         FROM bug_logs bl
+         JOIN day_attempts da ON da.id = bl.day_attempt_id
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         WHERE ? IS NULL OR bl.id != ?",
+    )
+    .bind(exclude_bug_id)
+    .bind(exclude_bug_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    if past.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let current_tags: HashSet<String> = match day_attempt_id {
+        Some(day_attempt_id) => sqlx::query_scalar(
+            "SELECT ct.name FROM day_attempts da
+             JOIN day_plan_tags dpt ON dpt.day_plan_id = da.day_plan_id
+             JOIN concept_tags ct ON ct.id = dpt.concept_tag_id
+             WHERE da.id = ?",
+        )
+        .bind(day_attempt_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .collect(),
+        None => HashSet::new(),
+    };
+
+    let mut day_tags: HashMap<String, Vec<String>> = HashMap::new();
+    if !current_tags.is_empty() {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT dpt.day_plan_id, ct.name FROM day_plan_tags dpt
+             JOIN concept_tags ct ON ct.id = dpt.concept_tag_id
+             ORDER BY ct.name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for (day_plan_id, name) in rows {
+            day_tags.entry(day_plan_id).or_default().push(name);
+        }
+    }
+
+    let documents: Vec<Vec<String>> = past
+        .iter()
+        .map(|past| terms(&past.bug.symptom, &past.bug.root_cause))
+        .collect();
+    let text_scores = bm25_scores(&query, &documents);
+
+    let mut similar: Vec<SimilarBug> = past
+        .into_iter()
+        .zip(text_scores)
+        .filter(|(_, text_score)| *text_score > 0.0)
+        .map(|(past, text_score)| {
+            let shared_concept_tags: Vec<String> = day_tags
+                .get(&past.day_plan_id)
+                .map(|tags| {
+                    tags.iter()
+                        .filter(|tag| current_tags.contains(*tag))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            let boost = TAG_BOOST * shared_concept_tags.len().min(MAX_BOOSTED_TAGS) as f64;
+
+            SimilarBug {
+                bug: past.bug,
+                program_id: past.program_id,
+                day_plan_id: past.day_plan_id,
+                day_title: past.day_title,
+                day_number: past.day_number,
+                text_score,
+                score: text_score * (1.0 + boost),
+                shared_concept_tags,
+            }
+        })
+        .collect();
+
+    similar.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.bug.created_at.cmp(&a.bug.created_at))
+    });
+    similar.truncate(limit);
+
+    Ok(similar)
+}
@@ -0,0 +1,281 @@
+use crate::db::models::DiffLine;
+use std::collections::{HashMap, HashSet};
+
+/// Lowercases `text` and collapses everything that isn't alphanumeric into single spaces.
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    normalize(text)
+        .split(' ')
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Sørensen–Dice coefficient over character bigrams of the normalized titles, in `0.0..=1.0`.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    if a == b {
+        return 1.0;
+    }
+    if a.chars().count() < 2 || b.chars().count() < 2 {
+        return 0.0;
+    }
+
+    let mut a_bigrams: HashMap<(char, char), usize> = HashMap::new();
+    let a_chars: Vec<char> = a.chars().collect();
+    for pair in a_chars.windows(2) {
+        *a_bigrams.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut shared = 0usize;
+    for pair in b_chars.windows(2) {
+        if let Some(count) = a_bigrams.get_mut(&(pair[0], pair[1])) {
+            if *count > 0 {
+                *count -= 1;
+                shared += 1;
+            }
+        }
+    }
+
+    let total = (a_chars.len() - 1) + (b_chars.len() - 1);
+    (2 * shared) as f64 / total as f64
+}
+
+/// Share of the distinct meaningful tokens (three or more characters) in `needle` that
+/// also appear in `haystack`, in `0.0..=1.0`.
+pub fn token_coverage(needle: &str, haystack: &str) -> f64 {
+    let needle: HashSet<String> = tokenize(needle)
+        .into_iter()
+        .filter(|token| token.chars().count() >= 3)
+        .collect();
+    if needle.is_empty() {
+        return 0.0;
+    }
+
+    let haystack: HashSet<String> = tokenize(haystack).into_iter().collect();
+    let found = needle.iter().filter(|token| haystack.contains(*token)).count();
+    found as f64 / needle.len() as f64
+}
+
+/// Cells above this in the LCS table fall back to replacing the whole changed middle.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Line diff of `base` against `target` from a longest common subsequence, after trimming
+/// the shared head and tail.
+pub fn line_diff(base: &str, target: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = base.lines().collect();
+    let new: Vec<&str> = target.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let same = |i: usize, j: usize, text: &str| DiffLine {
+        kind: "same".to_string(),
+        text: text.to_string(),
+        base_line: Some(i + 1),
+        target_line: Some(j + 1),
+    };
+    let removed = |i: usize, text: &str| DiffLine {
+        kind: "removed".to_string(),
+        text: text.to_string(),
+        base_line: Some(i + 1),
+        target_line: None,
+    };
+    let added = |j: usize, text: &str| DiffLine {
+        kind: "added".to_string(),
+        text: text.to_string(),
+        base_line: None,
+        target_line: Some(j + 1),
+    };
+
+    let mut diff: Vec<DiffLine> = (0..prefix).map(|i| same(i, i, old[i])).collect();
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n * m > MAX_DIFF_CELLS {
+        diff.extend((0..n).map(|i| removed(prefix + i, old_mid[i])));
+        diff.extend((0..m).map(|j| added(prefix + j, new_mid[j])));
+    } else {
+        // lcs[i][j] = length of the LCS of old_mid[i..] and new_mid[j..]
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                diff.push(same(prefix + i, prefix + j, old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                diff.push(removed(prefix + i, old_mid[i]));
+                i += 1;
+            } else {
+                diff.push(added(prefix + j, new_mid[j]));
+                j += 1;
+            }
+        }
+    }
+
+    let old_tail = old.len() - suffix;
+    let new_tail = new.len() - suffix;
+    diff.extend((0..suffix).map(|k| same(old_tail + k, new_tail + k, old[old_tail + k])));
+
+    diff
+}
+
+/// Lexical tokens of source code: identifiers, numbers and single punctuation characters,
+/// without whitespace, `//` and `/* */` comments, or `#` comments followed by a space (so
+/// `#[derive]` and `#include` survive).
+pub fn code_tokens(code: &str) -> Vec<String> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if (c == '/' && next == Some('/'))
+            || (c == '#' && next.map_or(true, char::is_whitespace))
+        {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                i += 1;
+            }
+            i += 2;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            tokens.push(c.to_string());
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// `2 * LCS / (|a| + |b|)` over the token sequences, in `0.0..=1.0`. Sequences too long for
+/// the LCS table are compared as token multisets instead, ignoring order.
+pub fn sequence_similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let common = if a.len() * b.len() > MAX_DIFF_CELLS {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for token in a {
+            *counts.entry(token.as_str()).or_insert(0) += 1;
+        }
+        b.iter()
+            .filter(|token| {
+                counts.get_mut(token.as_str()).is_some_and(|count| {
+                    let available = *count > 0;
+                    *count = count.saturating_sub(1);
+                    available
+                })
+            })
+            .count()
+    } else {
+        // Two rows of the LCS table are enough for its length
+        let mut previous = vec![0usize; b.len() + 1];
+        let mut current = vec![0usize; b.len() + 1];
+        for token in a {
+            for (j, other) in b.iter().enumerate() {
+                current[j + 1] = if token == other {
+                    previous[j] + 1
+                } else {
+                    previous[j + 1].max(current[j])
+                };
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+        previous[b.len()]
+    };
+
+    (2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// Okapi BM25 relevance of each document to `query`, in document order. Scores are only
+/// comparable within one call, since term rarity is measured across `documents`.
+pub fn bm25_scores(query: &[String], documents: &[Vec<String>]) -> Vec<f64> {
+    if documents.is_empty() {
+        return Vec::new();
+    }
+
+    let document_count = documents.len() as f64;
+    let average_length =
+        (documents.iter().map(Vec::len).sum::<usize>() as f64 / document_count).max(1.0);
+    let query_terms: HashSet<&str> = query.iter().map(String::as_str).collect();
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for document in documents {
+        let distinct: HashSet<&str> = document.iter().map(String::as_str).collect();
+        for term in distinct.into_iter().filter(|term| query_terms.contains(term)) {
+            *document_frequency.entry(term).or_insert(0) += 1;
+        }
+    }
+
+    documents
+        .iter()
+        .map(|document| {
+            let mut term_frequency: HashMap<&str, usize> = HashMap::new();
+            for token in document {
+                if query_terms.contains(token.as_str()) {
+                    *term_frequency.entry(token.as_str()).or_insert(0) += 1;
+                }
+            }
+            let length_norm =
+                1.0 - BM25_B + BM25_B * document.len() as f64 / average_length;
+
+            term_frequency
+                .into_iter()
+                .map(|(term, frequency)| {
+                    let containing = document_frequency.get(term).copied().unwrap_or(0) as f64;
+                    let idf = (1.0 + (document_count - containing + 0.5) / (containing + 0.5)).ln();
+                    let frequency = frequency as f64;
+                    idf * frequency * (BM25_K1 + 1.0) / (frequency + BM25_K1 * length_norm)
+                })
+                .sum()
+        })
+        .collect()
+}
@@ -0,0 +1,363 @@
+use crate::db::models::{
+    AttemptSnapshot, AttemptSnapshotSummary, AutosaveAttemptInput, DayAttempt, ExerciseEntry,
+    ExerciseEntryDraft, RecoverableDraft,
+};
+use crate::error::AppError;
+use crate::services::settings::setting_or;
+use sqlx::SqliteConnection;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const SNAPSHOT_LIMIT_SETTING: &str = "autosave_snapshot_limit";
+const DEFAULT_SNAPSHOT_LIMIT: i64 = 20;
+
+/// Snapshot times and `content_saved_at` are compared as text, so both are written in this
+/// one RFC 3339 shape, the same one `strftime('%Y-%m-%dT%H:%M:%fZ')` produces.
+pub fn saved_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+const SUMMARY_COLUMNS: &str = "id, day_attempt_id, sequence, LENGTH(code_snapshot) AS code_length,
+    json_array_length(exercise_entries_json) AS exercise_count, created_at";
+
+/// Everything an autosave captures, in comparable form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftState {
+    pub daily_summary: String,
+    pub what_went_well: String,
+    pub what_to_improve: String,
+    pub key_learnings: String,
+    pub memory_rebuild_notes: String,
+    pub code_snapshot: String,
+    pub exercise_entries: Vec<ExerciseEntryDraft>,
+}
+
+impl DraftState {
+    pub fn from_snapshot(snapshot: &AttemptSnapshot) -> Result<Self, AppError> {
+        Ok(Self {
+            daily_summary: snapshot.daily_summary.clone(),
+            what_went_well: snapshot.what_went_well.clone(),
+            what_to_improve: snapshot.what_to_improve.clone(),
+            key_learnings: snapshot.key_learnings.clone(),
+            memory_rebuild_notes: snapshot.memory_rebuild_notes.clone(),
+            code_snapshot: snapshot.code_snapshot.clone(),
+            exercise_entries: serde_json::from_str(&snapshot.exercise_entries_json)?,
+        })
+    }
+
+    fn apply(&mut self, input: AutosaveAttemptInput) {
+        let fields = [
+            (&mut self.daily_summary, input.daily_summary),
+            (&mut self.what_went_well, input.what_went_well),
+            (&mut self.what_to_improve, input.what_to_improve),
+            (&mut self.key_learnings, input.key_learnings),
+            (&mut self.memory_rebuild_notes, input.memory_rebuild_notes),
+            (&mut self.code_snapshot, input.code_snapshot),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        if let Some(entries) = input.exercise_entries {
+            self.exercise_entries = entries;
+        }
+    }
+
+    /// Names of the fields that differ between the two states.
+    pub fn changed_fields(&self, other: &Self) -> Vec<String> {
+        [
+            ("daily_summary", self.daily_summary != other.daily_summary),
+            ("what_went_well", self.what_went_well != other.what_went_well),
+            ("what_to_improve", self.what_to_improve != other.what_to_improve),
+            ("key_learnings", self.key_learnings != other.key_learnings),
+            ("memory_rebuild_notes", self.memory_rebuild_notes != other.memory_rebuild_notes),
+            ("code_snapshot", self.code_snapshot != other.code_snapshot),
+            ("exercise_entries", self.exercise_entries != other.exercise_entries),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name.to_string())
+        .collect()
+    }
+}
+
+/// The attempt as last explicitly saved, including its exercise entries.
+pub async fn saved_state(
+    conn: &mut SqliteConnection,
+    attempt: &DayAttempt,
+) -> Result<DraftState, AppError> {
+    let entries = sqlx::query_as::<_, ExerciseEntry>(
+        "SELECT * FROM exercise_entries WHERE day_attempt_id = ? ORDER BY order_index",
+    )
+    .bind(&attempt.id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(DraftState {
+        daily_summary: attempt.daily_summary.clone(),
+        what_went_well: attempt.what_went_well.clone(),
+        what_to_improve: attempt.what_to_improve.clone(),
+        key_learnings: attempt.key_learnings.clone(),
+        memory_rebuild_notes: attempt.memory_rebuild_notes.clone(),
+        code_snapshot: attempt.code_snapshot.clone(),
+        exercise_entries: entries
+            .into_iter()
+            .map(|entry| ExerciseEntryDraft {
+                id: Some(entry.id),
+                language: entry.language,
+                code: entry.code,
+                notes: entry.notes,
+                expected_output: entry.expected_output,
+            })
+            .collect(),
+    })
+}
+
+pub async fn latest_snapshot(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Option<AttemptSnapshot>, AppError> {
+    let snapshot = sqlx::query_as::<_, AttemptSnapshot>(
+        "SELECT * FROM attempt_snapshots WHERE day_attempt_id = ?
+         ORDER BY sequence DESC LIMIT 1",
+    )
+    .bind(day_attempt_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(snapshot)
+}
+
+pub async fn fetch_snapshot(
+    conn: &mut SqliteConnection,
+    snapshot_id: &str,
+) -> Result<AttemptSnapshot, AppError> {
+    sqlx::query_as::<_, AttemptSnapshot>("SELECT * FROM attempt_snapshots WHERE id = ?")
+        .bind(snapshot_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "AttemptSnapshot".to_string(),
+            id: snapshot_id.to_string(),
+        })
+}
+
+pub async fn list_summaries(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+) -> Result<Vec<AttemptSnapshotSummary>, AppError> {
+    let summaries = sqlx::query_as::<_, AttemptSnapshotSummary>(&format!(
+        "SELECT {} FROM attempt_snapshots WHERE day_attempt_id = ? ORDER BY sequence DESC",
+        SUMMARY_COLUMNS
+    ))
+    .bind(day_attempt_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(summaries)
+}
+
+/// Records the draft as a new snapshot on top of the latest one (or the saved state when
+/// there is none yet), then drops snapshots past the configured limit. An autosave that
+/// changes nothing reuses the latest snapshot.
+pub async fn capture(
+    conn: &mut SqliteConnection,
+    attempt: &DayAttempt,
+    input: AutosaveAttemptInput,
+) -> Result<AttemptSnapshotSummary, AppError> {
+    let latest = latest_snapshot(conn, &attempt.id).await?;
+    let previous = match &latest {
+        Some(snapshot) => Some(DraftState::from_snapshot(snapshot)?),
+        None => None,
+    };
+
+    let mut draft = match &previous {
+        Some(state) => state.clone(),
+        None => saved_state(conn, attempt).await?,
+    };
+    draft.apply(input);
+
+    let snapshot_id = match (&latest, &previous) {
+        (Some(snapshot), Some(state)) if *state == draft => snapshot.id.clone(),
+        _ => insert_snapshot(conn, &attempt.id, &draft).await?,
+    };
+
+    summary(conn, &snapshot_id).await
+}
+
+async fn insert_snapshot(
+    conn: &mut SqliteConnection,
+    day_attempt_id: &str,
+    draft: &DraftState,
+) -> Result<String, AppError> {
+    let id = Uuid::new_v4().to_string();
+    let now = saved_timestamp();
+    let limit: i64 = setting_or(conn, SNAPSHOT_LIMIT_SETTING, DEFAULT_SNAPSHOT_LIMIT)
+        .await?
+        .max(1);
+
+    let sequence: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(sequence), 0) + 1 FROM attempt_snapshots WHERE day_attempt_id = ?",
+    )
+    .bind(day_attempt_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO attempt_snapshots (
+            id, day_attempt_id, sequence, daily_summary, what_went_well, what_to_improve,
+            key_learnings, memory_rebuild_notes, code_snapshot, exercise_entries_json, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(day_attempt_id)
+    .bind(sequence)
+    .bind(&draft.daily_summary)
+    .bind(&draft.what_went_well)
+    .bind(&draft.what_to_improve)
+    .bind(&draft.key_learnings)
+    .bind(&draft.memory_rebuild_notes)
+    .bind(&draft.code_snapshot)
+    .bind(serde_json::to_string(&draft.exercise_entries)?)
+    .bind(&now)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM attempt_snapshots WHERE day_attempt_id = ? AND sequence <= ?")
+        .bind(day_attempt_id)
+        .bind(sequence - limit)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(id)
+}
+
+async fn summary(
+    conn: &mut SqliteConnection,
+    snapshot_id: &str,
+) -> Result<AttemptSnapshotSummary, AppError> {
+    let summary = sqlx::query_as::<_, AttemptSnapshotSummary>(&format!(
+        "SELECT {} FROM attempt_snapshots WHERE id = ?",
+        SUMMARY_COLUMNS
+    ))
+    .bind(snapshot_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(summary)
+}
+
+/// Writes the draft into the attempt and replaces its exercise entries. The state being
+/// replaced is snapshotted first, so a restore can itself be undone.
+pub async fn restore(
+    conn: &mut SqliteConnection,
+    attempt: &DayAttempt,
+    draft: &DraftState,
+) -> Result<DayAttempt, AppError> {
+    let current = saved_state(conn, attempt).await?;
+    if current != *draft {
+        insert_snapshot(conn, &attempt.id, &current).await?;
+    }
+
+    // Taken after the safety snapshot so the restored state doesn't look recoverable
+    let now = saved_timestamp();
+
+    let restored = sqlx::query_as::<_, DayAttempt>(
+        "UPDATE day_attempts SET
+            daily_summary = ?,
+            what_went_well = ?,
+            what_to_improve = ?,
+            key_learnings = ?,
+            memory_rebuild_notes = ?,
+            code_snapshot = ?,
+            content_saved_at = ?,
+            updated_at = ?
+         WHERE id = ?
+         RETURNING *",
+    )
+    .bind(&draft.daily_summary)
+    .bind(&draft.what_went_well)
+    .bind(&draft.what_to_improve)
+    .bind(&draft.key_learnings)
+    .bind(&draft.memory_rebuild_notes)
+    .bind(&draft.code_snapshot)
+    .bind(&now)
+    .bind(&now)
+    .bind(&attempt.id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    // Entries are updated in place and only the ones missing from the draft are deleted, so
+    // the runs recorded against kept entries survive the restore
+    let existing_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM exercise_entries WHERE day_attempt_id = ?")
+            .bind(&attempt.id)
+            .fetch_all(&mut *conn)
+            .await?;
+    let kept: HashSet<&str> = draft
+        .exercise_entries
+        .iter()
+        .filter_map(|entry| entry.id.as_deref())
+        .collect();
+    for id in existing_ids.iter().filter(|id| !kept.contains(id.as_str())) {
+        sqlx::query("DELETE FROM exercise_entries WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    for (order_index, entry) in (0i32..).zip(&draft.exercise_entries) {
+        sqlx::query(
+            "INSERT INTO exercise_entries (id, day_attempt_id, language, code, notes, expected_output, order_index, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                language = excluded.language,
+                code = excluded.code,
+                notes = excluded.notes,
+                expected_output = excluded.expected_output,
+                order_index = excluded.order_index,
+                updated_at = excluded.updated_at
+             WHERE exercise_entries.day_attempt_id = excluded.day_attempt_id",
+        )
+        .bind(
+            entry
+                .id
+                .clone()
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+        )
+        .bind(&attempt.id)
+        .bind(&entry.language)
+        .bind(&entry.code)
+        .bind(&entry.notes)
+        .bind(entry.expected_output.as_ref())
+        .bind(order_index)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(restored)
+}
+
+/// In-progress attempts whose newest snapshot is more recent than their last explicit
+/// save, i.e. work that only survived in autosave.
+pub async fn find_recoverable(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<RecoverableDraft>, AppError> {
+    let drafts = sqlx::query_as::<_, RecoverableDraft>(
+        "SELECT da.id AS day_attempt_id, da.day_plan_id, dp.title AS day_title,
+            s.id AS snapshot_id, s.created_at AS snapshot_created_at, da.content_saved_at AS saved_at
+         FROM day_attempts da
+         JOIN day_plans dp ON dp.id = da.day_plan_id
+         JOIN attempt_snapshots s ON s.day_attempt_id = da.id
+            AND s.sequence = (SELECT MAX(sequence) FROM attempt_snapshots WHERE day_attempt_id = da.id)
+         WHERE da.status = 'in_progress' AND s.created_at > da.content_saved_at
+         ORDER BY s.created_at DESC",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(drafts)
+}
@@ -0,0 +1,502 @@
+use crate::db::models::{ActivityHeatmapDay, Streak, StudyActivity, UserCapacityProfile};
+use crate::error::AppError;
+use crate::services::settings::setting_or;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+pub const FREEZES_PER_MONTH_SETTING: &str = "streak_freezes_per_month";
+const DEFAULT_FREEZES_PER_MONTH: i32 = 2;
+const AUTO_FREEZE_REASON: &str = "Missed study day";
+pub const DEFAULT_HEATMAP_DAYS: i32 = 365;
+pub const MAX_HEATMAP_DAYS: i32 = 730;
+/// Heatmap effort counts a review as this many minutes of study.
+const REVIEW_MINUTES: i32 = 2;
+
+/// Something that counts as studying on the day it happens.
+#[derive(Debug, Clone, Copy)]
+pub enum Activity {
+    /// A submitted attempt and the minutes spent on it
+    Attempt { minutes: i32 },
+    /// A spaced repetition or quiz card review
+    Review,
+}
+
+/// The streak as recomputed from activity history.
+#[derive(Debug, Clone, Default)]
+pub struct StreakWalk {
+    pub current: i32,
+    pub longest: i32,
+    /// `reached_at[n - 1]` is the last activity on the day a streak first reached `n` days
+    pub reached_at: Vec<String>,
+    /// Missed study days the walk covered with a freeze
+    pub auto_frozen: Vec<NaiveDate>,
+}
+
+struct History {
+    /// Studied days with the day's last activity
+    studied: BTreeMap<NaiveDate, String>,
+    frozen: HashSet<NaiveDate>,
+    profile: Option<UserCapacityProfile>,
+    freezes_per_month: i32,
+}
+
+impl History {
+    /// Without a capacity profile every day is a study day.
+    fn is_study_day(&self, date: NaiveDate) -> bool {
+        self.profile
+            .as_ref()
+            .map_or(true, |profile| profile.is_study_day(date))
+    }
+
+    fn freezes_in_month(&self, year: i32, month: u32) -> i32 {
+        self.frozen
+            .iter()
+            .filter(|day| day.year() == year && day.month() == month)
+            .count() as i32
+    }
+}
+
+fn date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Adds `activity` to today's history for the program `day_plan_id` belongs to.
+pub async fn record_activity(
+    conn: &mut SqliteConnection,
+    day_plan_id: &str,
+    activity: Activity,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let (attempts, reviews, minutes) = match activity {
+        Activity::Attempt { minutes } => (1, 0, minutes.max(0)),
+        Activity::Review => (0, 1, 0),
+    };
+
+    sqlx::query(
+        "INSERT INTO study_activity (
+            program_id, activity_date, attempts_submitted, reviews_completed,
+            minutes_studied, last_activity_at
+         )
+         SELECT program_id, ?, ?, ?, ?, ? FROM day_plans WHERE id = ?
+         ON CONFLICT(program_id, activity_date) DO UPDATE SET
+            attempts_submitted = attempts_submitted + excluded.attempts_submitted,
+            reviews_completed = reviews_completed + excluded.reviews_completed,
+            minutes_studied = minutes_studied + excluded.minutes_studied,
+            last_activity_at = excluded.last_activity_at",
+    )
+    .bind(format_date(now.date_naive()))
+    .bind(attempts)
+    .bind(reviews)
+    .bind(minutes)
+    .bind(now.to_rfc3339())
+    .bind(day_plan_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks today as studied without adding to its counts, for study the app doesn't see.
+pub async fn check_in(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO study_activity (program_id, activity_date, last_activity_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT(program_id, activity_date) DO UPDATE SET
+            last_activity_at = excluded.last_activity_at",
+    )
+    .bind(program_id)
+    .bind(format_date(now.date_naive()))
+    .bind(now.to_rfc3339())
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn load_history(conn: &mut SqliteConnection, program_id: &str) -> Result<History, AppError> {
+    let studied = sqlx::query_as::<_, (String, String)>(
+        "SELECT activity_date, last_activity_at FROM study_activity WHERE program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .filter_map(|(day, at)| Some((date(&day)?, at)))
+    .collect();
+
+    let frozen = sqlx::query_scalar::<_, String>(
+        "SELECT used_date FROM streak_freezes WHERE program_id = ?",
+    )
+    .bind(program_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .iter()
+    .filter_map(|day| date(day))
+    .collect();
+
+    let profile = sqlx::query_as::<_, UserCapacityProfile>(
+        "SELECT * FROM user_capacity_profiles WHERE user_id = 'default' LIMIT 1",
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let freezes_per_month = setting_or(conn, FREEZES_PER_MONTH_SETTING, DEFAULT_FREEZES_PER_MONTH)
+        .await?
+        .max(0);
+
+    Ok(History {
+        studied,
+        frozen,
+        profile,
+        freezes_per_month,
+    })
+}
+
+/// Walks day by day from the first studied day to `today`. Studied days extend the streak,
+/// rest days and frozen days carry it over, and a missed study day is frozen while the
+/// month has freezes left and breaks the streak otherwise. Today can't be missed yet.
+fn walk(history: &History, today: NaiveDate) -> StreakWalk {
+    let mut walk = StreakWalk::default();
+    let Some(&first) = history.studied.keys().next() else {
+        return walk;
+    };
+    let mut freezes_used: HashMap<(i32, u32), i32> = HashMap::new();
+    let mut run = 0;
+
+    let mut day = first;
+    while day <= today {
+        if let Some(at) = history.studied.get(&day) {
+            run += 1;
+            walk.longest = walk.longest.max(run);
+            if run as usize > walk.reached_at.len() {
+                walk.reached_at.push(at.clone());
+            }
+        } else if day < today
+            && run > 0
+            && !history.frozen.contains(&day)
+            && history.is_study_day(day)
+        {
+            let used = freezes_used
+                .entry((day.year(), day.month()))
+                .or_insert_with(|| history.freezes_in_month(day.year(), day.month()));
+            if *used < history.freezes_per_month {
+                *used += 1;
+                walk.auto_frozen.push(day);
+            } else {
+                run = 0;
+            }
+        }
+        day += Duration::days(1);
+    }
+    walk.current = run;
+
+    walk
+}
+
+/// The program's streak as of `today`, recomputed from its activity history without
+/// storing anything.
+pub async fn streak_walk(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    today: NaiveDate,
+) -> Result<StreakWalk, AppError> {
+    let history = load_history(conn, program_id).await?;
+    Ok(walk(&history, today))
+}
+
+/// Recomputes the program's streak from its history, records the freezes that covered
+/// missed study days and stores the result. Freezes replenish each month: what's available
+/// is the monthly allowance less the freezes used this month.
+pub async fn refresh(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    now: DateTime<Utc>,
+) -> Result<Streak, AppError> {
+    let today = now.date_naive();
+    let now_str = now.to_rfc3339();
+    let mut history = load_history(conn, program_id).await?;
+    let walk = walk(&history, today);
+
+    for day in walk.auto_frozen {
+        sqlx::query(
+            "INSERT INTO streak_freezes (id, program_id, used_date, reason, created_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(program_id, used_date) DO NOTHING",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(program_id)
+        .bind(format_date(day))
+        .bind(AUTO_FREEZE_REASON)
+        .bind(&now_str)
+        .execute(&mut *conn)
+        .await?;
+        history.frozen.insert(day);
+    }
+
+    let freezes_used_this_month = history.freezes_in_month(today.year(), today.month());
+    let freezes_available = (history.freezes_per_month - freezes_used_this_month).max(0);
+    let last_activity_date = history
+        .studied
+        .keys()
+        .next_back()
+        .map(|day| format_date(*day))
+        .unwrap_or_default();
+
+    let streak = sqlx::query_as::<_, Streak>(
+        "INSERT INTO streaks (
+            id, program_id, current_streak, longest_streak, last_activity_date,
+            freezes_available, freezes_used_this_month, created_at, updated_at
+         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(program_id) DO UPDATE SET
+            current_streak = excluded.current_streak,
+            longest_streak = excluded.longest_streak,
+            last_activity_date = excluded.last_activity_date,
+            freezes_available = excluded.freezes_available,
+            freezes_used_this_month = excluded.freezes_used_this_month,
+            updated_at = excluded.updated_at
+         RETURNING *",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(program_id)
+    .bind(walk.current)
+    .bind(walk.longest)
+    .bind(last_activity_date)
+    .bind(freezes_available)
+    .bind(freezes_used_this_month)
+    .bind(&now_str)
+    .bind(&now_str)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(streak)
+}
+
+/// Spends one of this month's freezes on today, ahead of a day off.
+pub async fn use_freeze(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    reason: &str,
+    now: DateTime<Utc>,
+) -> Result<Streak, AppError> {
+    let streak = refresh(conn, program_id, now).await?;
+    if streak.freezes_available <= 0 {
+        return Err(AppError::Validation(
+            "No freezes left this month".to_string(),
+        ));
+    }
+
+    let today = format_date(now.date_naive());
+    if streak.last_activity_date == today {
+        return Err(AppError::Validation(
+            "Today already counts toward the streak".to_string(),
+        ));
+    }
+
+    let inserted = sqlx::query(
+        "INSERT INTO streak_freezes (id, program_id, used_date, reason, created_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(program_id, used_date) DO NOTHING",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(program_id)
+    .bind(&today)
+    .bind(reason)
+    .bind(now.to_rfc3339())
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+    if inserted == 0 {
+        return Err(AppError::Validation("Today is already frozen".to_string()));
+    }
+
+    refresh(conn, program_id, now).await
+}
+
+/// The last `days` days of the program's history, oldest first. Days before the first
+/// studied day are "none", as is today until something is studied.
+pub async fn heatmap(
+    conn: &mut SqliteConnection,
+    program_id: &str,
+    days: i32,
+    now: DateTime<Utc>,
+) -> Result<Vec<ActivityHeatmapDay>, AppError> {
+    let today = now.date_naive();
+    let first_day = today - Duration::days(i64::from(days - 1));
+
+    let activity: HashMap<NaiveDate, StudyActivity> = sqlx::query_as::<_, StudyActivity>(
+        "SELECT * FROM study_activity
+         WHERE program_id = ? AND activity_date >= ? AND activity_date <= ?",
+    )
+    .bind(program_id)
+    .bind(format_date(first_day))
+    .bind(format_date(today))
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .filter_map(|row| Some((date(&row.activity_date)?, row)))
+    .collect();
+
+    let history = load_history(conn, program_id).await?;
+    let started = history.studied.keys().next().copied();
+
+    let effort = |row: &StudyActivity| row.minutes_studied + REVIEW_MINUTES * row.reviews_completed;
+    let busiest = activity.values().map(effort).max().unwrap_or(0).max(1);
+
+    let heatmap = (0..days)
+        .map(|offset| {
+            let day = first_day + Duration::days(i64::from(offset));
+            let row = activity.get(&day);
+            let status = if row.is_some() {
+                "studied"
+            } else if history.frozen.contains(&day) {
+                "frozen"
+            } else if started.map_or(true, |started| day < started) || day == today {
+                "none"
+            } else if !history.is_study_day(day) {
+                "rest"
+            } else {
+                "missed"
+            };
+            // Any activity shows, however small next to the busiest day
+            let level = row.map_or(0, |row| {
+                ((effort(row) * 4 + busiest - 1) / busiest).clamp(1, 4)
+            });
+
+            ActivityHeatmapDay {
+                date: format_date(day),
+                status: status.to_string(),
+                level,
+                attempts_submitted: row.map_or(0, |row| row.attempts_submitted),
+                reviews_completed: row.map_or(0, |row| row.reviews_completed),
+                minutes_studied: row.map_or(0, |row| row.minutes_studied),
+            }
+        })
+        .collect();
+
+    Ok(heatmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(value: &str) -> NaiveDate {
+        date(value).unwrap()
+    }
+
+    fn profile(weekly_study_days: i32) -> UserCapacityProfile {
+        UserCapacityProfile {
+            id: "profile".to_string(),
+            user_id: "default".to_string(),
+            default_daily_minutes: 60,
+            weekly_study_days,
+            preferred_start_time: "09:00".to_string(),
+            max_deep_days_per_week: 2,
+            break_pattern: "pomodoro".to_string(),
+            timezone: "UTC".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn history(
+        studied: &[&str],
+        frozen: &[&str],
+        profile: Option<UserCapacityProfile>,
+        freezes_per_month: i32,
+    ) -> History {
+        History {
+            studied: studied
+                .iter()
+                .map(|value| (day(value), format!("{}T20:00:00+00:00", value)))
+                .collect(),
+            frozen: frozen.iter().map(|value| day(value)).collect(),
+            profile,
+            freezes_per_month,
+        }
+    }
+
+    #[test]
+    fn rest_days_carry_the_streak_over() {
+        // Monday to Friday, then Monday again, with weekends off
+        let history = history(
+            &["2026-03-02", "2026-03-03", "2026-03-04", "2026-03-05", "2026-03-06", "2026-03-09"],
+            &[],
+            Some(profile(5)),
+            0,
+        );
+
+        let streak = walk(&history, day("2026-03-09"));
+        assert_eq!(streak.current, 6);
+        assert_eq!(streak.longest, 6);
+        assert!(streak.auto_frozen.is_empty());
+        assert_eq!(streak.reached_at.last().unwrap(), "2026-03-09T20:00:00+00:00");
+    }
+
+    #[test]
+    fn missed_study_days_use_the_months_freezes() {
+        let history = history(&["2026-03-02", "2026-03-05"], &[], None, 2);
+
+        let streak = walk(&history, day("2026-03-05"));
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.auto_frozen, vec![day("2026-03-03"), day("2026-03-04")]);
+    }
+
+    #[test]
+    fn auto_freezes_are_capped_per_month() {
+        // One of March's two freezes was already spent by hand
+        let history = history(&["2026-03-02", "2026-03-06"], &["2026-03-20"], None, 2);
+
+        let streak = walk(&history, day("2026-03-06"));
+        assert_eq!(streak.auto_frozen, vec![day("2026-03-03")]);
+        assert_eq!(streak.current, 1);
+    }
+
+    #[test]
+    fn freezes_replenish_each_month() {
+        let history = history(&["2026-03-30", "2026-04-02"], &[], None, 1);
+
+        let streak = walk(&history, day("2026-04-02"));
+        assert_eq!(streak.auto_frozen, vec![day("2026-03-31"), day("2026-04-01")]);
+        assert_eq!(streak.current, 2);
+    }
+
+    #[test]
+    fn streak_breaks_when_freezes_run_out() {
+        let history = history(
+            &["2026-03-02", "2026-03-03", "2026-03-06", "2026-03-07"],
+            &[],
+            None,
+            1,
+        );
+
+        let streak = walk(&history, day("2026-03-07"));
+        assert_eq!(streak.auto_frozen, vec![day("2026-03-04")]);
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.reached_at.len(), 2);
+    }
+
+    #[test]
+    fn today_is_never_missed() {
+        let history = history(&["2026-03-02", "2026-03-03", "2026-03-04"], &[], None, 0);
+
+        let streak = walk(&history, day("2026-03-05"));
+        assert_eq!(streak.current, 3);
+        assert!(streak.auto_frozen.is_empty());
+
+        let streak = walk(&history, day("2026-03-06"));
+        assert_eq!(streak.current, 0);
+        assert_eq!(streak.longest, 3);
+    }
+}
@@ -0,0 +1,129 @@
+//! Runs the migrations on a scratch database and checks that every model and every SQL
+//! statement in the source tree still matches the schema they produce.
+
+use buildops40_lib::db::{run_migrations, schema_check};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+
+async fn migrated_pool(name: &str) -> SqlitePool {
+    let path = std::env::temp_dir().join(format!("buildops40-{}-{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite:{}?mode=rwc", path.display()))
+        .await
+        .expect("Failed to open scratch database");
+    // Migrations are found relative to the crate root, which is where cargo runs tests
+    run_migrations(&pool).await;
+
+    pool
+}
+
+#[tokio::test]
+async fn models_match_schema() {
+    let pool = migrated_pool("models").await;
+    let mut conn = pool.acquire().await.unwrap();
+
+    let drift = schema_check::check_models(&mut conn).await.unwrap();
+
+    assert!(
+        drift.is_empty(),
+        "Models have drifted from the schema:\n{}",
+        drift.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+    );
+}
+
+#[tokio::test]
+async fn every_statement_prepares() {
+    let pool = migrated_pool("statements").await;
+    let mut conn = pool.acquire().await.unwrap();
+
+    let mut files = Vec::new();
+    rust_files(Path::new("src"), &mut files);
+    assert!(!files.is_empty(), "No sources found under src");
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for file in files {
+        let source = std::fs::read_to_string(&file).unwrap();
+        for sql in sql_literals(&source) {
+            checked += 1;
+            if let Err(e) = schema_check::check_statement(&mut conn, &sql).await {
+                let first_line = sql.lines().next().unwrap_or_default().trim();
+                failures.push(format!("{}: {} ({})", file.display(), first_line, e));
+            }
+        }
+    }
+
+    assert!(checked > 0, "No SQL statements found");
+    assert!(
+        failures.is_empty(),
+        "{} of {} statements don't prepare against the schema:\n{}",
+        failures.len(),
+        checked,
+        failures.join("\n")
+    );
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// String literals passed straight to `sqlx::query`, `query_as` or `query_scalar`.
+/// Statements assembled with `format!` can't be checked this way and are skipped.
+fn sql_literals(source: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("sqlx::query") {
+        rest = &rest[start + "sqlx::query".len()..];
+        let Some(open) = rest.find('(') else { break };
+        let argument = rest[open + 1..].trim_start();
+        let Some(literal) = argument.strip_prefix('"') else { continue };
+        let Some(sql) = unescape_literal(literal) else { continue };
+
+        let keyword = sql.trim_start().split_whitespace().next().unwrap_or_default().to_uppercase();
+        if ["SELECT", "INSERT", "UPDATE", "DELETE", "WITH"].contains(&keyword.as_str()) {
+            statements.push(sql);
+        }
+    }
+
+    statements
+}
+
+/// Reads a Rust string literal up to its closing quote, resolving escapes and `\`
+/// line continuations.
+fn unescape_literal(literal: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = literal.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                '\n' => {
+                    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                        chars.next();
+                    }
+                }
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+
+    None
+}
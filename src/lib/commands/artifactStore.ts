@@ -0,0 +1,19 @@
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	ArtifactGcReport,
+	ArtifactIntegrityReport,
+	ImportArtifactFileInput,
+	ImportedArtifact
+} from '$lib/types';
+
+export async function importArtifactFile(input: ImportArtifactFileInput): Promise<ImportedArtifact> {
+	return invoke<ImportedArtifact>('import_artifact_file', { input });
+}
+
+export async function verifyArtifactBlobs(): Promise<ArtifactIntegrityReport> {
+	return invoke<ArtifactIntegrityReport>('verify_artifact_blobs');
+}
+
+export async function collectArtifactGarbage(): Promise<ArtifactGcReport> {
+	return invoke<ArtifactGcReport>('collect_artifact_garbage');
+}
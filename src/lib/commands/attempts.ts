@@ -13,7 +13,12 @@ import type {
 	UpdateArtifactInput,
 	BugLog,
 	CreateBugLogInput,
-	UpdateBugLogInput
+	UpdateBugLogInput,
+	FindSimilarBugsInput,
+	SimilarBug,
+	AutosaveAttemptInput,
+	AttemptSnapshotSummary,
+	AttemptComparison
 } from '$lib/types';
 
 // Day Attempt Commands
@@ -33,12 +38,29 @@ export async function listAttempts(dayPlanId: string): Promise<DayAttemptSummary
 	return invoke<DayAttemptSummary[]>('list_attempts', { dayPlanId });
 }
 
+export async function compareAttempts(
+	baseAttemptId: string,
+	targetAttemptId: string
+): Promise<AttemptComparison> {
+	return invoke<AttemptComparison>('compare_attempts', { baseAttemptId, targetAttemptId });
+}
+
 export async function updateAttempt(id: string, input: UpdateDayAttemptInput): Promise<DayAttempt> {
 	return invoke<DayAttempt>('update_attempt', { id, input });
 }
 
-export async function autosaveAttempt(id: string): Promise<void> {
-	return invoke<void>('autosave_attempt', { id });
+export async function setAttemptProjectPath(
+	id: string,
+	projectPath: string | null
+): Promise<DayAttempt> {
+	return invoke<DayAttempt>('set_attempt_project_path', { id, projectPath });
+}
+
+export async function autosaveAttempt(
+	id: string,
+	input: AutosaveAttemptInput
+): Promise<AttemptSnapshotSummary> {
+	return invoke<AttemptSnapshotSummary>('autosave_attempt', { id, input });
 }
 
 export async function submitAttempt(id: string, input: SubmitScoresInput): Promise<DayAttempt> {
@@ -102,3 +124,7 @@ export async function listBugLogs(dayAttemptId: string): Promise<BugLog[]> {
 export async function deleteBugLog(id: string): Promise<void> {
 	return invoke<void>('delete_bug_log', { id });
 }
+
+export async function findSimilarBugs(input: FindSimilarBugsInput): Promise<SimilarBug[]> {
+	return invoke<SimilarBug[]>('find_similar_bugs', { input });
+}
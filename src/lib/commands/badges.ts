@@ -0,0 +1,14 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { Badge, BadgeDefinition, BadgeProgress } from '$lib/types';
+
+export async function listBadgeDefinitions(): Promise<BadgeDefinition[]> {
+	return invoke<BadgeDefinition[]>('list_badge_definitions');
+}
+
+export async function getBadgeProgress(programId: string): Promise<BadgeProgress[]> {
+	return invoke<BadgeProgress[]>('get_badge_progress', { programId });
+}
+
+export async function backfillBadges(): Promise<Badge[]> {
+	return invoke<Badge[]>('backfill_badges');
+}
@@ -0,0 +1,6 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { BugAnalytics } from '$lib/types';
+
+export async function getBugAnalytics(programId?: string): Promise<BugAnalytics> {
+	return invoke<BugAnalytics>('get_bug_analytics', { programId });
+}
@@ -0,0 +1,16 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AttemptChecklistItem, SubmissionCheck, ToggleChecklistItemInput } from '$lib/types';
+
+export async function getAttemptChecklist(dayAttemptId: string): Promise<AttemptChecklistItem[]> {
+	return invoke<AttemptChecklistItem[]>('get_attempt_checklist', { dayAttemptId });
+}
+
+export async function toggleChecklistItem(
+	input: ToggleChecklistItemInput
+): Promise<AttemptChecklistItem> {
+	return invoke<AttemptChecklistItem>('toggle_checklist_item', { input });
+}
+
+export async function validateAttemptSubmission(dayAttemptId: string): Promise<SubmissionCheck> {
+	return invoke<SubmissionCheck>('validate_attempt_submission', { dayAttemptId });
+}
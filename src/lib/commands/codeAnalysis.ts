@@ -0,0 +1,10 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AttemptScoreSuggestion } from '$lib/types';
+
+export async function analyzeAttemptCode(dayAttemptId: string): Promise<AttemptScoreSuggestion[]> {
+	return invoke<AttemptScoreSuggestion[]>('analyze_attempt_code', { dayAttemptId });
+}
+
+export async function listScoreSuggestions(dayAttemptId: string): Promise<AttemptScoreSuggestion[]> {
+	return invoke<AttemptScoreSuggestion[]>('list_score_suggestions', { dayAttemptId });
+}
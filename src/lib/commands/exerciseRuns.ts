@@ -0,0 +1,10 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ExerciseRun } from '$lib/types';
+
+export async function runExerciseEntry(exerciseEntryId: string): Promise<ExerciseRun> {
+	return invoke<ExerciseRun>('run_exercise_entry', { exerciseEntryId });
+}
+
+export async function listExerciseRuns(dayAttemptId: string): Promise<ExerciseRun[]> {
+	return invoke<ExerciseRun[]>('list_exercise_runs', { dayAttemptId });
+}
@@ -0,0 +1,10 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { CapturedGitEvidence, GitEvidence } from '$lib/types';
+
+export async function captureGitEvidence(dayAttemptId: string): Promise<CapturedGitEvidence> {
+	return invoke<CapturedGitEvidence>('capture_git_evidence', { dayAttemptId });
+}
+
+export async function getGitEvidence(dayAttemptId: string): Promise<GitEvidence | null> {
+	return invoke<GitEvidence | null>('get_git_evidence', { dayAttemptId });
+}
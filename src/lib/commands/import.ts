@@ -1,8 +1,13 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { 
+  CapacityPlan,
   ImportJob, 
   ImportJobSummary, 
-  ImportGeneratedPlan 
+  ImportGeneratedPlan,
+  ImportPlanEdit,
+  ImportQualityReport,
+  PlanEditOperation,
+  ReimportMergePlan
 } from '$lib/types';
 import type { Program } from '$lib/types';
 
@@ -29,10 +34,50 @@ export async function updateImportPreview(
   return invoke('update_import_preview', { jobId, reviewedPlanJson });
 }
 
+export async function getImportReport(jobId: string): Promise<ImportQualityReport> {
+  return invoke('get_import_report', { jobId });
+}
+
+export async function applyImportEdit(
+  jobId: string,
+  operation: PlanEditOperation
+): Promise<ImportGeneratedPlan> {
+  return invoke('apply_import_edit', { jobId, operation });
+}
+
+export async function generateCapacityPlan(
+  jobId: string,
+  startDate: string | null
+): Promise<CapacityPlan> {
+  return invoke('generate_capacity_plan', { jobId, startDate });
+}
+
+export async function undoImportEdit(jobId: string): Promise<ImportGeneratedPlan> {
+  return invoke('undo_import_edit', { jobId });
+}
+
+export async function listImportEdits(jobId: string): Promise<ImportPlanEdit[]> {
+  return invoke('list_import_edits', { jobId });
+}
+
 export async function applyImport(jobId: string): Promise<Program> {
   return invoke('apply_import', { jobId });
 }
 
+export async function previewReimport(
+  jobId: string,
+  programId: string
+): Promise<ReimportMergePlan> {
+  return invoke('preview_reimport', { jobId, programId });
+}
+
+export async function applyReimport(
+  jobId: string,
+  mergePlan: ReimportMergePlan
+): Promise<Program> {
+  return invoke('apply_reimport', { jobId, mergePlan });
+}
+
 export async function cancelImport(jobId: string): Promise<void> {
   return invoke('cancel_import', { jobId });
 }
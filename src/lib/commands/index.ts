@@ -17,3 +17,45 @@ export * from './sessions';
 
 // Import
 export * from './import';
+
+// Scoring Rubrics
+export * from './rubrics';
+
+// Quiz Engine
+export * from './quizzes';
+
+// Attempt Checklists
+export * from './checklists';
+
+// Autosave Snapshots
+export * from './snapshots';
+
+// Exercise Runs
+export * from './exerciseRuns';
+
+// Code Analysis
+export * from './codeAnalysis';
+
+// Git Evidence
+export * from './gitEvidence';
+
+// Memory Rebuild
+export * from './memoryRebuild';
+
+// Artifact Store
+export * from './artifactStore';
+
+// Bug Analytics
+export * from './bugAnalytics';
+
+// Review Scheduler
+export * from './reviewScheduler';
+
+// Review Queue
+export * from './reviewQueue';
+
+// Quiz Cards
+export * from './quizCards';
+
+// Badge Rules
+export * from './badges';
@@ -3,7 +3,9 @@ import type {
 	SpacedRepetition,
 	RecordReviewInput,
 	DueReview,
+	ForgettingAlert,
 	Streak,
+	ActivityHeatmapDay,
 	Badge,
 	SkillRadarData
 } from '$lib/types';
@@ -17,8 +19,8 @@ export async function recordReview(input: RecordReviewInput): Promise<SpacedRepe
 	return invoke<SpacedRepetition>('record_review', { input });
 }
 
-export async function getForgettingCurveAlerts(programId: string): Promise<DueReview[]> {
-	return invoke<DueReview[]>('get_forgetting_curve_alerts', { programId });
+export async function getForgettingCurveAlerts(programId: string): Promise<ForgettingAlert[]> {
+	return invoke<ForgettingAlert[]>('get_forgetting_curve_alerts', { programId });
 }
 
 // Streak Commands
@@ -34,6 +36,13 @@ export async function useStreakFreeze(programId: string, reason: string): Promis
 	return invoke<Streak>('use_streak_freeze', { programId, reason });
 }
 
+export async function getActivityHeatmap(
+	programId: string,
+	days?: number
+): Promise<ActivityHeatmapDay[]> {
+	return invoke<ActivityHeatmapDay[]>('get_activity_heatmap', { programId, days });
+}
+
 // Badge Commands
 export async function getBadges(programId: string): Promise<Badge[]> {
 	return invoke<Badge[]>('get_badges', { programId });
@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { MemoryRebuildResult, MemoryRebuildSession } from '$lib/types';
+
+export async function startMemoryRebuild(dayAttemptId: string): Promise<MemoryRebuildSession> {
+	return invoke<MemoryRebuildSession>('start_memory_rebuild', { dayAttemptId });
+}
+
+export async function submitMemoryRebuild(
+	sessionId: string,
+	rebuiltCode: string
+): Promise<MemoryRebuildResult> {
+	return invoke<MemoryRebuildResult>('submit_memory_rebuild', { sessionId, rebuiltCode });
+}
+
+export async function getMemoryRebuildResult(sessionId: string): Promise<MemoryRebuildResult> {
+	return invoke<MemoryRebuildResult>('get_memory_rebuild_result', { sessionId });
+}
+
+export async function listMemoryRebuildSessions(
+	dayAttemptId: string
+): Promise<MemoryRebuildSession[]> {
+	return invoke<MemoryRebuildSession[]>('list_memory_rebuild_sessions', { dayAttemptId });
+}
@@ -0,0 +1,29 @@
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	QuizCard,
+	QuizCardPrompt,
+	AnswerQuizCardInput,
+	QuizCardReviewResult,
+	LeechCard,
+	QuizCardSyncResult
+} from '$lib/types';
+
+export async function syncQuizCards(programId: string): Promise<QuizCardSyncResult> {
+	return invoke<QuizCardSyncResult>('sync_quiz_cards', { programId });
+}
+
+export async function getDueQuizCards(programId: string): Promise<QuizCardPrompt[]> {
+	return invoke<QuizCardPrompt[]>('get_due_quiz_cards', { programId });
+}
+
+export async function answerQuizCard(input: AnswerQuizCardInput): Promise<QuizCardReviewResult> {
+	return invoke<QuizCardReviewResult>('answer_quiz_card', { input });
+}
+
+export async function listLeechCards(programId: string): Promise<LeechCard[]> {
+	return invoke<LeechCard[]>('list_leech_cards', { programId });
+}
+
+export async function setQuizCardSuspended(id: string, suspended: boolean): Promise<QuizCard> {
+	return invoke<QuizCard>('set_quiz_card_suspended', { id, suspended });
+}
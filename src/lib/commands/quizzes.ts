@@ -0,0 +1,21 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { QuizAnswerInput, QuizResult, QuizSession, StartedQuiz } from '$lib/types';
+
+export async function startQuiz(dayAttemptId: string): Promise<StartedQuiz> {
+	return invoke<StartedQuiz>('start_quiz', { dayAttemptId });
+}
+
+export async function submitQuizAnswers(
+	sessionId: string,
+	answers: QuizAnswerInput[]
+): Promise<QuizResult> {
+	return invoke<QuizResult>('submit_quiz_answers', { sessionId, answers });
+}
+
+export async function getQuizResult(sessionId: string): Promise<QuizResult> {
+	return invoke<QuizResult>('get_quiz_result', { sessionId });
+}
+
+export async function listQuizSessions(dayAttemptId: string): Promise<QuizSession[]> {
+	return invoke<QuizSession[]>('list_quiz_sessions', { dayAttemptId });
+}
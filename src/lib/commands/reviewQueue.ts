@@ -0,0 +1,13 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ReviewSession, ReviewForecastDay } from '$lib/types';
+
+export async function buildReviewSession(programId: string): Promise<ReviewSession> {
+	return invoke<ReviewSession>('build_review_session', { programId });
+}
+
+export async function getReviewForecast(
+	programId: string,
+	days?: number
+): Promise<ReviewForecastDay[]> {
+	return invoke<ReviewForecastDay[]>('get_review_forecast', { programId, days });
+}
@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	ReviewLog,
+	ReviewSchedulerSettings,
+	UpdateReviewSchedulerInput,
+	FsrsOptimizationReport
+} from '$lib/types';
+
+export async function getReviewSchedulerSettings(): Promise<ReviewSchedulerSettings> {
+	return invoke<ReviewSchedulerSettings>('get_review_scheduler_settings');
+}
+
+export async function updateReviewSchedulerSettings(
+	input: UpdateReviewSchedulerInput
+): Promise<ReviewSchedulerSettings> {
+	return invoke<ReviewSchedulerSettings>('update_review_scheduler_settings', { input });
+}
+
+export async function listReviewLog(dayPlanId: string, conceptTagId: string): Promise<ReviewLog[]> {
+	return invoke<ReviewLog[]>('list_review_log', { dayPlanId, conceptTagId });
+}
+
+export async function optimizeFsrsParameters(apply: boolean): Promise<FsrsOptimizationReport> {
+	return invoke<FsrsOptimizationReport>('optimize_fsrs_parameters', { apply });
+}
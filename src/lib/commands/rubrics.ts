@@ -0,0 +1,20 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { AttemptDimensionScore, RubricWithDimensions, SaveRubricInput } from '$lib/types';
+
+export async function getProgramRubric(programId: string): Promise<RubricWithDimensions> {
+	return invoke<RubricWithDimensions>('get_program_rubric', { programId });
+}
+
+export async function saveProgramRubric(input: SaveRubricInput): Promise<RubricWithDimensions> {
+	return invoke<RubricWithDimensions>('save_program_rubric', { input });
+}
+
+export async function resetProgramRubric(programId: string): Promise<RubricWithDimensions> {
+	return invoke<RubricWithDimensions>('reset_program_rubric', { programId });
+}
+
+export async function getAttemptDimensionScores(
+	dayAttemptId: string
+): Promise<AttemptDimensionScore[]> {
+	return invoke<AttemptDimensionScore[]>('get_attempt_dimension_scores', { dayAttemptId });
+}
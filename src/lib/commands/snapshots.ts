@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	AttemptSnapshotSummary,
+	DayAttempt,
+	RecoverableDraft,
+	SnapshotPreview
+} from '$lib/types';
+
+export async function listAttemptSnapshots(dayAttemptId: string): Promise<AttemptSnapshotSummary[]> {
+	return invoke<AttemptSnapshotSummary[]>('list_attempt_snapshots', { dayAttemptId });
+}
+
+export async function previewAttemptSnapshot(snapshotId: string): Promise<SnapshotPreview> {
+	return invoke<SnapshotPreview>('preview_attempt_snapshot', { snapshotId });
+}
+
+export async function restoreAttemptSnapshot(snapshotId: string): Promise<DayAttempt> {
+	return invoke<DayAttempt>('restore_attempt_snapshot', { snapshotId });
+}
+
+export async function findRecoverableDrafts(): Promise<RecoverableDraft[]> {
+	return invoke<RecoverableDraft[]>('find_recoverable_drafts');
+}
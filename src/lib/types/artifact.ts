@@ -1,13 +1,17 @@
 export interface Artifact {
 	id: string;
 	day_attempt_id: string;
-	artifact_type: 'file' | 'link' | 'code' | 'note';
+	artifact_type: 'file' | 'screenshot' | 'link' | 'code' | 'note';
 	title: string;
+	description: string;
 	content: string;
 	file_path: string | null;
 	file_size: number | null;
 	mime_type: string | null;
 	url: string | null;
+	sha256: string | null;
+	storage_path: string | null;
+	thumbnail_path: string | null;
 	created_at: string;
 }
 
@@ -15,6 +19,7 @@ export interface CreateArtifactInput {
 	day_attempt_id: string;
 	artifact_type: string;
 	title: string;
+	description?: string;
 	content: string;
 	file_path?: string;
 	file_size?: number;
@@ -24,5 +29,40 @@ export interface CreateArtifactInput {
 
 export interface UpdateArtifactInput {
 	title?: string;
+	description?: string;
 	content?: string;
 }
+
+export interface ImportArtifactFileInput {
+	day_attempt_id: string;
+	source_path: string;
+	artifact_type?: 'file' | 'screenshot';
+	title?: string;
+	description?: string;
+	content?: string;
+}
+
+export interface ImportedArtifact {
+	artifact: Artifact;
+	deduplicated: boolean;
+}
+
+export interface ArtifactBlobIssue {
+	artifact_id: string;
+	storage_path: string;
+	expected_sha256: string;
+	problem: 'missing' | 'corrupted';
+	actual_sha256: string | null;
+}
+
+export interface ArtifactIntegrityReport {
+	checked: number;
+	healthy: number;
+	issues: ArtifactBlobIssue[];
+}
+
+export interface ArtifactGcReport {
+	removed_blobs: number;
+	removed_thumbnails: number;
+	reclaimed_bytes: number;
+}
@@ -18,3 +18,18 @@ export interface BadgeProgress {
 	progress_percent: number;
 	is_earned: boolean;
 }
+
+export interface BadgeDefinition {
+	badge_type: string;
+	title: string;
+	description: string;
+	icon: string;
+	/** Key of the metric the badge engine computes for this badge */
+	metric: string;
+	/** null means every day of the program */
+	threshold: number | null;
+	metadata: string;
+	sort_order: number;
+	is_active: boolean;
+	created_at: string;
+}
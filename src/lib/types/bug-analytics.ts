@@ -0,0 +1,59 @@
+export interface BugCluster {
+	category: string;
+	representative: string;
+	bug_ids: string[];
+	occurrences: number;
+	attempt_count: number;
+	program_ids: string[];
+	first_seen: string;
+	last_seen: string;
+	occurrences_this_month: number;
+	mean_time_to_fix_minutes: number | null;
+	highest_severity: string;
+}
+
+export interface RepeatOffender {
+	category: string;
+	representative: string;
+	occurrences_this_month: number;
+	lifetime_occurrences: number;
+	message: string;
+}
+
+export interface MonthlyTimeToFix {
+	month: string;
+	bug_count: number;
+	mean_minutes: number;
+}
+
+export interface TimeToFixTrend {
+	category: string;
+	months: MonthlyTimeToFix[];
+	earlier_mean_minutes: number | null;
+	recent_mean_minutes: number | null;
+	change_percent: number | null;
+	direction: 'improving' | 'worsening' | 'steady' | 'insufficient_data';
+}
+
+export interface PreventionOutcome {
+	category: string;
+	representative: string;
+	bug_id: string;
+	prevention_strategy: string;
+	logged_at: string;
+	occurrences_before: number;
+	occurrences_after: number;
+	rate_before_per_30_days: number;
+	rate_after_per_30_days: number;
+	days_observed: number;
+	verdict: 'reduced' | 'not_reduced' | 'too_early';
+}
+
+export interface BugAnalytics {
+	program_id: string | null;
+	total_bugs: number;
+	clusters: BugCluster[];
+	repeat_offenders: RepeatOffender[];
+	time_to_fix_trends: TimeToFixTrend[];
+	prevention_outcomes: PreventionOutcome[];
+}
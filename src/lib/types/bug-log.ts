@@ -32,3 +32,22 @@ export interface UpdateBugLogInput {
 	prevention_strategy?: string;
 	time_to_fix_minutes?: number;
 }
+
+export interface FindSimilarBugsInput {
+	day_attempt_id?: string;
+	symptom: string;
+	root_cause?: string;
+	exclude_bug_id?: string;
+	limit?: number;
+}
+
+export interface SimilarBug {
+	bug: BugLog;
+	program_id: string;
+	day_plan_id: string;
+	day_title: string;
+	day_number: number;
+	text_score: number;
+	shared_concept_tags: string[];
+	score: number;
+}
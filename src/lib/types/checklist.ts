@@ -24,4 +24,32 @@ export interface AttemptChecklist {
 	checklist_item_id: string;
 	is_completed: boolean;
 	completed_at: string | null;
+	artifact_id: string | null;
+	created_at: string;
+	updated_at: string;
+}
+
+export interface AttemptChecklistItem {
+	checklist_item_id: string;
+	label: string;
+	is_required: boolean;
+	order_index: number;
+	is_completed: boolean;
+	completed_at: string | null;
+	artifact_id: string | null;
+}
+
+export interface ToggleChecklistItemInput {
+	day_attempt_id: string;
+	checklist_item_id: string;
+	is_completed: boolean;
+	artifact_id?: string | null;
+}
+
+export interface SubmissionCheck {
+	enforcement: 'warn' | 'block';
+	can_submit: boolean;
+	unchecked_required: AttemptChecklistItem[];
+	completed_count: number;
+	total_count: number;
 }
@@ -0,0 +1,63 @@
+import type { BugLog } from './bug-log';
+
+export interface DiffLine {
+	kind: 'same' | 'added' | 'removed';
+	text: string;
+	base_line: number | null;
+	target_line: number | null;
+}
+
+export interface ComparedAttempt {
+	id: string;
+	attempt_number: number;
+	status: string;
+	total_score: number;
+	submitted_at: string | null;
+}
+
+export interface DimensionDelta {
+	dimension_key: string;
+	label: string;
+	base_score: number | null;
+	base_max_points: number | null;
+	target_score: number | null;
+	target_max_points: number | null;
+	delta_percent: number | null;
+}
+
+export interface TimeDelta {
+	base_minutes: number;
+	target_minutes: number;
+	delta_minutes: number;
+	base_elapsed_minutes: number | null;
+	target_elapsed_minutes: number | null;
+}
+
+export interface ExerciseDiff {
+	position: number;
+	base_entry_id: string | null;
+	target_entry_id: string | null;
+	base_language: string | null;
+	target_language: string | null;
+	code_diff: DiffLine[];
+	notes_diff: DiffLine[];
+}
+
+export interface TextFieldDiff {
+	field: string;
+	lines: DiffLine[];
+}
+
+export interface AttemptComparison {
+	day_plan_id: string;
+	base: ComparedAttempt;
+	target: ComparedAttempt;
+	total_score_delta: number;
+	dimensions: DimensionDelta[];
+	time: TimeDelta;
+	code_diff: DiffLine[];
+	exercise_diffs: ExerciseDiff[];
+	bugs_added: BugLog[];
+	bugs_removed: BugLog[];
+	reflection_diffs: TextFieldDiff[];
+}
@@ -1,9 +1,13 @@
+import type { DimensionScoreInput } from './rubric';
+
 export interface DayAttempt {
 	id: string;
 	day_plan_id: string;
 	attempt_number: number;
 	status: 'in_progress' | 'passed' | 'blocked' | 'mastery';
 	is_draft: boolean;
+	rubric_id: string | null;
+	prerequisite_override_reason: string | null;
 	score_implementation: number;
 	score_code_quality: number;
 	score_accessibility: number;
@@ -16,16 +20,21 @@ export interface DayAttempt {
 	key_learnings: string;
 	memory_rebuild_passed: boolean;
 	memory_rebuild_notes: string;
+	code_snapshot: string;
 	actual_minutes: number;
+	project_path: string | null;
 	started_at: string;
 	submitted_at: string | null;
 	last_autosave: string;
+	content_saved_at: string;
 	created_at: string;
 	updated_at: string;
 }
 
 export interface CreateDayAttemptInput {
 	day_plan_id: string;
+	/** Required to start while prerequisite dependencies are still unmet */
+	prerequisite_override_reason?: string;
 }
 
 export interface UpdateDayAttemptInput {
@@ -35,14 +44,18 @@ export interface UpdateDayAttemptInput {
 	key_learnings?: string;
 	memory_rebuild_passed?: boolean;
 	memory_rebuild_notes?: string;
+	code_snapshot?: string;
 }
 
 export interface SubmitScoresInput {
-	score_implementation: number;
-	score_code_quality: number;
-	score_accessibility: number;
-	score_performance: number;
-	score_quiz: number;
+	scores?: DimensionScoreInput[];
+	score_implementation?: number;
+	score_code_quality?: number;
+	score_accessibility?: number;
+	score_performance?: number;
+	score_quiz?: number;
+	/** Take the pending analyzer suggestions in place of the submitted scores for their dimensions */
+	accept_suggestions?: boolean;
 	daily_summary: string;
 	what_went_well: string;
 	what_to_improve: string;
@@ -4,6 +4,7 @@ export interface ExerciseEntry {
 	language: string;
 	code: string;
 	notes: string;
+	expected_output: string | null;
 	order_index: number;
 	created_at: string;
 	updated_at: string;
@@ -14,10 +15,29 @@ export interface CreateExerciseEntryInput {
 	language: string;
 	code: string;
 	notes: string;
+	expected_output?: string | null;
 }
 
 export interface UpdateExerciseEntryInput {
 	language?: string;
 	code?: string;
 	notes?: string;
+	expected_output?: string | null;
+}
+
+export interface ExerciseRun {
+	id: string;
+	exercise_entry_id: string;
+	day_attempt_id: string;
+	language: string;
+	code: string;
+	stage: 'compile' | 'run';
+	exit_code: number | null;
+	timed_out: boolean;
+	stdout: string;
+	stderr: string;
+	duration_ms: number;
+	expected_output: string | null;
+	passed: boolean | null;
+	created_at: string;
 }
@@ -0,0 +1,45 @@
+export interface GitEvidence {
+	id: string;
+	day_attempt_id: string;
+	repository_path: string;
+	branch: string;
+	/** Last commit inside the window; the tree snapshot is taken from it */
+	head_commit: string | null;
+	window_start: string;
+	window_end: string;
+	commit_count: number;
+	files_changed: number;
+	insertions: number;
+	deletions: number;
+	/** GitCommit[] as JSON, oldest first */
+	commits_json: string;
+	/** GitTreeEntry[] as JSON */
+	tree_json: string;
+	estimated_minutes: number;
+	captured_at: string;
+}
+
+export interface GitCommit {
+	hash: string;
+	author: string;
+	committed_at: string;
+	message: string;
+	files: GitFileChange[];
+}
+
+export interface GitFileChange {
+	path: string;
+	insertions: number | null;
+	deletions: number | null;
+}
+
+export interface GitTreeEntry {
+	path: string;
+	object_hash: string;
+	size_bytes: number | null;
+}
+
+export interface CapturedGitEvidence {
+	evidence: GitEvidence;
+	applied_fields: ('code_snapshot' | 'actual_minutes')[];
+}
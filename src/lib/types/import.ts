@@ -9,6 +9,8 @@ export interface ImportJob {
   ai_analysis_json: string;
   generated_plan_json: string;
   reviewed_plan_json: string | null;
+  projected_calendar_json: string | null;
+  projected_end_date: string | null;
   total_pages: number;
   total_tokens: number;
   total_days_generated: number;
@@ -58,6 +60,7 @@ export interface ImportGeneratedPlan {
   tag_assignments: [number, string][];
   dependencies: DependencyDraft[];
   validation_warnings: string[];
+  inferred_dependencies: InferredDependency[];
 }
 
 export interface ProgramDraft {
@@ -101,7 +104,7 @@ export interface ChecklistItemDraft {
 export interface QuizQuestionDraft {
   day_index: number;
   question_text: string;
-  question_type: 'short_answer' | 'multiple_choice' | 'code_prompt' | 'reflection';
+  question_type: 'short_answer' | 'multiple_choice' | 'multi_select' | 'true_false' | 'numeric' | 'code_prompt' | 'reflection';
   correct_answer: string;
   options: string[];
   points: number;
@@ -113,9 +116,155 @@ export interface ConceptTagDraft {
   domain: string;
 }
 
+export interface InferredDependency {
+  day_index: number;
+  depends_on_day_number: number;
+  minimum_score: number;
+  tag_names: string[];
+  status: 'pending' | 'accepted' | 'rejected';
+}
+
 export interface DependencyDraft {
   day_index: number;
   depends_on_day_number: number;
   dependency_type: 'prerequisite' | 'recommended';
   minimum_score: number;
 }
+
+export interface ReimportMergePlan {
+  job_id: string;
+  program_id: string;
+  modules: ModuleMatch[];
+  added: AddedDay[];
+  changed: DayMatch[];
+  moved: DayMatch[];
+  unchanged: DayMatch[];
+  removed: RemovedDay[];
+}
+
+export interface ModuleMatch {
+  module_index: number;
+  title: string;
+  existing_module_id: string | null;
+  existing_title: string | null;
+  similarity: number;
+}
+
+export interface DayMatch {
+  day_index: number;
+  existing_day_plan_id: string;
+  existing_title: string;
+  new_title: string;
+  old_day_number: number;
+  new_day_number: number;
+  old_module_id: string;
+  new_module_index: number;
+  similarity: number;
+  changed_fields: string[];
+  attempt_count: number;
+}
+
+export interface AddedDay {
+  day_index: number;
+  title: string;
+  day_number: number;
+  module_index: number;
+}
+
+export interface RemovedDay {
+  day_plan_id: string;
+  title: string;
+  day_number: number;
+  attempt_count: number;
+}
+
+export type PlanEditOperation =
+  | { op: 'rename_module'; module_index: number; title: string }
+  | { op: 'move_day'; day_index: number; module_index: number }
+  | { op: 'merge_days'; day_index: number; into_day_index: number }
+  | { op: 'split_day'; day_index: number; second_title: string }
+  | { op: 'delete_quiz_question'; question_index: number }
+  | { op: 'retag'; day_index: number; tags: ConceptTagDraft[] }
+  | { op: 'fit_to_capacity'; daily_minutes: number }
+  | { op: 'infer_dependencies' }
+  | { op: 'accept_inferred_dependency'; index: number }
+  | { op: 'reject_inferred_dependency'; index: number }
+  | { op: 'replace_plan'; plan: ImportGeneratedPlan };
+
+export interface ImportPlanEdit {
+  id: string;
+  job_id: string;
+  sequence: number;
+  operation_json: string;
+  created_at: string;
+}
+
+export interface ImportQualityReport {
+  job_id: string;
+  score: number;
+  total_sections: number;
+  covered_sections: SectionCoverage[];
+  orphaned_sections: SectionCoverage[];
+  days_without_quiz: DayIssue[];
+  days_without_checklist: DayIssue[];
+  estimated_total_days: number;
+  generated_days: number;
+  total_estimated_minutes: number;
+  estimate_clash: string | null;
+  duplicate_titles: DuplicateTitle[];
+  complexity_distribution: ComplexityBucket[];
+  validation_warnings: string[];
+}
+
+export interface SectionCoverage {
+  heading: string;
+  page_number: number;
+  covered_by_days: number[];
+  best_match: number;
+}
+
+export interface DayIssue {
+  day_index: number;
+  day_number: number;
+  title: string;
+}
+
+export interface DuplicateTitle {
+  title: string;
+  day_numbers: number[];
+}
+
+export interface ComplexityBucket {
+  level: number;
+  count: number;
+}
+
+export interface CapacityPlan {
+  plan: ImportGeneratedPlan;
+  calendar: ProjectedCalendar;
+}
+
+export interface ProjectedCalendar {
+  start_date: string;
+  end_date: string;
+  study_days: number;
+  idle_study_days: number;
+  entries: CalendarEntry[];
+  /** Deep days that can't go anywhere because the profile allows none per week */
+  unplaceable: UnplaceableDay[];
+}
+
+export interface CalendarEntry {
+  day_index: number;
+  day_number: number;
+  title: string;
+  date: string;
+  estimated_minutes: number;
+  is_deep: boolean;
+}
+
+export interface UnplaceableDay {
+  day_index: number;
+  day_number: number;
+  title: string;
+}
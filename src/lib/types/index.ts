@@ -19,3 +19,13 @@ export * from './session';
 export * from './recommendation';
 export * from './analytics';
 export * from './import';
+export * from './rubric';
+export * from './snapshot';
+export * from './comparison';
+export * from './score-suggestion';
+export * from './git-evidence';
+export * from './memory-rebuild';
+export * from './bug-analytics';
+export * from './review-scheduler';
+export * from './review-queue';
+export * from './quiz-card';
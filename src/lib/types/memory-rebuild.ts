@@ -0,0 +1,39 @@
+import type { DiffLine } from './comparison';
+
+export interface MemoryRebuildSession {
+	id: string;
+	day_attempt_id: string;
+	/** The best submitted attempt with a code snapshot when the session started */
+	reference_attempt_id: string | null;
+	/** expired means submitted past the deadline */
+	status: 'in_progress' | 'submitted' | 'expired';
+	/** 0 means untimed */
+	time_limit_minutes: number;
+	started_at: string;
+	deadline_at: string | null;
+	submitted_at: string | null;
+	elapsed_seconds: number | null;
+	rebuilt_code: string;
+	token_similarity: number | null;
+	target_coverage: number | null;
+	/** SyntaxTargetCoverage[] as JSON */
+	targets_json: string;
+	/** string[] as JSON */
+	missing_identifiers_json: string;
+	suggested_pass: boolean | null;
+	evidence: string;
+	created_at: string;
+}
+
+export interface SyntaxTargetCoverage {
+	target: string;
+	covered: boolean;
+}
+
+export interface MemoryRebuildResult {
+	session: MemoryRebuildSession;
+	targets: SyntaxTargetCoverage[];
+	missing_identifiers: string[];
+	/** Reference against rebuild, only once the session is over */
+	diff: DiffLine[];
+}
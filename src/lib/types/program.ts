@@ -1,3 +1,5 @@
+import type { DimensionAverage } from './rubric';
+
 export interface Program {
 	id: string;
 	title: string;
@@ -21,6 +23,7 @@ export interface ProgramStats {
 	average_score: number;
 	current_streak: number;
 	total_time_minutes: number;
+	dimension_averages: DimensionAverage[];
 }
 
 export interface CreateProgramInput {
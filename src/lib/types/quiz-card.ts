@@ -0,0 +1,75 @@
+import type { QuizPrompt } from './quiz';
+
+export interface QuizCard {
+	id: string;
+	quiz_question_id: string;
+	day_plan_id: string;
+	status: 'active' | 'suspended';
+	easiness_factor: number;
+	interval_days: number;
+	repetition_count: number;
+	stability: number | null;
+	difficulty: number | null;
+	last_review_date: string | null;
+	next_review_date: string;
+	last_score: number | null;
+	review_count: number;
+	lapse_count: number;
+	is_leech: boolean;
+	created_at: string;
+	updated_at: string;
+}
+
+export interface QuizCardReview {
+	id: string;
+	quiz_card_id: string;
+	scheduler: 'sm2' | 'fsrs';
+	answer: string;
+	is_correct: boolean;
+	score: number;
+	rating: 1 | 2 | 3 | 4;
+	time_taken_seconds: number;
+	elapsed_days: number;
+	scheduled_days: number;
+	stability: number;
+	difficulty: number;
+	reviewed_at: string;
+}
+
+export interface QuizCardPrompt {
+	card: QuizCard;
+	question: QuizPrompt;
+	day_title: string;
+	day_number: number;
+}
+
+export interface AnswerQuizCardInput {
+	quiz_card_id: string;
+	answer: string;
+	time_taken_seconds: number;
+}
+
+export interface LeechSuggestion {
+	action: 'rewrite_question' | 'replay_day';
+	message: string;
+}
+
+export interface QuizCardReviewResult {
+	card: QuizCard;
+	review: QuizCardReview;
+	correct_answer: string;
+	leech: LeechSuggestion | null;
+}
+
+export interface LeechCard {
+	card: QuizCard;
+	question_text: string;
+	day_title: string;
+	day_number: number;
+	suggestion: LeechSuggestion;
+}
+
+export interface QuizCardSyncResult {
+	created: number;
+	skipped_ungraded: number;
+}
@@ -1,10 +1,20 @@
+export type QuizQuestionType =
+	| 'short_answer'
+	| 'multiple_choice'
+	| 'multi_select'
+	| 'true_false'
+	| 'numeric'
+	| 'code_prompt'
+	| 'reflection';
+
 export interface QuizQuestion {
 	id: string;
 	day_plan_id: string;
 	question_text: string;
-	question_type: 'short_answer' | 'multiple_choice' | 'code_prompt' | 'reflection';
+	question_type: QuizQuestionType;
 	correct_answer: string;
 	options: string;
+	answer_tolerance: number;
 	points: number;
 	time_limit_seconds: number;
 	order_index: number;
@@ -17,6 +27,7 @@ export interface CreateQuizQuestionInput {
 	question_type: string;
 	correct_answer: string;
 	options?: string;
+	answer_tolerance?: number;
 	points: number;
 	time_limit_seconds: number;
 }
@@ -26,16 +37,61 @@ export interface UpdateQuizQuestionInput {
 	question_type?: string;
 	correct_answer?: string;
 	options?: string;
+	answer_tolerance?: number;
 	points?: number;
 	time_limit_seconds?: number;
 }
 
 export interface QuizAttempt {
 	id: string;
+	quiz_session_id: string;
 	day_attempt_id: string;
 	quiz_question_id: string;
 	answer: string;
-	is_correct: boolean;
+	is_correct: boolean | null;
+	points_earned: number;
+	points_possible: number;
 	time_taken_seconds: number;
+	timed_out: boolean;
 	submitted_at: string;
 }
+
+export interface QuizSession {
+	id: string;
+	day_attempt_id: string;
+	status: 'in_progress' | 'submitted' | 'expired';
+	started_at: string;
+	deadline_at: string | null;
+	submitted_at: string | null;
+	points_earned: number;
+	points_possible: number;
+	score_percent: number;
+	created_at: string;
+}
+
+export interface QuizPrompt {
+	id: string;
+	question_text: string;
+	question_type: QuizQuestionType;
+	options: string;
+	points: number;
+	time_limit_seconds: number;
+	order_index: number;
+}
+
+export interface StartedQuiz {
+	session: QuizSession;
+	questions: QuizPrompt[];
+}
+
+export interface QuizAnswerInput {
+	quiz_question_id: string;
+	answer: string;
+	time_taken_seconds: number;
+}
+
+export interface QuizResult {
+	session: QuizSession;
+	answers: QuizAttempt[];
+	score_quiz: number;
+}
@@ -0,0 +1,31 @@
+export interface QueuedReview {
+	id: string;
+	day_plan_id: string;
+	day_title: string;
+	day_number: number;
+	concept_tag_id: string;
+	concept_name: string;
+	domain: string;
+	next_review_date: string;
+	interval_days: number;
+	overdue_days: number;
+	predicted_recall: number;
+	priority: number;
+}
+
+export interface ReviewSession {
+	program_id: string;
+	date: string;
+	daily_review_cap: number;
+	reviewed_today: number;
+	due_count: number;
+	deferred_count: number;
+	reviews: QueuedReview[];
+}
+
+export interface ReviewForecastDay {
+	date: string;
+	due_count: number;
+	projected_reviews: number;
+	projected_backlog: number;
+}
@@ -0,0 +1,41 @@
+export type ReviewSchedulerKind = 'sm2' | 'fsrs';
+
+export interface ReviewLog {
+	id: string;
+	day_plan_id: string;
+	concept_tag_id: string;
+	scheduler: ReviewSchedulerKind;
+	score: number;
+	rating: 1 | 2 | 3 | 4;
+	elapsed_days: number;
+	scheduled_days: number;
+	stability: number;
+	difficulty: number;
+	reviewed_at: string;
+}
+
+export interface ReviewSchedulerSettings {
+	scheduler: ReviewSchedulerKind;
+	desired_retention: number;
+	maximum_interval_days: number;
+	daily_review_cap: number;
+	fsrs_parameters: number[];
+	parameters_fitted: boolean;
+}
+
+export interface UpdateReviewSchedulerInput {
+	scheduler?: ReviewSchedulerKind;
+	desired_retention?: number;
+	maximum_interval_days?: number;
+	daily_review_cap?: number;
+	reset_fsrs_parameters?: boolean;
+}
+
+export interface FsrsOptimizationReport {
+	item_count: number;
+	review_count: number;
+	log_loss_before: number;
+	log_loss_after: number;
+	parameters: number[];
+	applied: boolean;
+}
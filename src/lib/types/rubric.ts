@@ -0,0 +1,62 @@
+export interface ScoringRubric {
+	id: string;
+	program_id: string | null;
+	name: string;
+	pass_threshold: number;
+	mastery_threshold: number;
+	created_at: string;
+	updated_at: string;
+}
+
+export interface RubricDimension {
+	id: string;
+	rubric_id: string;
+	key: string;
+	label: string;
+	max_points: number;
+	weight: number;
+	order_index: number;
+}
+
+export interface RubricWithDimensions {
+	rubric: ScoringRubric;
+	dimensions: RubricDimension[];
+}
+
+export interface SaveRubricInput {
+	program_id: string;
+	name: string;
+	pass_threshold: number;
+	mastery_threshold: number;
+	dimensions: RubricDimensionInput[];
+}
+
+export interface RubricDimensionInput {
+	key: string;
+	label: string;
+	max_points: number;
+	weight: number;
+}
+
+export interface DimensionScoreInput {
+	dimension_key: string;
+	score: number;
+}
+
+export interface AttemptDimensionScore {
+	id: string;
+	day_attempt_id: string;
+	dimension_key: string;
+	label: string;
+	score: number;
+	max_points: number;
+	weight: number;
+	created_at: string;
+}
+
+export interface DimensionAverage {
+	dimension_key: string;
+	label: string;
+	average_percent: number;
+	attempt_count: number;
+}
@@ -0,0 +1,36 @@
+export interface AttemptScoreSuggestion {
+	id: string;
+	day_attempt_id: string;
+	dimension_key: string;
+	suggested_score: number;
+	max_points: number;
+	explanation: string;
+	/** AnalyzerFinding[] as JSON */
+	findings_json: string;
+	/** AnalysisMetrics as JSON */
+	metrics_json: string;
+	status: 'pending' | 'accepted' | 'overridden';
+	final_score: number | null;
+	created_at: string;
+	resolved_at: string | null;
+}
+
+export interface AnalyzerFinding {
+	tool: 'clippy' | 'eslint' | 'ruff';
+	category: 'quality' | 'performance';
+	severity: 'error' | 'warning' | 'info';
+	rule: string | null;
+	message: string;
+	file: string | null;
+	line: number | null;
+}
+
+export interface AnalysisMetrics {
+	files_analyzed: number;
+	code_lines: number;
+	branches: number;
+	max_nesting: number;
+	max_loop_nesting: number;
+	tools_run: string[];
+	tools_skipped: string[];
+}
@@ -0,0 +1,55 @@
+export interface AttemptSnapshot {
+	id: string;
+	day_attempt_id: string;
+	sequence: number;
+	daily_summary: string;
+	what_went_well: string;
+	what_to_improve: string;
+	key_learnings: string;
+	memory_rebuild_notes: string;
+	code_snapshot: string;
+	exercise_entries_json: string;
+	created_at: string;
+}
+
+export interface AttemptSnapshotSummary {
+	id: string;
+	day_attempt_id: string;
+	sequence: number;
+	code_length: number;
+	exercise_count: number;
+	created_at: string;
+}
+
+export interface ExerciseEntryDraft {
+	id: string | null;
+	language: string;
+	code: string;
+	notes: string;
+	expected_output?: string | null;
+}
+
+export interface AutosaveAttemptInput {
+	daily_summary?: string;
+	what_went_well?: string;
+	what_to_improve?: string;
+	key_learnings?: string;
+	memory_rebuild_notes?: string;
+	code_snapshot?: string;
+	exercise_entries?: ExerciseEntryDraft[];
+}
+
+export interface SnapshotPreview {
+	snapshot: AttemptSnapshot;
+	exercise_entries: ExerciseEntryDraft[];
+	changed_fields: string[];
+}
+
+export interface RecoverableDraft {
+	day_attempt_id: string;
+	day_plan_id: string;
+	day_title: string;
+	snapshot_id: string;
+	snapshot_created_at: string;
+	saved_at: string;
+}
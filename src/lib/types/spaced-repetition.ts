@@ -8,6 +8,8 @@ export interface SpacedRepetition {
 	last_review_date: string;
 	next_review_date: string;
 	last_score: number;
+	stability: number | null;
+	difficulty: number | null;
 	created_at: string;
 	updated_at: string;
 }
@@ -28,3 +30,23 @@ export interface DueReview {
 	next_review_date: string;
 	interval_days: number;
 }
+
+export interface ForgettingAlert {
+	id: string;
+	day_plan_id: string;
+	day_title: string;
+	day_number: number;
+	concept_tag_id: string;
+	concept_name: string;
+	last_review_date: string;
+	next_review_date: string;
+	elapsed_days: number;
+	predicted_recall: number;
+	risk: number;
+	importance: number;
+	dependent_day_count: number;
+	upcoming_dependent_count: number;
+	priority: number;
+	suggested_action: 'review' | 'memory_rebuild' | 'replay_day';
+	message: string;
+}
@@ -17,3 +17,26 @@ export interface StreakFreeze {
 	reason: string;
 	created_at: string;
 }
+
+export interface StudyActivity {
+	program_id: string;
+	/** YYYY-MM-DD */
+	activity_date: string;
+	attempts_submitted: number;
+	reviews_completed: number;
+	minutes_studied: number;
+	last_activity_at: string;
+}
+
+export type HeatmapDayStatus = 'studied' | 'frozen' | 'rest' | 'missed' | 'none';
+
+/** One cell of the contribution heatmap */
+export interface ActivityHeatmapDay {
+	date: string;
+	status: HeatmapDayStatus;
+	/** 0 without study, otherwise 1-4 by effort relative to the busiest day shown */
+	level: number;
+	attempts_submitted: number;
+	reviews_completed: number;
+	minutes_studied: number;
+}